@@ -0,0 +1,74 @@
+// Terminal preview
+// Renders the face with synthetic audio (a slow sine sweep, no microphone or gamepad)
+// into a VirtualLedCanvas and prints every frame with 24-bit ANSI colors, two LED rows
+// per character cell: "▀" with the upper pixel as the foreground color and the lower one
+// as the background. A quick way to see the face working without the panels:
+//
+//     cargo run --example terminal          # runs until Ctrl+C
+//     cargo run --example terminal -- 90    # 90 frames, then exits
+//
+// Needs a terminal at least 128 columns wide with true color support.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pi_mask_test::audio::{AudioLevel, AudioSynthesizer, SynthMode};
+use pi_mask_test::canvas::VirtualLedCanvas;
+use pi_mask_test::face::ProtogenFace;
+use pi_mask_test::gamepad::MaskState;
+use pi_mask_test::metrics::FaceMetrics;
+use pi_mask_test::pacing::FramePacer;
+use pi_mask_test::{PANEL_HEIGHT, PANEL_WIDTH};
+
+const TARGET_FPS: f64 = 30.0;
+
+fn main() -> io::Result<()> {
+    let frames: Option<u64> = std::env::args().nth(1).and_then(|arg| arg.parse().ok());
+
+    let audio_level = Arc::new(AudioLevel::new());
+    let mut synth = AudioSynthesizer::new(audio_level.clone(), SynthMode::Sine);
+    let mask_state = Arc::new(Mutex::new(MaskState::new()));
+    let mut protogen = ProtogenFace::new(audio_level, mask_state, Arc::new(FaceMetrics::new()));
+    let frame_secs = FramePacer::new(TARGET_FPS).frame_secs();
+    protogen.set_frame_secs(frame_secs);
+
+    // Updates and draws on this one thread (the mask splits them, see input.rs)
+    let mut renderer = protogen.renderer();
+    let mut canvas = VirtualLedCanvas::new(PANEL_WIDTH * 2, PANEL_HEIGHT);
+    let mut out = String::new();
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "\x1b[2J")?;  // Clear once, then redraw in place
+
+    let mut frame = 0;
+    while frames.is_none_or(|frames| frame < frames) {
+        let start = Instant::now();
+        synth.update(frame_secs);
+        protogen.update();
+        renderer.render_to_virtual(&mut canvas);
+
+        out.clear();
+        out.push_str("\x1b[H");
+        for y in (0..PANEL_HEIGHT).step_by(2) {
+            for x in 0..PANEL_WIDTH * 2 {
+                let (tr, tg, tb) = canvas.get(x, y);
+                let (br, bg, bb) = canvas.get(x, y + 1);
+                let _ = write!(out, "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                               tr, tg, tb, br, bg, bb);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        let _ = write!(out, "{}\x1b[K", protogen.telemetry());
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()?;
+
+        frame += 1;
+        if let Some(rest) = Duration::from_secs_f64(frame_secs).checked_sub(start.elapsed()) {
+            thread::sleep(rest);
+        }
+    }
+    writeln!(stdout, "\x1b[0m")?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+// Timed expression sequences
+// Loads a JSON script of timed face actions (e.g. for a performance) and fires each
+// action once its trigger time has passed. Times are seconds since the sequence started.
+//
+// Example script:
+// [
+//   { "trigger_time": 0.0,  "action": "set_eyes", "name": "Default Eyes" },
+//   { "trigger_time": 5.0,  "action": "set_eyes", "name": "Heart Eyes" },
+//   { "trigger_time": 10.0, "action": "trigger_accessory", "name": "Sparkle" },
+//   { "trigger_time": 20.0, "action": "play_video", "name": "intro.mp4" },
+//   { "trigger_time": 25.0, "action": "seek_video", "frame": 300 },
+//   { "trigger_time": 30.0, "action": "emote", "name": "surprised" }
+// ]
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+use serde::Deserialize;
+
+use crate::face::ProtogenFace;
+use crate::gamepad::{MaskState, VideoAction};
+use tracing::{info, warn};
+
+/// Something the sequencer can do to the face
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FaceAction {
+    SetEyes { name: String },
+    PlayVideo { name: String },
+    SeekVideo { frame: usize },
+    TriggerAccessory {
+        name: String,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+    },
+    Emote { name: String },
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A face action scheduled at a point in the sequence
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SequenceEvent {
+    pub trigger_time: f64,
+    #[serde(flatten)]
+    pub action: FaceAction,
+}
+
+/// Plays back a list of timed face actions
+pub struct TimedSequencer {
+    events: Vec<SequenceEvent>, // Sorted by trigger_time, fired events are removed
+}
+
+impl TimedSequencer {
+    pub fn new(mut events: Vec<SequenceEvent>) -> Self {
+        events.sort_by(|a, b| a.trigger_time.total_cmp(&b.trigger_time));
+        Self { events }
+    }
+
+    /// Load a sequence script from a JSON file
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        let events: Vec<SequenceEvent> = serde_json::from_str(&contents)?;
+        Ok(Self::new(events))
+    }
+
+    /// Number of events that have not fired yet
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Fire every event whose trigger time has passed
+    pub fn update(&mut self, elapsed: f64, protogen: &mut ProtogenFace,
+                  state: &Arc<Mutex<MaskState>>) {
+        let due = self.events.iter()
+            .take_while(|e| e.trigger_time <= elapsed)
+            .count();
+
+        for event in self.events.drain(..due) {
+            info!("🎬 Sequence T={:.1}s: {:?}", event.trigger_time, event.action);
+            Self::execute(event.action, protogen, state);
+        }
+    }
+
+    fn execute(action: FaceAction, protogen: &mut ProtogenFace, state: &Arc<Mutex<MaskState>>) {
+        match action {
+            FaceAction::SetEyes { name } => {
+                if !protogen.set_active_eyes(&name) {
+                    warn!("⚠️  Sequence: unknown eyes \"{}\" (available: {})",
+                          name, protogen.list_eyes().join(", "));
+                }
+            }
+            FaceAction::PlayVideo { name } => {
+                state.lock().unwrap().video_action = VideoAction::Play(name);
+            }
+            FaceAction::SeekVideo { frame } => {
+                state.lock().unwrap().video_action = VideoAction::Seek(frame);
+            }
+            FaceAction::TriggerAccessory { name, enabled } => {
+                if !protogen.set_element_enabled(&name, enabled) {
+                    warn!("⚠️  Sequence: unknown accessory \"{}\"", name);
+                }
+            }
+            FaceAction::Emote { name } => {
+                if !protogen.queue_emote(&name) {
+                    warn!("⚠️  Sequence: unknown emote \"{}\"", name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,472 @@
+// HTTP control API
+// Runs a small blocking HTTP server on its own thread. Requests are turned into
+// ApiCommands and handed to the main loop over a channel, so all face/state
+// changes still happen on the render thread (same idea as VideoAction).
+// The server is also advertised over mDNS so companion apps can find it without an IP.
+// GET /stream.mjpg is the exception to the one-thread model: each viewer is streamed
+// from its own thread, since the response never ends. GET /elements asks the render
+// thread to describe the face's elements and waits for the answer.
+
+use std::fs;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use serde::Deserialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+use crate::effects::{StrobeEffect, MAX_STROBE_HZ};
+use crate::elements::TextElement;
+use crate::face::{ElementDescription, ProtogenFace};
+use crate::gamepad::CycleEyes;
+use crate::gamepad::MaskState;
+use crate::metrics::FaceMetrics;
+use crate::profile::PROFILES_PATH;
+use crate::snapshot::MAX_SLOT_NAME_LEN;
+use crate::stream::{self, FrameFeed};
+use tracing::{info, warn};
+
+pub const DEFAULT_API_PORT: u16 = 8080;
+
+// Longest message accepted by POST /text
+const MAX_TEXT_LEN: usize = 64;
+
+// mDNS service type companion apps browse for
+const MDNS_SERVICE_TYPE: &str = "_protogen._tcp.local.";
+
+// How long GET /elements waits for the render thread to describe the elements
+const CATALOG_WAIT: Duration = Duration::from_secs(1);
+
+/// Commands sent from the HTTP thread to the main loop
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiCommand {
+    ShowText { message: String },
+    Undo,
+    SetElementEnabled { name: String, enabled: bool },
+    SetNightVision { enabled: bool },
+    Blink,
+    SetStrobe { hz: Option<f64> },
+    SaveSnapshot { name: String },
+    RecallSnapshot { name: String },
+    SwitchProfile { name: String },
+    SaveProfile { name: String },
+}
+
+#[derive(Deserialize)]
+struct TextRequest {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct EffectRequest {
+    enabled: bool,
+}
+
+#[derive(Deserialize)]
+struct StrobeRequest {
+    hz: Option<f64>,  // null or 0 turns the strobe off
+}
+
+#[derive(Deserialize)]
+struct SnapshotRequest {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ElementRequest {
+    name: String,
+    enabled: bool,
+}
+
+// Element list for GET /elements, filled in by the render thread only while a request
+// is waiting for it (the face and its elements never leave the render thread)
+#[derive(Default)]
+struct ElementCatalog {
+    elements: Mutex<(u64, Vec<ElementDescription>)>,  // Version and descriptions
+    waiting: AtomicUsize,                             // Requests waiting for a fresh list
+    updated: Condvar,
+}
+
+impl ElementCatalog {
+    // Render thread: describe the elements if a request is waiting
+    fn publish(&self, protogen: &ProtogenFace) {
+        if self.waiting.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let descriptions = protogen.describe_elements();
+        let mut elements = self.elements.lock().unwrap();
+        elements.0 += 1;
+        elements.1 = descriptions;
+        self.updated.notify_all();
+    }
+
+    // HTTP thread: wait for a list made after the request came in, None if the render
+    // loop didn't answer within CATALOG_WAIT
+    fn fetch(&self) -> Option<Vec<ElementDescription>> {
+        let elements = self.elements.lock().unwrap();
+        let version = elements.0;
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        let (elements, wait) = self.updated
+            .wait_timeout_while(elements, CATALOG_WAIT, |elements| elements.0 == version)
+            .unwrap();
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+        if wait.timed_out() { None } else { Some(elements.1.clone()) }
+    }
+}
+
+/// Handle to the running HTTP server
+pub struct ApiServer {
+    server: Arc<Server>,
+    commands: Receiver<ApiCommand>,
+    catalog: Arc<ElementCatalog>,
+    advertisement: Option<Advertisement>,  // None if mDNS couldn't start (the API still works)
+}
+
+/// mDNS announcement of the API, with the face status in its TXT record
+struct Advertisement {
+    daemon: ServiceDaemon,
+    hostname: String,
+    port: u16,
+    status: Option<(String, String)>,  // Eye type and palette last announced, None before the first
+}
+
+impl Advertisement {
+    fn start(port: u16) -> Result<Self, mdns_sd::Error> {
+        Ok(Self {
+            daemon: ServiceDaemon::new()?,
+            hostname: hostname(),
+            port,
+            status: None,
+        })
+    }
+
+    fn service_info(&self, eye_type: &str, palette: &str) -> Result<ServiceInfo, mdns_sd::Error> {
+        let properties = [
+            ("version", env!("CARGO_PKG_VERSION")),
+            ("eye_type", eye_type),
+            ("palette", palette),
+        ];
+        let info = ServiceInfo::new(MDNS_SERVICE_TYPE, &self.hostname,
+                                    &format!("{}.local.", self.hostname), (),
+                                    self.port, &properties[..])?;
+        Ok(info.enable_addr_auto())
+    }
+
+    // Announce (or re-announce) the service when the status changed since the last time
+    fn update(&mut self, eye_type: &str, palette: &str) {
+        if self.status.as_ref().is_some_and(|(eyes, pal)| eyes == eye_type && pal == palette) {
+            return;
+        }
+        let first = self.status.is_none();
+        self.status = Some((eye_type.to_string(), palette.to_string()));
+        match self.service_info(eye_type, palette).and_then(|info| self.daemon.register(info)) {
+            Ok(()) if first => info!("📡 Advertising {} as {}.local", MDNS_SERVICE_TYPE, self.hostname),
+            Ok(()) => {}
+            Err(e) => warn!("⚠️  Could not advertise the HTTP API over mDNS: {}", e),
+        }
+    }
+
+    fn stop(self) {
+        if self.status.is_some() {
+            let fullname = format!("{}.{}", self.hostname, MDNS_SERVICE_TYPE);
+            // Wait for the goodbye packets so apps drop the mask right away
+            if let Ok(done) = self.daemon.unregister(&fullname) {
+                let _ = done.recv_timeout(Duration::from_secs(1));
+            }
+        }
+        let _ = self.daemon.shutdown();
+    }
+}
+
+// This machine's host name, used as the mDNS instance and host name
+fn hostname() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|name| name.trim().to_string())
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "protogen".to_string())
+}
+
+impl ApiServer {
+    /// Bind the server and start serving requests on a background thread
+    pub fn start(port: u16, metrics: Arc<FaceMetrics>, state: Arc<Mutex<MaskState>>,
+                 feed: Arc<FrameFeed>) -> Result<Self, Box<dyn std::error::Error>> {
+        let server = Server::http(("0.0.0.0", port))
+            .map_err(|e| format!("Failed to bind HTTP API on port {}: {}", port, e))?;
+        let server = Arc::new(server);
+        let (tx, rx) = mpsc::channel();
+        let catalog = Arc::new(ElementCatalog::default());
+
+        let worker = server.clone();
+        let worker_catalog = catalog.clone();
+        thread::spawn(move || {
+            for request in worker.incoming_requests() {
+                handle_request(request, &tx, &metrics, &state, &feed, &worker_catalog);
+            }
+        });
+
+        let advertisement = Advertisement::start(port)
+            .map_err(|e| warn!("⚠️  mDNS unavailable, the mask won't be discoverable: {}", e))
+            .ok();
+
+        Ok(Self {
+            server,
+            commands: rx,
+            catalog,
+            advertisement,
+        })
+    }
+
+    /// Stop accepting requests and let the server thread exit
+    pub fn shutdown(self) {
+        self.server.unblock();
+        if let Some(advertisement) = self.advertisement {
+            advertisement.stop();
+        }
+    }
+
+    /// Keep the mDNS TXT record in step with the face (only re-announces on a change)
+    pub fn advertise_status(&mut self, eye_type: &str, palette: &str) {
+        if let Some(advertisement) = &mut self.advertisement {
+            advertisement.update(eye_type, palette);
+        }
+    }
+
+    /// Drain all commands received since the last call (non-blocking)
+    pub fn poll(&self) -> Vec<ApiCommand> {
+        self.commands.try_iter().collect()
+    }
+}
+
+fn handle_request(mut request: Request, tx: &Sender<ApiCommand>, metrics: &FaceMetrics,
+                  state: &Mutex<MaskState>, feed: &Arc<FrameFeed>, catalog: &ElementCatalog) {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() {
+        respond_json(request, 400, r#"{"error":"unreadable body"}"#);
+        return;
+    }
+
+    match (method, url.as_str()) {
+        (Method::Post, "/text") => {
+            match serde_json::from_str::<TextRequest>(&body) {
+                Ok(text) if text.message.chars().count() <= MAX_TEXT_LEN => {
+                    let _ = tx.send(ApiCommand::ShowText { message: text.message });
+                    respond_json(request, 200, r#"{"status":"ok"}"#);
+                }
+                Ok(_) => {
+                    respond_json(request, 400, r#"{"error":"message too long"}"#);
+                }
+                Err(_) => {
+                    respond_json(request, 400, r#"{"error":"expected {\"message\": \"...\"}"}"#);
+                }
+            }
+        }
+        (Method::Get, "/metrics") => {
+            let header = Header::from_bytes(&b"Content-Type"[..],
+                                            &b"text/plain; version=0.0.4"[..]).unwrap();
+            let response = Response::from_string(metrics.encode()).with_header(header);
+            let _ = request.respond(response);
+        }
+        (Method::Get, "/stream.mjpg") => {
+            match feed.subscribe() {
+                Some(stream) => {
+                    let header = Header::from_bytes(&b"Content-Type"[..],
+                                                    stream::content_type().as_bytes()).unwrap();
+                    let response = Response::new(StatusCode(200), vec![header], stream, None, None);
+                    // Runs until the viewer disconnects
+                    thread::spawn(move || {
+                        let _ = request.respond(response);
+                    });
+                }
+                None => respond_json(request, 503, r#"{"error":"too many viewers"}"#),
+            }
+        }
+        (Method::Get, "/history") => {
+            // Most recent first, read-only so it's safe to answer from this thread
+            let history: Vec<_> = state.lock().unwrap().history.iter()
+                .map(|snapshot| json!({
+                    "action": snapshot.action,
+                    "brightness": snapshot.brightness,
+                    "palette": snapshot.color_palette.name(),
+                }))
+                .collect();
+            respond_json(request, 200, &json!({ "history": history }).to_string());
+        }
+        (Method::Get, "/elements") => {
+            match catalog.fetch() {
+                Some(elements) => {
+                    respond_json(request, 200, &serde_json::to_string(&elements).unwrap());
+                }
+                None => respond_json(request, 503, r#"{"error":"face not responding"}"#),
+            }
+        }
+        (Method::Post, "/undo") => {
+            let _ = tx.send(ApiCommand::Undo);
+            respond_json(request, 200, r#"{"status":"ok"}"#);
+        }
+        (Method::Post, "/eyes/blink") => {
+            let _ = tx.send(ApiCommand::Blink);
+            respond_json(request, 200, r#"{"status":"ok"}"#);
+        }
+        (Method::Post, "/effects/night-vision") => {
+            match serde_json::from_str::<EffectRequest>(&body) {
+                Ok(effect) => {
+                    let _ = tx.send(ApiCommand::SetNightVision { enabled: effect.enabled });
+                    respond_json(request, 200, r#"{"status":"ok"}"#);
+                }
+                Err(_) => {
+                    respond_json(request, 400, r#"{"error":"expected {\"enabled\": true}"}"#);
+                }
+            }
+        }
+        (Method::Post, "/effects/strobe") => {
+            match serde_json::from_str::<StrobeRequest>(&body) {
+                Ok(strobe) => {
+                    let hz = strobe.hz.map(StrobeEffect::safe_hz).filter(|&hz| hz > 0.0);
+                    let _ = tx.send(ApiCommand::SetStrobe { hz });
+                    // Report the rate actually used, since requests above the cap are lowered
+                    respond_json(request, 200,
+                                 &json!({ "status": "ok", "hz": hz.unwrap_or(0.0) }).to_string());
+                }
+                Err(_) => {
+                    respond_json(request, 400, r#"{"error":"expected {\"hz\": 2.0}"}"#);
+                }
+            }
+        }
+        (Method::Post, "/snapshot") | (Method::Post, "/snapshot/recall") => {
+            match serde_json::from_str::<SnapshotRequest>(&body) {
+                Ok(slot) if !slot.name.is_empty()
+                    && slot.name.chars().count() <= MAX_SLOT_NAME_LEN => {
+                    let command = if url == "/snapshot" {
+                        ApiCommand::SaveSnapshot { name: slot.name }
+                    } else {
+                        ApiCommand::RecallSnapshot { name: slot.name }
+                    };
+                    let _ = tx.send(command);
+                    respond_json(request, 200, r#"{"status":"ok"}"#);
+                }
+                Ok(_) => {
+                    respond_json(request, 400, r#"{"error":"name must be 1-32 characters"}"#);
+                }
+                Err(_) => {
+                    respond_json(request, 400, r#"{"error":"expected {\"name\": \"...\"}"}"#);
+                }
+            }
+        }
+        (Method::Post, "/profile") | (Method::Post, "/profile/save") => {
+            match serde_json::from_str::<SnapshotRequest>(&body) {
+                Ok(profile) if !profile.name.is_empty()
+                    && profile.name.chars().count() <= MAX_SLOT_NAME_LEN => {
+                    let command = if url == "/profile" {
+                        ApiCommand::SwitchProfile { name: profile.name }
+                    } else {
+                        ApiCommand::SaveProfile { name: profile.name }
+                    };
+                    let _ = tx.send(command);
+                    respond_json(request, 200, r#"{"status":"ok"}"#);
+                }
+                Ok(_) => {
+                    respond_json(request, 400, r#"{"error":"name must be 1-32 characters"}"#);
+                }
+                Err(_) => {
+                    respond_json(request, 400, r#"{"error":"expected {\"name\": \"...\"}"}"#);
+                }
+            }
+        }
+        (Method::Post, "/element") => {
+            match serde_json::from_str::<ElementRequest>(&body) {
+                Ok(element) => {
+                    let _ = tx.send(ApiCommand::SetElementEnabled {
+                        name: element.name,
+                        enabled: element.enabled,
+                    });
+                    respond_json(request, 200, r#"{"status":"ok"}"#);
+                }
+                Err(_) => {
+                    respond_json(request, 400,
+                                 r#"{"error":"expected {\"name\": \"...\", \"enabled\": true}"}"#);
+                }
+            }
+        }
+        _ => respond_json(request, 404, r#"{"error":"not found"}"#),
+    }
+}
+
+fn respond_json(request: Request, status: u16, body: &str) {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header);
+    let _ = request.respond(response);
+}
+
+/// Apply pending API commands to the face (called once per frame from the main loop)
+pub fn handle_api_commands(api: &ApiServer, state: &Arc<Mutex<MaskState>>,
+                           protogen: &mut ProtogenFace) {
+    api.catalog.publish(protogen);
+    for command in api.poll() {
+        match command {
+            ApiCommand::ShowText { message } => {
+                // Replace any previous message
+                protogen.remove_element("Text");
+                if !message.is_empty() {
+                    info!("💬 Text: {}", message);
+                    protogen.add_element(Box::new(TextElement::scrolling(&message)));
+                }
+            }
+            ApiCommand::Undo => {
+                let mut s = state.lock().unwrap();
+                match s.undo() {
+                    Some(snapshot) => info!("↩️  Undo {} (API)", snapshot.action),
+                    None => info!("↩️  Nothing to undo"),
+                }
+            }
+            ApiCommand::Blink => {
+                if protogen.trigger_blink() {
+                    info!("😉 Blink (API)");
+                } else {
+                    warn!("⚠️  Blink ignored: only Default Eyes can blink on command");
+                }
+            }
+            ApiCommand::SetNightVision { enabled } => {
+                state.lock().unwrap().night_vision = enabled;
+                info!("🌙 Night vision {} (API)", if enabled { "ON" } else { "OFF" });
+            }
+            ApiCommand::SetStrobe { hz } => {
+                state.lock().unwrap().strobe_hz = hz;
+                match hz {
+                    Some(hz) => warn!("⚡ Strobe ON at {:.1} Hz (API) - flashing light, max {:.0} Hz", hz, MAX_STROBE_HZ),
+                    None => info!("⚡ Strobe OFF (API)"),
+                }
+            }
+            ApiCommand::SaveSnapshot { name } => protogen.save_snapshot(&name),
+            ApiCommand::RecallSnapshot { name } => protogen.recall_snapshot(&name),
+            ApiCommand::SwitchProfile { name } => {
+                if protogen.switch_profile(&name) {
+                    info!("🗂️  Profile: {} (API)", name);
+                } else {
+                    warn!("⚠️  No profile \"{}\" (loaded: {})", name,
+                          protogen.list_profiles().join(", "));
+                }
+            }
+            ApiCommand::SaveProfile { name } => match protogen.save_profile(&name) {
+                Ok(()) => info!("💾 Saved profile \"{}\" to {}", name, PROFILES_PATH),
+                Err(e) => warn!("⚠️  Could not save profile \"{}\": {}", name, e),
+            },
+            ApiCommand::SetElementEnabled { name, enabled } => {
+                if protogen.set_element_enabled(&name, enabled) {
+                    info!("🧩 {} {} (API)", name, if enabled { "enabled" } else { "disabled" });
+                } else {
+                    warn!("⚠️  No element named {}", name);
+                }
+            }
+        }
+    }
+}
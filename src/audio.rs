@@ -1,28 +1,127 @@
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use tracing::{error, info, info_span, warn};
+
+use crate::elements::StatusIndicators;
+use crate::elements::indicator::NO_AUDIO_DEVICE;
+use crate::PANEL_WIDTH;
 
 // Microphone constants (matching Arduino code)
 pub const SILENT_LIMIT: f64 = 0.05; // Normalized audio threshold (0.0 to 1.0)
 
+// Weight of each new sample in the smoothed level (lower = smoother, slower)
+const LEVEL_SMOOTHING: f64 = 0.2;
+
+// Beat detection: weight of each frame in the running average level, and the shortest
+// gap between beats (so one loud syllable doesn't count several times)
+const BEAT_AVERAGE_SMOOTHING: f64 = 0.05;
+const BEAT_MIN_INTERVAL_SECS: f64 = 0.25;
+
+// Startup microphone calibration: how long the noise floor is measured, and the highest
+// floor accepted (anything louder means someone was talking, not background noise)
+pub const MIC_CALIBRATION_TIME: Duration = Duration::from_secs(3);
+const MAX_NOISE_FLOOR: f64 = 0.2;
+
+// Recent raw samples kept for the waveform display: one per column across both panels
+pub const WAVEFORM_LEN: usize = (PANEL_WIDTH * 2) as usize;
+
+// Keep every Nth sample, so the buffer spans a few voice periods (~40ms at 48kHz)
+const WAVEFORM_DECIMATION: usize = 16;
+
+// Microphone noise floor measurement
+struct MicCalibration {
+    until: Option<Instant>,  // End of the measurement window (None = not measuring)
+    peak: f64,               // Loudest level seen during the window
+    noise_floor: f64,        // Level treated as silence once calibrated
+}
+
 // Audio level tracker
 pub struct AudioLevel {
     current_level: Arc<Mutex<f64>>,
+    smoothed_level: Arc<Mutex<f64>>,  // Exponential moving average of current_level
+    channel_levels: Arc<Mutex<(f64, f64)>>,  // Smoothed left and right levels (equal for mono)
     last_audio_time: Arc<Mutex<Instant>>,
+    waveform: Arc<Mutex<VecDeque<f64>>>,  // Last WAVEFORM_LEN samples (-1.0 to 1.0), oldest first
+    calibration: Arc<Mutex<MicCalibration>>,
 }
 
 impl AudioLevel {
     pub fn new() -> Self {
         Self {
             current_level: Arc::new(Mutex::new(0.0)),
+            smoothed_level: Arc::new(Mutex::new(0.0)),
+            channel_levels: Arc::new(Mutex::new((0.0, 0.0))),
             last_audio_time: Arc::new(Mutex::new(Instant::now())),
+            waveform: Arc::new(Mutex::new(VecDeque::with_capacity(WAVEFORM_LEN))),
+            calibration: Arc::new(Mutex::new(MicCalibration {
+                until: None,
+                peak: 0.0,
+                noise_floor: 0.0,
+            })),
+        }
+    }
+
+    /// Measure the microphone's noise floor for `duration` (the room should be quiet)
+    /// Levels read as 0 until it's done; afterwards the floor is removed from every reading
+    pub fn start_calibration(&self, duration: Duration) {
+        if let Ok(mut calibration) = self.calibration.lock() {
+            calibration.until = Some(Instant::now() + duration);
+            calibration.peak = 0.0;
         }
+        info!("🎚️  Calibrating microphone for {}s, stay quiet...", duration.as_secs());
+    }
+
+    // Apply the calibration to a raw RMS reading
+    fn calibrate(&self, level: f64) -> f64 {
+        let Ok(mut calibration) = self.calibration.lock() else {
+            return level;
+        };
+        if let Some(until) = calibration.until {
+            calibration.peak = calibration.peak.max(level);
+            if Instant::now() < until {
+                return 0.0;
+            }
+            calibration.until = None;
+            if calibration.peak > MAX_NOISE_FLOOR {
+                warn!("⚠️  Microphone calibration too loud (peak {:.4}), keeping noise floor {:.4}",
+                      calibration.peak, calibration.noise_floor);
+            } else {
+                calibration.noise_floor = calibration.peak;
+                info!("🎚️  Microphone calibrated: noise floor {:.4}", calibration.noise_floor);
+            }
+        }
+        remove_noise_floor(level, calibration.noise_floor)
+    }
+
+    /// Add a block of raw samples (-1.0 to 1.0) from the capture callback
+    pub fn push_samples(&self, samples: impl Iterator<Item = f32>) {
+        if let Ok(mut waveform) = self.waveform.lock() {
+            for sample in samples.step_by(WAVEFORM_DECIMATION) {
+                if waveform.len() == WAVEFORM_LEN {
+                    waveform.pop_front();
+                }
+                waveform.push_back(sample as f64);
+            }
+        }
+    }
+
+    /// Copy of the recent samples, oldest first (fewer than WAVEFORM_LEN right after startup)
+    pub fn get_samples(&self) -> Vec<f64> {
+        self.waveform.lock().map(|w| w.iter().copied().collect()).unwrap_or_default()
     }
 
     pub fn update(&self, level: f64) {
+        // A broken reading would stick in the smoothed level, count it as silence
+        let level = if level.is_finite() { level } else { 0.0 };
+        let level = self.calibrate(level);
         if let Ok(mut current) = self.current_level.lock() {
             *current = level;
         }
+        if let Ok(mut smoothed) = self.smoothed_level.lock() {
+            *smoothed += (level - *smoothed) * LEVEL_SMOOTHING;
+        }
         // Update last_audio_time if we're above threshold
         if level > SILENT_LIMIT {
             if let Ok(mut last_time) = self.last_audio_time.lock() {
@@ -31,10 +130,41 @@ impl AudioLevel {
         }
     }
 
+    /// Per-channel RMS readings from the capture callback (the same value twice for a
+    /// mono microphone); reads as silence while the noise floor is still being measured
+    pub fn update_channels(&self, left: f64, right: f64) {
+        let finite = |level: f64| if level.is_finite() { level } else { 0.0 };
+        let (left, right) = (finite(left), finite(right));
+        let noise_floor = match self.calibration.lock() {
+            Ok(calibration) if calibration.until.is_some() => return self.set_channels(0.0, 0.0),
+            Ok(calibration) => calibration.noise_floor,
+            Err(_) => 0.0,
+        };
+        self.set_channels(remove_noise_floor(left, noise_floor),
+                          remove_noise_floor(right, noise_floor));
+    }
+
+    fn set_channels(&self, left: f64, right: f64) {
+        if let Ok(mut levels) = self.channel_levels.lock() {
+            levels.0 += (left - levels.0) * LEVEL_SMOOTHING;
+            levels.1 += (right - levels.1) * LEVEL_SMOOTHING;
+        }
+    }
+
+    /// Smoothed (left, right) levels
+    pub fn get_channel_levels(&self) -> (f64, f64) {
+        self.channel_levels.lock().map(|l| *l).unwrap_or((0.0, 0.0))
+    }
+
     pub fn get_level(&self) -> f64 {
         self.current_level.lock().map(|l| *l).unwrap_or(0.0)
     }
 
+    /// Level with short spikes smoothed out, for effects that shouldn't flicker
+    pub fn get_smoothed_level(&self) -> f64 {
+        self.smoothed_level.lock().map(|l| *l).unwrap_or(0.0)
+    }
+
     pub fn seconds_since_audio(&self) -> u64 {
         self.last_audio_time.lock()
             .map(|t| t.elapsed().as_secs())
@@ -42,30 +172,195 @@ impl AudioLevel {
     }
 }
 
+// Rescale a level so the noise floor reads as 0.0 and full scale still reads as 1.0
+fn remove_noise_floor(level: f64, noise_floor: f64) -> f64 {
+    if noise_floor <= 0.0 {
+        return level;
+    }
+    ((level - noise_floor) / (1.0 - noise_floor)).clamp(0.0, 1.0)
+}
+
+// RMS of the first two channels of interleaved samples (left, right); a mono stream
+// gives the same level for both. Takes an iterator so the capture callback can convert
+// samples on the fly without allocating
+fn channel_rms(samples: impl Iterator<Item = f32>, channels: usize) -> (f64, f64) {
+    let channels = channels.max(1);
+    let mut sums = [0.0; 2];
+    let mut counts = [0usize; 2];
+    for (i, sample) in samples.enumerate() {
+        let channel = i % channels;
+        if channel < 2 {
+            sums[channel] += (sample as f64) * (sample as f64);
+            counts[channel] += 1;
+        }
+    }
+    let rms = |channel: usize| -> f64 {
+        if counts[channel] == 0 { 0.0 } else { (sums[channel] / counts[channel] as f64).sqrt() }
+    };
+    let left = rms(0);
+    let right = if channels >= 2 { rms(1) } else { left };
+    (left, right)
+}
+
+/// How far each side opens relative to the louder channel, 1.0 on the louder side
+/// None while both channels are below SILENT_LIMIT (nothing to tell apart)
+pub fn stereo_balance(left: f64, right: f64) -> Option<(f64, f64)> {
+    let louder = left.max(right);
+    if louder <= SILENT_LIMIT {
+        return None;
+    }
+    Some((left.max(0.0) / louder, right.max(0.0) / louder))
+}
+
+// Frequency bands of a block of samples, lowest first, for spectrum displays
+// A plain DFT over the (short, decimated) waveform buffer is cheap enough per frame and
+// avoids an FFT dependency. Bins are grouped on a log scale so bass doesn't get one band
+// while the highs get the rest. Empty input (no microphone) gives all-zero bands.
+pub fn spectrum_bands(samples: &[f64], bands: usize) -> Vec<f64> {
+    let mut result = vec![0.0; bands];
+    let n = samples.len();
+    let bins = n / 2;
+    if bands == 0 || bins < 2 {
+        return result;
+    }
+
+    // Magnitude of every bin except DC, scaled so a full-scale sine reads 1.0
+    let magnitudes: Vec<f64> = (1..bins)
+        .map(|k| {
+            let (mut re, mut im) = (0.0, 0.0);
+            for (i, sample) in samples.iter().enumerate() {
+                let angle = std::f64::consts::TAU * (k * i) as f64 / n as f64;
+                re += sample * angle.cos();
+                im -= sample * angle.sin();
+            }
+            (re * re + im * im).sqrt() * 2.0 / n as f64
+        })
+        .collect();
+
+    // Band edges spaced evenly in log frequency, each band at least one bin wide
+    // (with more bands than bins the top ones stay empty)
+    let top = magnitudes.len();
+    let mut start = 0;
+    for (band, value) in result.iter_mut().enumerate() {
+        let edge = (top as f64).powf((band + 1) as f64 / bands as f64).round() as usize;
+        let end = edge.max(start + 1).min(top);
+        *value = magnitudes[start.min(end)..end].iter().cloned().fold(0.0, f64::max);
+        start = end;
+    }
+    result
+}
+
+// Simple energy beat detector
+// A beat is a level clearly above the recent average (and above the silence threshold)
+#[derive(Clone)]
+pub struct BeatDetector {
+    average: f64,      // Running average of the level
+    since_beat: f64,   // Seconds since the last beat
+    sensitivity: f64,  // How far above the average counts as a beat (1.5 = 50% louder)
+}
+
+impl BeatDetector {
+    pub fn new(sensitivity: f64) -> Self {
+        Self {
+            average: 0.0,
+            since_beat: BEAT_MIN_INTERVAL_SECS,
+            sensitivity: sensitivity.max(1.0),
+        }
+    }
+
+    /// Feed this frame's level, returns true on a beat
+    pub fn update(&mut self, level: f64, dt: f64) -> bool {
+        self.since_beat += dt;
+        let beat = level > SILENT_LIMIT
+            && level > self.average * self.sensitivity
+            && self.since_beat >= BEAT_MIN_INTERVAL_SECS;
+        self.average += (level - self.average) * BEAT_AVERAGE_SMOOTHING;
+        if beat {
+            self.since_beat = 0.0;
+        }
+        beat
+    }
+}
+
+// Synthetic audio source modes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SynthMode {
+    Silent,  // Constant zero level
+    Sine,    // Slow sine wave sweeping 0.0 to 1.0
+}
+
+// Synthetic audio source for running without a microphone (dry runs, testing)
+// Feeds generated levels into an AudioLevel exactly like the capture callback does
+pub struct AudioSynthesizer {
+    audio_level: Arc<AudioLevel>,
+    mode: SynthMode,
+    phase: f64,      // Seconds since the synthesizer started
+    frequency: f64,  // Sine frequency in Hz
+}
+
+impl AudioSynthesizer {
+    pub fn new(audio_level: Arc<AudioLevel>, mode: SynthMode) -> Self {
+        Self {
+            audio_level,
+            mode,
+            phase: 0.0,
+            frequency: 0.5,
+        }
+    }
+
+    /// Advance by dt seconds and push the generated level
+    pub fn update(&mut self, dt: f64) {
+        self.phase += dt;
+        let level = match self.mode {
+            SynthMode::Silent => 0.0,
+            SynthMode::Sine => {
+                0.5 + 0.5 * (self.phase * self.frequency * std::f64::consts::TAU).sin()
+            }
+        };
+        self.audio_level.update(level);
+    }
+}
+
 // Initialize microphone capture
-pub fn start_audio_capture(audio_level: Arc<AudioLevel>) -> Result<cpal::Stream, Box<dyn std::error::Error>> {
+// Stream errors raise the NO_AUDIO_DEVICE status indicator so they're visible inside the suit
+pub fn start_audio_capture(audio_level: Arc<AudioLevel>, indicators: StatusIndicators)
+                           -> Result<cpal::Stream, Box<dyn std::error::Error>> {
     let host = cpal::default_host();
     let device = host.default_input_device()
         .ok_or("No input device available")?;
 
-    println!("Using audio input device: {}", device.name()?);
+    info!("Using audio input device: {}", device.name()?);
 
     let config = device.default_input_config()?;
-    println!("Audio config: {:?}", config);
+    info!("Audio config: {:?}", config);
 
     let audio_level_clone = audio_level.clone();
+    let channels = config.channels() as usize;
+
+    // Span entered on the audio thread for every callback
+    let audio_span = info_span!("audio");
+    let error_span = audio_span.clone();
+    let error_indicators = indicators;
 
     let stream = match config.sample_format() {
         cpal::SampleFormat::F32 => {
             device.build_input_stream(
                 &config.into(),
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    let _span = audio_span.enter();
                     // Calculate RMS amplitude (similar to Arduino analogRead)
                     let sum: f32 = data.iter().map(|&s| s * s).sum();
                     let rms = (sum / data.len() as f32).sqrt();
                     audio_level_clone.update(rms as f64);
+                    let (left, right) = channel_rms(data.iter().copied(), channels);
+                    audio_level_clone.update_channels(left, right);
+                    audio_level_clone.push_samples(data.iter().copied());
+                },
+                move |err| {
+                    let _span = error_span.enter();
+                    error!("Audio stream error: {}", err);
+                    error_indicators.show(NO_AUDIO_DEVICE);
                 },
-                |err| eprintln!("Audio stream error: {}", err),
                 None,
             )?
         }
@@ -73,6 +368,7 @@ pub fn start_audio_capture(audio_level: Arc<AudioLevel>) -> Result<cpal::Stream,
             device.build_input_stream(
                 &config.into(),
                 move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    let _span = audio_span.enter();
                     // Normalize i16 to 0.0-1.0 range and calculate RMS
                     let sum: f32 = data.iter()
                         .map(|&s| {
@@ -82,8 +378,16 @@ pub fn start_audio_capture(audio_level: Arc<AudioLevel>) -> Result<cpal::Stream,
                         .sum();
                     let rms = (sum / data.len() as f32).sqrt();
                     audio_level_clone.update(rms as f64);
+                    let normalized = data.iter().map(|&s| s as f32 / i16::MAX as f32);
+                    let (left, right) = channel_rms(normalized.clone(), channels);
+                    audio_level_clone.update_channels(left, right);
+                    audio_level_clone.push_samples(normalized);
+                },
+                move |err| {
+                    let _span = error_span.enter();
+                    error!("Audio stream error: {}", err);
+                    error_indicators.show(NO_AUDIO_DEVICE);
                 },
-                |err| eprintln!("Audio stream error: {}", err),
                 None,
             )?
         }
@@ -93,3 +397,59 @@ pub fn start_audio_capture(audio_level: Arc<AudioLevel>) -> Result<cpal::Stream,
     stream.play()?;
     Ok(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_floor_maps_to_silence_and_full_scale_is_kept() {
+        assert_eq!(remove_noise_floor(0.3, 0.0), 0.3);
+        assert_eq!(remove_noise_floor(0.02, 0.04), 0.0);
+        assert_eq!(remove_noise_floor(0.04, 0.04), 0.0);
+        assert!((remove_noise_floor(1.0, 0.04) - 1.0).abs() < 1e-9);
+        assert!((remove_noise_floor(0.52, 0.04) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn channel_levels_split_interleaved_samples() {
+        let stereo = [0.5, 0.0, -0.5, 0.0, 0.5, 0.0, -0.5, 0.0];
+        assert_eq!(channel_rms(stereo.into_iter(), 2), (0.5, 0.0));
+        assert_eq!(channel_rms([0.5, -0.5].into_iter(), 1), (0.5, 0.5));
+        assert_eq!(channel_rms(std::iter::empty(), 2), (0.0, 0.0));
+
+        assert_eq!(stereo_balance(0.4, 0.1), Some((1.0, 0.25)));
+        assert_eq!(stereo_balance(0.01, 0.02), None);
+    }
+
+    #[test]
+    fn spectrum_puts_a_tone_in_the_matching_band() {
+        let tone = |bin: f64| -> Vec<f64> {
+            (0..128).map(|i| (std::f64::consts::TAU * bin * i as f64 / 128.0).sin()).collect()
+        };
+        let low = spectrum_bands(&tone(2.0), 8);
+        let high = spectrum_bands(&tone(40.0), 8);
+
+        assert!((low[0] - 1.0).abs() < 0.05, "{:?}", low);
+        assert!(low[7] < 0.05);
+        assert!((high[7] - 1.0).abs() < 0.05, "{:?}", high);
+        assert!(high[0] < 0.05);
+        assert_eq!(spectrum_bands(&[], 8), vec![0.0; 8]);
+    }
+
+    #[test]
+    fn beat_detector_fires_on_spikes_but_not_steady_or_silent_audio() {
+        let mut detector = BeatDetector::new(1.5);
+        assert!(!detector.update(0.01, 0.033));
+
+        // Steady level settles into the average
+        for _ in 0..200 {
+            detector.update(0.2, 0.033);
+        }
+        assert!(!detector.update(0.2, 0.033));
+
+        assert!(detector.update(0.6, 0.033));
+        // Too soon after the last beat
+        assert!(!detector.update(0.6, 0.033));
+    }
+}
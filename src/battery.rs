@@ -0,0 +1,142 @@
+// Battery monitor
+// Portable setups often run from a UPS HAT with an I²C battery gauge. A background thread
+// reads the charge every 15 seconds for the battery-low icon, and stops the mask cleanly
+// (the same flag SIGTERM sets) before the cells are drained far enough to corrupt the SD
+// card. The gauge is read with rppal's I²C support (the kernel's /dev/i2c-1).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rppal::i2c::{self, I2c};
+use serde::Deserialize;
+
+use tracing::{error, info, warn};
+
+/// I²C bus the HAT is on (the header pins 3 and 5), and its device for messages
+pub const I2C_BUS: u8 = 1;
+pub const I2C_BUS_PATH: &str = "/dev/i2c-1";
+
+/// Charge (%) below which the battery-low icon flashes
+pub const BATTERY_LOW_PERCENT: u8 = 10;
+
+/// Charge (%) below which the mask shuts down
+pub const BATTERY_SHUTDOWN_PERCENT: u8 = 5;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Latest charge in percent
+pub type BatteryLevel = Arc<Mutex<u8>>;
+
+/// Battery gauge chip on the UPS HAT ("ip5306", "max17048" in config files)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatteryGauge {
+    Ip5306,    // Power bank SoC, reports charge in 25% steps
+    Max17048,  // Fuel gauge, 1% resolution (also MAX17043/MAX17044)
+}
+
+impl BatteryGauge {
+    pub fn name(&self) -> &str {
+        match self {
+            BatteryGauge::Ip5306 => "ip5306",
+            BatteryGauge::Max17048 => "max17048",
+        }
+    }
+
+    fn address(&self) -> u16 {
+        match self {
+            BatteryGauge::Ip5306 => 0x75,
+            BatteryGauge::Max17048 => 0x36,
+        }
+    }
+
+    // Register holding the charge and how many bytes to read from it
+    fn register(&self) -> (u8, usize) {
+        match self {
+            BatteryGauge::Ip5306 => (0x78, 1),  // Charge LED state
+            BatteryGauge::Max17048 => (0x04, 2),  // SOC, whole percent in the high byte
+        }
+    }
+
+    /// Charge in percent from the register contents
+    pub fn decode(&self, data: &[u8]) -> u8 {
+        match self {
+            // The four charge LEDs: more of the high bits set means fewer LEDs lit.
+            // With every LED off the charge is only known to be under 25%; report 0 so
+            // the warning and shutdown still fire
+            BatteryGauge::Ip5306 => match data[0] & 0xF0 {
+                0x00 => 100,
+                0x80 => 75,
+                0xC0 => 50,
+                0xE0 => 25,
+                _ => 0,
+            },
+            BatteryGauge::Max17048 => data[0].min(100),
+        }
+    }
+}
+
+/// Read the gauge once (so a missing HAT is reported right away), then keep reading it on
+/// a background thread; sets `shutdown` once the charge stays under BATTERY_SHUTDOWN_PERCENT
+pub fn start_battery_monitor(gauge: BatteryGauge, shutdown: Arc<AtomicBool>)
+                             -> i2c::Result<BatteryLevel> {
+    let mut device = I2c::with_bus(I2C_BUS)?;
+    device.set_slave_address(gauge.address())?;
+    let percent = read_charge(&device, gauge)?;
+    info!("🔋 Battery: {}% ({})", percent, gauge.name());
+    let level = Arc::new(Mutex::new(percent));
+
+    let shared = level.clone();
+    thread::spawn(move || {
+        let mut low = false;
+        let mut empty_readings = 0;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let percent = match read_charge(&device, gauge) {
+                Ok(percent) => percent,
+                Err(e) => {
+                    warn!("⚠️  Could not read the battery gauge: {}", e);
+                    continue;
+                }
+            };
+            *shared.lock().unwrap() = percent;
+
+            if percent < BATTERY_LOW_PERCENT && !low {
+                warn!("🪫 Battery low: {}%", percent);
+            }
+            low = percent < BATTERY_LOW_PERCENT;
+
+            // Two readings in a row, so one bad read can't end the show
+            empty_readings = if percent < BATTERY_SHUTDOWN_PERCENT { empty_readings + 1 } else { 0 };
+            if empty_readings >= 2 {
+                error!("🪫 Battery at {}%, shutting down", percent);
+                shutdown.store(true, Ordering::Relaxed);
+                return;
+            }
+        }
+    });
+    Ok(level)
+}
+
+fn read_charge(device: &I2c, gauge: BatteryGauge) -> i2c::Result<u8> {
+    let (register, len) = gauge.register();
+    let mut data = [0u8; 2];
+    device.block_read(register, &mut data[..len])?;
+    Ok(gauge.decode(&data[..len]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_gauge_registers() {
+        assert_eq!(BatteryGauge::Ip5306.decode(&[0x00]), 100);
+        assert_eq!(BatteryGauge::Ip5306.decode(&[0xC3]), 50);
+        assert_eq!(BatteryGauge::Ip5306.decode(&[0xF0]), 0);
+        assert_eq!(BatteryGauge::Max17048.decode(&[42, 0x80]), 42);
+        // Freshly reset gauges can report a little over 100%
+        assert_eq!(BatteryGauge::Max17048.decode(&[103, 0]), 100);
+    }
+}
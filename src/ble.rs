@@ -0,0 +1,137 @@
+// Heart rate monitor
+// Some suit builders wear a Bluetooth LE heart rate strap or watch. A background thread
+// connects to the one set in the config (heart_rate_device, its MAC address), subscribes
+// to the standard Heart Rate Measurement characteristic and keeps the latest BPM. The
+// face turns a raised heart rate into a more excited expression and calms back down at
+// rest. btleplug is async, so the thread runs its own single-threaded tokio runtime and
+// reconnects whenever the device drops out.
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use btleplug::api::bleuuid::uuid_from_u16;
+use btleplug::api::{BDAddr, Central, Manager as _, Peripheral as _, ScanFilter};
+use btleplug::platform::{Adapter, Manager, Peripheral};
+use futures::stream::StreamExt;
+
+use tracing::{info, warn};
+
+// Heart Rate Measurement characteristic (Bluetooth SIG assigned number)
+const HEART_RATE_MEASUREMENT: u16 = 0x2A37;
+
+// How long to scan for the device, and the pause before trying again after losing it
+const SCAN_SECS: u32 = 10;
+const RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Latest heart rate in beats per minute, 0 while not connected
+pub type HeartRate = Arc<Mutex<u32>>;
+
+/// Start following the heart rate monitor with this MAC address ("AA:BB:CC:DD:EE:FF")
+/// on a background thread; fails only if the address doesn't parse
+pub fn start_heart_rate_monitor(address: &str) -> Result<HeartRate, Box<dyn Error>> {
+    let address: BDAddr = address.parse()?;
+    let heart_rate = Arc::new(Mutex::new(0));
+    let shared = heart_rate.clone();
+    thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                warn!("⚠️  Could not start the heart rate monitor: {}", e);
+                return;
+            }
+        };
+        runtime.block_on(async {
+            loop {
+                if let Err(e) = follow_heart_rate(address, &shared).await {
+                    warn!("⚠️  Heart rate monitor {}: {}", address, e);
+                }
+                *shared.lock().unwrap() = 0;
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        });
+    });
+    Ok(heart_rate)
+}
+
+// Connect and store every measurement until the device goes away
+async fn follow_heart_rate(address: BDAddr, heart_rate: &HeartRate) -> Result<(), Box<dyn Error>> {
+    let manager = Manager::new().await?;
+    let adapter = manager.adapters().await?.into_iter().next()
+        .ok_or("no Bluetooth adapter")?;
+    adapter.start_scan(ScanFilter::default()).await?;
+    let device = find_device(&adapter, address).await;
+    adapter.stop_scan().await?;
+    let device = device.ok_or("not found (is it on and not paired with a phone?)")?;
+
+    device.connect().await?;
+    device.discover_services().await?;
+    let measurement = device.characteristics().into_iter()
+        .find(|characteristic| characteristic.uuid == uuid_from_u16(HEART_RATE_MEASUREMENT))
+        .ok_or("no heart rate measurement characteristic")?;
+    device.subscribe(&measurement).await?;
+    info!("❤️  Heart rate monitor {} connected", address);
+
+    let mut notifications = device.notifications().await?;
+    while let Some(notification) = notifications.next().await {
+        if notification.uuid == measurement.uuid {
+            if let Some(bpm) = parse_heart_rate(&notification.value) {
+                *heart_rate.lock().unwrap() = bpm;
+            }
+        }
+    }
+    Err("disconnected".into())
+}
+
+// Look for the device among the scan results for up to SCAN_SECS
+async fn find_device(adapter: &Adapter, address: BDAddr) -> Option<Peripheral> {
+    for _ in 0..SCAN_SECS {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let found = adapter.peripherals().await.ok()?.into_iter()
+            .find(|peripheral| peripheral.address() == address);
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// BPM from a Heart Rate Measurement value: a flags byte, then the rate as one byte,
+/// or two (little-endian) when bit 0 of the flags is set
+pub fn parse_heart_rate(value: &[u8]) -> Option<u32> {
+    let flags = *value.first()?;
+    if flags & 0x01 != 0 {
+        Some(u16::from_le_bytes([*value.get(1)?, *value.get(2)?]) as u32)
+    } else {
+        value.get(1).map(|&bpm| bpm as u32)
+    }
+}
+
+/// How excited the face looks for a heart rate: 0.0 at or below `rest_bpm`, rising to
+/// 1.0 at `excited_bpm` (0 BPM, no reading, counts as calm)
+pub fn excitement(bpm: u32, rest_bpm: u32, excited_bpm: u32) -> f64 {
+    if bpm <= rest_bpm {
+        return 0.0;
+    }
+    let span = excited_bpm.saturating_sub(rest_bpm).max(1) as f64;
+    ((bpm - rest_bpm) as f64 / span).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_measurements_and_maps_excitement() {
+        assert_eq!(parse_heart_rate(&[0x00, 72]), Some(72));
+        // 16-bit format, with RR intervals after it
+        assert_eq!(parse_heart_rate(&[0x11, 0x2C, 0x01, 0x10, 0x03]), Some(300));
+        assert_eq!(parse_heart_rate(&[0x01, 0x2C]), None);
+        assert_eq!(parse_heart_rate(&[]), None);
+
+        assert_eq!(excitement(0, 70, 120), 0.0);
+        assert_eq!(excitement(70, 70, 120), 0.0);
+        assert_eq!(excitement(95, 70, 120), 0.5);
+        assert_eq!(excitement(180, 70, 120), 1.0);
+    }
+}
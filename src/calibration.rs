@@ -0,0 +1,210 @@
+// Gamepad calibration wizard
+// Walks through every control one at a time ("PRESS MUTE", "PULL MOUTH TRIGGER", ...),
+// records the raw event code the controller sends for each, and saves the result so
+// arbitrary controllers work without gilrs knowing their layout. Prompts are shown on
+// the panel with the text element, so it can be run from inside the suit.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::time::{Duration, Instant};
+use gilrs::{Button, Event, EventType, Gilrs};
+use gilrs::ev::Code;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::gamepad::{axis_code, AxisTravel, LearnedTrigger};
+
+pub const DEFAULT_BUTTON_MAP_PATH: &str = "gamepad_map.toml";
+
+// A step with no input for this long is skipped (keeps gilrs's own mapping for it)
+const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Every remappable button: name in the map file, the role it plays, and the prompt
+const BUTTON_STEPS: [(&str, Button, &str); 15] = [
+    ("south", Button::South, "MUTE MIC"),
+    ("east", Button::East, "MANUAL MOUTH"),
+    ("north", Button::North, "BLINK"),
+    ("west", Button::West, "PALETTE"),
+    ("dpad_up", Button::DPadUp, "BRIGHTER"),
+    ("dpad_down", Button::DPadDown, "DIMMER"),
+    ("dpad_right", Button::DPadRight, "NEXT EYES"),
+    ("dpad_left", Button::DPadLeft, "PREV EYES"),
+    ("right_thumb", Button::RightThumb, "MOUTH STYLE"),
+    ("left_thumb", Button::LeftThumb, "BLEP"),
+    ("start", Button::Start, "VIDEO"),
+    ("select", Button::Select, "VIDEO COLORS"),
+    ("mode", Button::Mode, "GLITCH"),
+    ("right_trigger", Button::RightTrigger, "SLIDESHOW"),
+    ("left_trigger", Button::LeftTrigger, "EXIT SLIDES"),
+];
+
+/// Name of a button in map and state files ("south", "dpad_up", ...)
+pub fn button_name(button: Button) -> Option<&'static str> {
+    BUTTON_STEPS.iter().find(|(_, b, _)| *b == button).map(|(name, _, _)| *name)
+}
+
+/// Button with the given file name, the reverse of `button_name`
+pub fn button_by_name(name: &str) -> Option<Button> {
+    BUTTON_STEPS.iter().find(|(n, _, _)| *n == name).map(|(_, button, _)| *button)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ButtonMapFile {
+    buttons: BTreeMap<String, u32>,  // Button name -> raw event code
+}
+
+/// Raw button codes learned by the wizard, applied on top of gilrs's mapping
+#[derive(Debug, Clone, Default)]
+pub struct ButtonMap {
+    buttons: HashMap<u32, Button>,
+}
+
+impl ButtonMap {
+    /// Load a map file, an empty map if it is missing or invalid
+    pub fn load_or_default(path: &str) -> Self {
+        let file = match fs::read_to_string(path) {
+            Ok(contents) => match toml::from_str::<ButtonMapFile>(&contents) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("⚠️  Could not parse gamepad map {}: {}", path, e);
+                    return Self::default();
+                }
+            },
+            Err(e) => {
+                if e.kind() != ErrorKind::NotFound {
+                    warn!("⚠️  Could not read gamepad map {}: {}", path, e);
+                }
+                return Self::default();
+            }
+        };
+
+        let map = Self::from_file(&file);
+        info!("🎮 Loaded {} calibrated button(s) from {}", map.buttons.len(), path);
+        map
+    }
+
+    fn from_file(file: &ButtonMapFile) -> Self {
+        let buttons = BUTTON_STEPS.iter()
+            .filter_map(|(name, button, _)| file.buttons.get(*name).map(|code| (*code, *button)))
+            .collect();
+        Self { buttons }
+    }
+
+    /// The role of a pressed button: the calibrated one if its code was learned,
+    /// otherwise whatever gilrs mapped it to
+    pub fn remap(&self, button: Button, code: Code) -> Button {
+        self.buttons.get(&axis_code(code)).copied().unwrap_or(button)
+    }
+}
+
+// What the current step waits for
+enum Step {
+    Button(usize),  // Index into BUTTON_STEPS
+    Trigger,
+}
+
+/// Step-by-step capture of every control's raw code
+pub struct CalibrationWizard {
+    step: Option<Step>,       // None once every step is done
+    step_started: Instant,
+    buttons: BTreeMap<String, u32>,
+    trigger_travel: HashMap<u32, AxisTravel>,
+    trigger: Option<LearnedTrigger>,
+}
+
+impl CalibrationWizard {
+    pub fn new() -> Self {
+        info!("🎯 Gamepad calibration started");
+        Self {
+            step: Some(Step::Button(0)),
+            step_started: Instant::now(),
+            buttons: BTreeMap::new(),
+            trigger_travel: HashMap::new(),
+            trigger: None,
+        }
+    }
+
+    /// Text to show for the current step
+    pub fn prompt(&self) -> String {
+        match self.step {
+            Some(Step::Button(i)) => format!("PRESS {}", BUTTON_STEPS[i].2),
+            Some(Step::Trigger) => "PULL MOUTH TRIGGER".to_string(),
+            None => "CALIBRATION DONE".to_string(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step.is_none()
+    }
+
+    /// Trigger settings learned in the last step, if it wasn't skipped
+    pub fn learned_trigger(&self) -> Option<LearnedTrigger> {
+        self.trigger
+    }
+
+    /// Consume pending gamepad events, returns true when the step changed
+    pub fn poll(&mut self, gilrs: &mut Gilrs) -> bool {
+        let mut changed = false;
+        while let Some(Event { event, .. }) = gilrs.next_event() {
+            if self.handle_event(event) {
+                changed = true;
+            }
+        }
+        if !self.is_finished() && self.step_started.elapsed() >= STEP_TIMEOUT {
+            info!("⏭️  No input, skipping: {}", self.prompt());
+            self.advance();
+            changed = true;
+        }
+        changed
+    }
+
+    fn handle_event(&mut self, event: EventType) -> bool {
+        match (&self.step, event) {
+            (Some(Step::Button(i)), EventType::ButtonPressed(_, code)) => {
+                let code = axis_code(code);
+                // A code already taken is a repeat press of an earlier button
+                if self.buttons.values().any(|&c| c == code) {
+                    return false;
+                }
+                let name = BUTTON_STEPS[*i].0;
+                info!("🎯 {} = code {}", name, code);
+                self.buttons.insert(name.to_string(), code);
+                self.advance();
+                true
+            }
+            (Some(Step::Trigger), EventType::AxisChanged(_, value, code)) => {
+                let code = axis_code(code);
+                let value = value as f64;
+                let axis = self.trigger_travel.entry(code).or_insert_with(|| AxisTravel::new(value));
+                if !axis.observe(value) {
+                    return false;
+                }
+                let learned = axis.learned(code);
+                info!("🎯 Mouth trigger = axis code {}, {} range{}", learned.axis_code,
+                      learned.range.name(), if learned.invert { ", inverted" } else { "" });
+                self.trigger = Some(learned);
+                self.advance();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn advance(&mut self) {
+        self.step = match self.step {
+            Some(Step::Button(i)) if i + 1 < BUTTON_STEPS.len() => Some(Step::Button(i + 1)),
+            Some(Step::Button(_)) => Some(Step::Trigger),
+            _ => None,
+        };
+        self.step_started = Instant::now();
+    }
+
+    /// Write the learned button codes to a map file and return them as a ButtonMap
+    pub fn save(&self, path: &str) -> Result<ButtonMap, Box<dyn std::error::Error>> {
+        let file = ButtonMapFile { buttons: self.buttons.clone() };
+        fs::write(path, toml::to_string(&file)?)?;
+        info!("💾 Saved {} calibrated button(s) to {}", file.buttons.len(), path);
+        Ok(ButtonMap::from_file(&file))
+    }
+}
@@ -0,0 +1,104 @@
+// Virtual LED canvas
+// In-memory framebuffer with the same set/clear interface as the hardware canvas.
+// The face renders into one of these and it is copied to the matrix once per frame,
+// which also lets the face render without any hardware (dry runs, tests).
+
+use rpi_led_matrix::{LedCanvas, LedColor};
+use crate::color::ColorOrder;
+
+/// RGB framebuffer covering the full chained display
+pub struct VirtualLedCanvas {
+    width: i32,
+    height: i32,
+    pixels: Vec<(u8, u8, u8)>,
+}
+
+impl VirtualLedCanvas {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![(0, 0, 0); (width.max(0) * height.max(0)) as usize],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Set a pixel (out-of-range coordinates are ignored, like the hardware canvas)
+    pub fn set(&mut self, x: i32, y: i32, color: &LedColor) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = (color.red, color.green, color.blue);
+        }
+    }
+
+    /// Get a pixel as (r, g, b), black if out of range
+    pub fn get(&self, x: i32, y: i32) -> (u8, u8, u8) {
+        self.index(x, y)
+            .map(|index| self.pixels[index])
+            .unwrap_or((0, 0, 0))
+    }
+
+    /// Replace every pixel with `f((r, g, b))`, for full-frame color post-processing
+    pub fn map_pixels<F: FnMut((u8, u8, u8)) -> (u8, u8, u8)>(&mut self, mut f: F) {
+        for pixel in self.pixels.iter_mut() {
+            *pixel = f(*pixel);
+        }
+    }
+
+    /// Draw the cross-fade of two same-sized layers onto this canvas: `alpha` 0.0 shows
+    /// `from`, 1.0 shows `to`. Pixels black in both layers leave this canvas untouched
+    pub fn blend_layers(&mut self, from: &VirtualLedCanvas, to: &VirtualLedCanvas, alpha: f64) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * alpha).round() as u8;
+        for (index, pixel) in self.pixels.iter_mut().enumerate() {
+            let (a, b) = match (from.pixels.get(index), to.pixels.get(index)) {
+                (Some(&a), Some(&b)) => (a, b),
+                _ => continue,
+            };
+            if a == (0, 0, 0) && b == (0, 0, 0) {
+                continue;
+            }
+            *pixel = (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pixels.fill((0, 0, 0));
+    }
+
+    /// Number of pixels that are not black
+    pub fn lit_pixel_count(&self) -> usize {
+        self.pixels.iter().filter(|&&p| p != (0, 0, 0)).count()
+    }
+
+    /// Copy the pixels out as an image (RGB, one image pixel per LED)
+    pub fn to_rgb_image(&self) -> image::RgbImage {
+        image::RgbImage::from_fn(self.width.max(0) as u32, self.height.max(0) as u32, |x, y| {
+            let (red, green, blue) = self.get(x as i32, y as i32);
+            image::Rgb([red, green, blue])
+        })
+    }
+
+    /// Copy every pixel to a hardware canvas, in the panels' channel order
+    pub fn blit_to(&self, canvas: &mut LedCanvas, order: ColorOrder) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (red, green, blue) = self.get(x, y);
+                canvas.set(x, y, &order.apply(red, green, blue));
+            }
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || x >= self.width || y < 0 || y >= self.height {
+            return None;
+        }
+        Some((y * self.width + x) as usize)
+    }
+}
@@ -0,0 +1,140 @@
+// Command-line argument parsing
+// Flags are intentionally simple (no external parser) to keep the Pi build light
+
+use crate::api::DEFAULT_API_PORT;
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::osc::DEFAULT_OSC_PORT;
+use crate::profile::PROFILES_PATH;
+use tracing::warn;
+
+/// Parsed command-line options
+#[derive(Debug, Clone)]
+pub struct CliArgs {
+    pub debug_overlay: bool,  // Draw element bounding boxes and names over the face
+    pub api_port: u16,        // HTTP control API port
+    pub osc_port: u16,        // UDP port for OSC messages
+    pub config_path: String,  // TOML config file
+    pub sequence_path: Option<String>,  // JSON timed expression script
+    pub profile: Option<String>,  // Profile from profiles.toml to start with
+    pub dry_run: bool,        // Validate config and elements, then exit without touching hardware
+    pub learn_trigger: bool,  // Record the mouth trigger axis at startup and save it to the config
+    pub calibrate: bool,      // Start in the gamepad calibration wizard
+    pub test_panel: bool,     // Show panel diagnostic patterns, then exit
+    pub skip_calibration: bool,  // Don't measure the microphone noise floor at startup
+    pub seed: Option<u64>,    // Fixed RNG seed for reproducible runs (overrides the config)
+    pub frame_log: Option<String>,  // JSONL file for the per-frame debug log
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        Self {
+            debug_overlay: false,
+            api_port: DEFAULT_API_PORT,
+            osc_port: DEFAULT_OSC_PORT,
+            config_path: DEFAULT_CONFIG_PATH.to_string(),
+            sequence_path: None,
+            profile: None,
+            dry_run: false,
+            learn_trigger: false,
+            calibrate: false,
+            test_panel: false,
+            skip_calibration: false,
+            seed: None,
+            frame_log: None,
+        }
+    }
+}
+
+impl CliArgs {
+    /// Parse arguments from the current process
+    pub fn parse() -> Self {
+        Self::from_args(std::env::args().skip(1))
+    }
+
+    fn from_args<I: Iterator<Item = String>>(mut args: I) -> Self {
+        let mut cli = CliArgs::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--debug-overlay" => cli.debug_overlay = true,
+                "--dry-run" => cli.dry_run = true,
+                "--learn-trigger" => cli.learn_trigger = true,
+                "--calibrate" => cli.calibrate = true,
+                "--test-panel" => cli.test_panel = true,
+                "--skip-calibration" => cli.skip_calibration = true,
+                "--api-port" => {
+                    match args.next().and_then(|v| v.parse().ok()) {
+                        Some(port) => cli.api_port = port,
+                        None => warn!("⚠️  --api-port expects a port number"),
+                    }
+                }
+                "--osc-port" => {
+                    match args.next().and_then(|v| v.parse().ok()) {
+                        Some(port) => cli.osc_port = port,
+                        None => warn!("⚠️  --osc-port expects a port number"),
+                    }
+                }
+                "--seed" => {
+                    match args.next().and_then(|v| v.parse().ok()) {
+                        Some(seed) => cli.seed = Some(seed),
+                        None => warn!("⚠️  --seed expects a number"),
+                    }
+                }
+                "--config" => {
+                    match args.next() {
+                        Some(path) => cli.config_path = path,
+                        None => warn!("⚠️  --config expects a file path"),
+                    }
+                }
+                "--sequence" => {
+                    match args.next() {
+                        Some(path) => cli.sequence_path = Some(path),
+                        None => warn!("⚠️  --sequence expects a file path"),
+                    }
+                }
+                "--profile" => {
+                    match args.next() {
+                        Some(name) => cli.profile = Some(name),
+                        None => warn!("⚠️  --profile expects a profile name"),
+                    }
+                }
+                "--frame-log" => {
+                    match args.next() {
+                        Some(path) => cli.frame_log = Some(path),
+                        None => warn!("⚠️  --frame-log expects a file path"),
+                    }
+                }
+                "-h" | "--help" => {
+                    print_usage();
+                    std::process::exit(0);
+                }
+                other => {
+                    warn!("⚠️  Unknown argument: {}", other);
+                }
+            }
+        }
+
+        cli
+    }
+}
+
+/// Print available command-line flags
+fn print_usage() {
+    println!("Usage: pi_mask_test [OPTIONS]");
+    println!();
+    println!("Options:");
+    println!("  --debug-overlay   Draw element bounding boxes and names on the matrix");
+    println!("  --api-port <PORT> HTTP control API port (default {})", DEFAULT_API_PORT);
+    println!("  --osc-port <PORT> UDP port for OSC control messages (default {})", DEFAULT_OSC_PORT);
+    println!("  --config <PATH>   Config file (default {})", DEFAULT_CONFIG_PATH);
+    println!("  --sequence <PATH> Play a timed JSON expression script");
+    println!("  --profile <NAME>  Start with a profile from {}", PROFILES_PATH);
+    println!("  --dry-run         Validate config and face elements, then exit (no hardware)");
+    println!("  --learn-trigger   Pull the mouth trigger at startup to record it in the config");
+    println!("  --calibrate       Map every gamepad button step by step, with prompts on the panel");
+    println!("  --test-panel      Show color, panel order and ramp test patterns, then exit");
+    println!("  --skip-calibration  Don't measure the microphone noise floor for 3s at startup");
+    println!("  --seed <N>        Fixed random seed so sparkles, glitches etc. repeat exactly");
+    println!("  --frame-log <PATH>  Write mouth, eyelids, eyes, audio level and changes per frame as JSON lines");
+    println!("  -h, --help        Show this help");
+}
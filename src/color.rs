@@ -1,13 +1,26 @@
+use std::sync::RwLock;
 use rpi_led_matrix::LedColor;
+use serde::{Deserialize, Serialize};
 
-// Color palettes
-#[derive(Debug, Clone, Copy, PartialEq)]
+// Number of colors in each palette table
+const PALETTE_SIZE: usize = 6;
+
+// Color index span of one full trip through a palette (entries are 10 index units apart)
+pub const PALETTE_CYCLE: f64 = PALETTE_SIZE as f64 * 10.0;
+
+// Colors of the Custom palette, sampled by ColorPalette::from_image (empty until then)
+static CUSTOM_COLORS: RwLock<Vec<(u8, u8, u8)>> = RwLock::new(Vec::new());
+
+// Color palettes (lowercase names in config files: "forest", "fire", ...)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ColorPalette {
     Forest,      // Green
     Fire,        // Red/Orange
     Ocean,       // Blue/Cyan
     Purple,      // Purple/Pink
     Rainbow,     // Multi-color
+    Custom,      // Sampled from an image (palette_image), Forest until one is loaded
 }
 
 impl ColorPalette {
@@ -17,7 +30,9 @@ impl ColorPalette {
             ColorPalette::Fire => ColorPalette::Ocean,
             ColorPalette::Ocean => ColorPalette::Purple,
             ColorPalette::Purple => ColorPalette::Rainbow,
+            ColorPalette::Rainbow if has_custom_colors() => ColorPalette::Custom,
             ColorPalette::Rainbow => ColorPalette::Forest,
+            ColorPalette::Custom => ColorPalette::Forest,
         }
     }
 
@@ -28,12 +43,131 @@ impl ColorPalette {
             ColorPalette::Ocean => "Ocean (Blue/Cyan)",
             ColorPalette::Purple => "Purple/Pink",
             ColorPalette::Rainbow => "Rainbow",
+            ColorPalette::Custom => "Custom (Image)",
+        }
+    }
+
+    /// Look up a palette by its config file name ("forest", "fire", ...), any case
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "forest" => Some(ColorPalette::Forest),
+            "fire" => Some(ColorPalette::Fire),
+            "ocean" => Some(ColorPalette::Ocean),
+            "purple" => Some(ColorPalette::Purple),
+            "rainbow" => Some(ColorPalette::Rainbow),
+            "custom" => Some(ColorPalette::Custom),
+            _ => None,
         }
     }
+
+    /// Build the Custom palette from the dominant colors of an image (median cut),
+    /// ordered dark to bright like the built-in palettes
+    pub fn from_image(path: &str) -> Result<Self, image::ImageError> {
+        let image = image::open(path)?.to_rgb8();
+        // A thumbnail keeps big images quick and barely changes the averages
+        let image = image::imageops::thumbnail(&image, 64, 64);
+        let pixels: Vec<(u8, u8, u8)> = image.pixels()
+            .map(|p| (p[0], p[1], p[2]))
+            .collect();
+
+        let mut colors = median_cut(pixels, PALETTE_SIZE);
+        colors.sort_by(|a, b| luminance(a.0, a.1, a.2).total_cmp(&luminance(b.0, b.1, b.2)));
+        *CUSTOM_COLORS.write().unwrap() = colors;
+        Ok(ColorPalette::Custom)
+    }
+}
+
+fn has_custom_colors() -> bool {
+    !CUSTOM_COLORS.read().unwrap().is_empty()
+}
+
+// Reduce pixels to `count` representative colors: repeatedly split the box with the
+// widest channel range at its median, then average each box.
+// Images with fewer distinct colors repeat the last one so the table is always full
+fn median_cut(pixels: Vec<(u8, u8, u8)>, count: usize) -> Vec<(u8, u8, u8)> {
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+
+    // Widest channel of a box and its range
+    let widest = |pixels: &[(u8, u8, u8)]| -> (usize, u8) {
+        let range = |channel: fn(&(u8, u8, u8)) -> u8| {
+            let min = pixels.iter().map(channel).min().unwrap_or(0);
+            let max = pixels.iter().map(channel).max().unwrap_or(0);
+            max - min
+        };
+        let ranges = [range(|p| p.0), range(|p| p.1), range(|p| p.2)];
+        let channel = (0..3).max_by_key(|&c| ranges[c]).unwrap_or(0);
+        (channel, ranges[channel])
+    };
+
+    let mut boxes = vec![pixels];
+    while boxes.len() < count {
+        let Some((index, channel)) = boxes.iter()
+            .enumerate()
+            .filter(|(_, b)| b.len() > 1)
+            .map(|(i, b)| (i, widest(b)))
+            .filter(|(_, (_, range))| *range > 0)
+            .max_by_key(|(_, (_, range))| *range)
+            .map(|(i, (channel, _))| (i, channel)) else {
+            break;  // Every box is a single color
+        };
+
+        let mut split = boxes.swap_remove(index);
+        split.sort_by_key(|p| [p.0, p.1, p.2][channel]);
+        let upper = split.split_off(split.len() / 2);
+        boxes.push(split);
+        boxes.push(upper);
+    }
+
+    let mut colors: Vec<(u8, u8, u8)> = boxes.iter()
+        .map(|b| {
+            let n = b.len() as u32;
+            let sum = b.iter().fold((0u32, 0u32, 0u32), |s, p| {
+                (s.0 + p.0 as u32, s.1 + p.1 as u32, s.2 + p.2 as u32)
+            });
+            ((sum.0 / n) as u8, (sum.1 / n) as u8, (sum.2 / n) as u8)
+        })
+        .collect();
+    while colors.len() < count {
+        colors.push(*colors.last().unwrap());
+    }
+    colors
 }
 
 // Color palette for shimmer effect with multiple color schemes
 pub fn get_shimmer_color(color_index: f64, brightness: f64, palette: ColorPalette) -> LedColor {
+    get_shimmer_color_blend(color_index, brightness, palette, palette, 0.0)
+}
+
+// Shimmer color cross-faded between two palettes: t = 0.0 is palette_a, 1.0 is palette_b
+// Building block for palette fades (auto cycling, audio-reactive color)
+pub fn get_shimmer_color_blend(color_index: f64, brightness: f64, palette_a: ColorPalette,
+                               palette_b: ColorPalette, t: f64) -> LedColor {
+    let t = t.clamp(0.0, 1.0);
+    let (r1, g1, b1) = palette_color(color_index, palette_a);
+    // Same palette on both sides is the common single-palette case, skip the second lookup
+    let (r2, g2, b2) = if palette_b == palette_a {
+        (r1, g1, b1)
+    } else {
+        palette_color(color_index, palette_b)
+    };
+
+    let r = r1 + (r2 - r1) * t;
+    let g = g1 + (g2 - g1) * t;
+    let b = b1 + (b2 - b1) * t;
+
+    let bright_factor = (brightness / 255.0).clamp(0.0, 1.0);
+
+    LedColor {
+        red: (r * bright_factor) as u8,
+        green: (g * bright_factor) as u8,
+        blue: (b * bright_factor) as u8,
+    }
+}
+
+// Full-brightness palette color at color_index, before any brightness scaling
+fn palette_color(color_index: f64, palette: ColorPalette) -> (f64, f64, f64) {
     let colors = match palette {
         ColorPalette::Forest => vec![
             (0, 64, 0), (0, 128, 32), (32, 160, 64),
@@ -55,6 +189,13 @@ pub fn get_shimmer_color(color_index: f64, brightness: f64, palette: ColorPalett
             (255, 0, 0), (255, 128, 0), (255, 255, 0),
             (0, 255, 0), (0, 128, 255), (128, 0, 255),
         ],
+        ColorPalette::Custom => {
+            let colors = CUSTOM_COLORS.read().unwrap().clone();
+            if colors.is_empty() {
+                return palette_color(color_index, ColorPalette::Forest);
+            }
+            colors
+        }
     };
 
     // Smooth interpolation between colors
@@ -68,15 +209,202 @@ pub fn get_shimmer_color(color_index: f64, brightness: f64, palette: ColorPalett
     let (r2, g2, b2) = colors[next_index];
 
     // Linear interpolation between adjacent colors
-    let r = r1 as f64 + (r2 as f64 - r1 as f64) * blend;
-    let g = g1 as f64 + (g2 as f64 - g1 as f64) * blend;
-    let b = b1 as f64 + (b2 as f64 - b1 as f64) * blend;
+    (r1 as f64 + (r2 as f64 - r1 as f64) * blend,
+     g1 as f64 + (g2 as f64 - g1 as f64) * blend,
+     b1 as f64 + (b2 as f64 - b1 as f64) * blend)
+}
 
-    let bright_factor = (brightness / 255.0).clamp(0.0, 1.0);
+// Keep a lit pixel from vanishing at very low brightness: if truncation left its brightest
+// channel below `floor`, use `full` (the same color at full brightness) scaled so that
+// channel lands on `floor`. Channels that are 0 at full brightness stay off, so the hue holds
+pub fn apply_brightness_floor(color: LedColor, full: &LedColor, floor: u8) -> LedColor {
+    let brightest = color.red.max(color.green).max(color.blue);
+    let full_max = full.red.max(full.green).max(full.blue);
+    if brightest >= floor || full_max == 0 {
+        return color;
+    }
+    let scale = |c: u8| (c as f64 * floor as f64 / full_max as f64).ceil() as u8;
+    LedColor {
+        red: scale(full.red),
+        green: scale(full.green),
+        blue: scale(full.blue),
+    }
+}
+
+// Map a luminance value (0-255) onto the palette gradient
+// Dark pixels take the darkest palette color and bright pixels the brightest,
+// so video frames render as a monochrome image in the face's color scheme
+pub fn get_palette_color_for_luminance(luminance: f64, brightness: f64, palette: ColorPalette) -> LedColor {
+    let luminance = luminance.clamp(0.0, 255.0);
+
+    // get_shimmer_color spaces palette entries 10 index units apart; stop at the last
+    // entry (5 * 10) so the brightest pixels don't wrap back to the darkest color
+    let color_index = luminance / 255.0 * 50.0;
+
+    get_shimmer_color(color_index, luminance * brightness, palette)
+}
+
+// Fully saturated color for a hue in degrees (0-360), scaled by brightness (0.0-1.0)
+pub fn hue_to_color(hue: f64, brightness: f64) -> LedColor {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let scale = 255.0 * brightness.clamp(0.0, 1.0);
 
     LedColor {
-        red: (r * bright_factor) as u8,
-        green: (g * bright_factor) as u8,
-        blue: (b * bright_factor) as u8,
+        red: (r * scale) as u8,
+        green: (g * scale) as u8,
+        blue: (b * scale) as u8,
+    }
+}
+
+// How elements spread the palette over the face ("linear", "plasma", "radial" in config files)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShimmerMode {
+    Linear,  // Diagonal ramp sliding across the face (the original look)
+    Plasma,  // Overlapping sine waves, a slow organic flow
+    Radial,  // Rings spreading out from the middle of each panel
+}
+
+impl ShimmerMode {
+    pub fn name(&self) -> &str {
+        match self {
+            ShimmerMode::Linear => "Linear",
+            ShimmerMode::Plasma => "Plasma",
+            ShimmerMode::Radial => "Radial",
+        }
+    }
+
+    /// Palette color index for a pixel (element coordinates) at animation time `time`
+    pub fn color_index(&self, time: f64, x: f64, y: f64) -> f64 {
+        match self {
+            ShimmerMode::Linear => time + (x + y) * 5.0,
+            ShimmerMode::Plasma => {
+                let wave = (x * 0.2 + time * 0.05).sin() + (y * 0.3 + time * 0.07).sin()
+                    + ((x + y) * 0.1 + time * 0.03).sin();
+                time + wave * 12.0
+            }
+            ShimmerMode::Radial => {
+                let dx = x - crate::PANEL_WIDTH as f64 / 2.0;
+                let dy = y - crate::PANEL_HEIGHT as f64 / 2.0;
+                // Subtracting distance makes the rings travel outward as time grows
+                time - (dx * dx + dy * dy).sqrt() * 5.0
+            }
+        }
+    }
+}
+
+// Channel order of the LED panels ("RGB", "GRB", ... in config files)
+// Some panels are wired with swapped color channels; the order that makes the red
+// --test-panel step show red is the right one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ColorOrder {
+    Rgb,
+    Rbg,
+    Grb,
+    Gbr,
+    Brg,
+    Bgr,
+}
+
+impl ColorOrder {
+    pub fn name(&self) -> &str {
+        match self {
+            ColorOrder::Rgb => "RGB",
+            ColorOrder::Rbg => "RBG",
+            ColorOrder::Grb => "GRB",
+            ColorOrder::Gbr => "GBR",
+            ColorOrder::Brg => "BRG",
+            ColorOrder::Bgr => "BGR",
+        }
+    }
+
+    /// Reorder a color for the panel: its red, green and blue inputs get the channels
+    /// named by the order, e.g. GRB sends green to the red input and red to the green one
+    pub fn apply(&self, red: u8, green: u8, blue: u8) -> LedColor {
+        let (red, green, blue) = match self {
+            ColorOrder::Rgb => (red, green, blue),
+            ColorOrder::Rbg => (red, blue, green),
+            ColorOrder::Grb => (green, red, blue),
+            ColorOrder::Gbr => (green, blue, red),
+            ColorOrder::Brg => (blue, red, green),
+            ColorOrder::Bgr => (blue, green, red),
+        };
+        LedColor { red, green, blue }
+    }
+}
+
+// Perceived luminance of an RGB pixel (ITU-R BT.601 weights)
+pub fn luminance(r: u8, g: u8, b: u8) -> f64 {
+    0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_order_swaps_channels() {
+        let rgb = |c: LedColor| (c.red, c.green, c.blue);
+        assert_eq!(rgb(ColorOrder::Rgb.apply(1, 2, 3)), (1, 2, 3));
+        assert_eq!(rgb(ColorOrder::Grb.apply(1, 2, 3)), (2, 1, 3));
+        assert_eq!(rgb(ColorOrder::Bgr.apply(1, 2, 3)), (3, 2, 1));
+        assert_eq!(rgb(ColorOrder::Gbr.apply(1, 2, 3)), (2, 3, 1));
+    }
+
+    #[test]
+    fn median_cut_separates_distinct_colors() {
+        let mut pixels = vec![(255, 0, 0); 40];
+        pixels.extend(vec![(0, 0, 255); 40]);
+        let mut colors = median_cut(pixels, 2);
+        colors.sort();
+        assert_eq!(colors, vec![(0, 0, 255), (255, 0, 0)]);
+
+        // A single color fills the whole table
+        assert_eq!(median_cut(vec![(10, 20, 30); 4], 6), vec![(10, 20, 30); 6]);
+    }
+
+    #[test]
+    fn linear_shimmer_is_the_diagonal_ramp() {
+        assert_eq!(ShimmerMode::Linear.color_index(10.0, 2.0, 3.0), 35.0);
+        // Radial is the same all the way around a ring
+        let a = ShimmerMode::Radial.color_index(0.0, 32.0 + 5.0, 16.0);
+        let b = ShimmerMode::Radial.color_index(0.0, 32.0, 16.0 - 5.0);
+        assert!((a - b).abs() < 1e-9);
+    }
+
+    #[test]
+    fn brightness_floor_keeps_dim_pixels_lit() {
+        let rgb = |c: LedColor| (c.red, c.green, c.blue);
+        let full = LedColor { red: 0, green: 128, blue: 32 };
+        let dim = get_shimmer_color(10.0, 1.0, ColorPalette::Forest);
+        assert_eq!((dim.red, dim.green, dim.blue), (0, 0, 0));
+
+        assert_eq!(rgb(apply_brightness_floor(dim, &full, 2)), (0, 2, 1));
+        // Pixels already at or above the floor are left alone
+        let lit = LedColor { red: 0, green: 40, blue: 10 };
+        assert_eq!(rgb(apply_brightness_floor(lit, &full, 2)), (0, 40, 10));
+    }
+
+    #[test]
+    fn palette_blend_interpolates_between_palettes() {
+        let rgb = |c: LedColor| (c.red, c.green, c.blue);
+        let blend = |t| rgb(get_shimmer_color_blend(0.0, 255.0, ColorPalette::Forest,
+                                                     ColorPalette::Fire, t));
+        let forest = rgb(get_shimmer_color(0.0, 255.0, ColorPalette::Forest));
+        let fire = rgb(get_shimmer_color(0.0, 255.0, ColorPalette::Fire));
+
+        assert_eq!(blend(0.0), forest);
+        assert_eq!(blend(1.0), fire);
+        assert_eq!(blend(0.5), (32, 40, 0));  // Halfway between (0, 64, 0) and (64, 16, 0)
     }
 }
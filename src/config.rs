@@ -0,0 +1,318 @@
+// Runtime configuration
+// Loaded from a TOML file (protogen.toml by default, override with --config).
+// Every field has a default, so the file and any of its keys are optional.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use serde::Deserialize;
+
+use crate::battery::BatteryGauge;
+use crate::calibration::DEFAULT_BUTTON_MAP_PATH;
+use crate::color::{ColorOrder, ShimmerMode};
+use crate::elements::{IndicatorStyle, MouthBlend};
+use crate::emote::Emote;
+use crate::elements::accessories::DEFAULT_AUTO_RETRACT_SECS;
+use crate::gamepad::{ControlGroup, GamepadRoles, LearnedTrigger, TriggerAxis, TriggerRange};
+use crate::quality::QualityEffect;
+use crate::schedule::ScheduleEntry;
+use crate::video::EndOfVideoPolicy;
+use tracing::{info, warn};
+
+pub const DEFAULT_CONFIG_PATH: &str = "protogen.toml";
+
+/// Settings read from the config file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub idle_animation_interval_secs: f64,  // How often a new idle animation is picked
+    pub video_end_policy: EndOfVideoPolicy, // What to do when a video finishes
+    pub video_overlay_alpha: f64,           // Video opacity over the face (1.0 = video only)
+    pub slideshow_dwell_secs: f64,          // How long each slideshow image is shown
+    pub slideshow_crossfade_secs: f64,      // Crossfade between images (0 = hard cut)
+    pub slideshow_auto_advance: bool,       // Advance automatically after the dwell time
+    pub blep_auto_retract_secs: f64,        // How long the blep stays out (0 = until pressed again)
+    pub background_brightness: f64,         // Rainbow background, fraction of full (0 = black)
+    pub glitch_intensity: f64,              // Glitch effect strength, 0.0 to 1.0
+    pub glitch_frequency: f64,              // Average glitch bursts per second
+    pub eye_pose_distance: f64,             // Pixels the eyes shift for a look pose (Select + D-pad)
+    pub color_order: ColorOrder,            // Channel order of the panels (RGB, GRB, BGR, ...)
+    pub shimmer_mode: ShimmerMode,          // Palette flow over the face: linear, plasma or radial
+    pub anti_aliasing: bool,                // Smooth eye edges (false = hard edges, a little faster)
+    pub palette_shift_speed: f64,           // Slow palette drift, color index units per second (0 = off)
+    pub brightness_oscillation_amplitude: f64, // Brightness pulse around the set level (0 = steady)
+    pub brightness_oscillation_hz: f64,     // Brightness pulses per second
+    pub min_pixel_brightness: u8,           // Lowest channel value of a lit pixel (0 = no floor)
+    pub target_fps: f64,                    // Frame rate the main loop paces itself to
+    pub night_vision: bool,                 // Start with the green night-vision look
+    pub palette_image: Option<String>,      // Image to sample the Custom palette from
+    pub bloom_enabled: bool,                // Soft glow around lit pixels (extra work every frame)
+    pub bloom_radius: i32,                  // How far the glow reaches, in pixels (1-4)
+    pub bloom_intensity: f64,               // Glow strength next to a lit pixel, 0.0 to 1.0
+    pub eye_glow_enabled: bool,             // Soft halo around the eyes only
+    pub eye_glow_radius: i32,               // How far the halo reaches, in pixels (1-4)
+    pub eye_glow_intensity: f64,            // Halo strength next to an eye pixel, 0.0 to 1.0
+    pub adaptive_quality: bool,             // Drop optional effects while rendering is over budget
+    pub quality_shed_order: Vec<QualityEffect>,  // Which effects go first ("bloom", "eye_glow", ...)
+    pub rng_seed: Option<u64>,              // Fixed seed for random effects (None = entropy)
+    pub matrix_rain_duration_secs: f64,     // How long the matrix rain runs once triggered
+    pub fire_enabled: bool,                 // Full-screen procedural fire in place of the face
+    pub waveform_enabled: bool,             // Audio waveform across the panels instead of the face
+    pub waveform_smoothed: bool,            // Smooth the waveform (false = raw samples)
+    pub burst_enabled: bool,                // Color rings bursting from the center on beats
+    pub burst_ring_speed: f64,              // Ring growth, pixels per second
+    pub burst_ring_width: f64,              // Ring thickness in pixels
+    pub spectrum_enabled: bool,             // Audio spectrum bars along the bottom of the face
+    pub spectrum_bands: usize,              // Number of bars per panel (1-32)
+    pub spectrum_y: i32,                    // Row the bars stand on (0 = bottom edge)
+    pub pulse_breathing_enabled: bool,      // Glow behind the face that breathes while idle
+    pub nose_breathing: bool,               // Nose grows slightly as the mouth opens
+    pub mouth_emotion_curve: f64,           // Pixels the mouth corner moves for a smile/frown (Select + R1/L1)
+    pub mouth_blend: MouthBlend,            // Manual mouth mode: override, max or mix the trigger with audio
+    pub mouth_blend_weight: f64,            // Trigger share of the "mix" blend (0.0 to 1.0)
+    pub stereo_mouth: bool,                 // Left/right mouth follow the left/right mic channel
+    pub mirror_offset: i32,                 // Shift the right half's columns (+ away from center)
+    pub right_panel_brightness: f64,        // Right half brightness multiplier (1.0 = same as left)
+    pub right_panel_color_shift: f64,       // Right half palette offset (0.0 = same as left)
+    pub indicator_style: IndicatorStyle,    // Status indicator look (spinner or pulse)
+    pub indicator_x: i32,                   // Status indicator center, face coordinates
+    pub indicator_y: i32,
+    pub show_temp_warning: bool,            // Thermometer icon in the top corner while the CPU is over 80°C
+    pub show_clock: bool,                   // HH:MM in the top-right corner of the right panel
+    pub show_wifi: bool,                    // Wi-Fi signal bars in the bottom-right corner of the right panel
+    pub show_battery: bool,                 // Read a UPS HAT battery gauge: low icon under 10%, shut down under 5%
+    pub battery_gauge: BatteryGauge,        // Gauge chip on the HAT: ip5306 or max17048
+    pub mouth_trigger_range: TriggerRange,  // Trigger travel: auto, unipolar (0..1), bipolar (-1..1)
+    pub mouth_trigger_invert: bool,         // Trigger reports pressed as the low end
+    pub mouth_trigger_deadzone: f64,        // Released-end travel ignored (0.0 to 1.0)
+    pub mouth_trigger_axis_code: u32,       // evdev ABS code of an unmapped trigger axis
+    pub gamepad_mappings: Vec<String>,      // Extra SDL game controller mapping lines
+    pub gamepad_map_path: String,           // Button codes saved by the calibration wizard
+    pub gamepad_roles: HashMap<String, Vec<ControlGroup>>, // Gamepad id -> control groups it may use
+    pub emotes: Vec<Emote>,                 // Timed one-shot expressions (Select + face button)
+    pub standby_after_secs: f64,            // Quiet time (no sound or buttons) before standby, 0 = never
+    pub standby_wake_level: f64,            // Audio level (0.0 to 1.0) that wakes the face from standby
+    pub standby_fade_in_secs: f64,          // Fade up from black after waking
+    pub standby_dot: bool,                  // Dim breathing dot in standby instead of dark panels
+    pub fade_speed: f64,                    // Face fade out/in around video and standby, brightness per second (0 = cut)
+    pub heart_rate_device: Option<String>,  // MAC address of a BLE heart rate monitor
+    pub heart_rate_rest_bpm: u32,           // Heart rate with a calm face
+    pub heart_rate_excited_bpm: u32,        // Heart rate with the full excited smile
+    pub schedule: Vec<ScheduleEntry>,       // Time-of-day palette/brightness/expression changes
+    pub schedule_fade_minutes: f64,         // Brightness and expression fade after each schedule entry
+    pub expression_logging: bool,           // Append eye/mouth/palette/video/accessory changes to a CSV log
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            idle_animation_interval_secs: 20.0,
+            video_end_policy: EndOfVideoPolicy::Stop,
+            video_overlay_alpha: 1.0,
+            slideshow_dwell_secs: 5.0,
+            slideshow_crossfade_secs: 1.0,
+            slideshow_auto_advance: true,
+            blep_auto_retract_secs: DEFAULT_AUTO_RETRACT_SECS,
+            background_brightness: 0.0,
+            glitch_intensity: 0.5,
+            glitch_frequency: 0.5,
+            eye_pose_distance: 3.0,
+            color_order: ColorOrder::Rgb,
+            shimmer_mode: ShimmerMode::Linear,
+            anti_aliasing: true,
+            palette_shift_speed: 0.0,
+            brightness_oscillation_amplitude: 0.0,
+            brightness_oscillation_hz: 0.5,
+            min_pixel_brightness: 0,
+            target_fps: 30.0,
+            night_vision: false,
+            palette_image: None,
+            bloom_enabled: false,
+            bloom_radius: 1,
+            bloom_intensity: 0.25,
+            eye_glow_enabled: false,
+            eye_glow_radius: 2,
+            eye_glow_intensity: 0.35,
+            adaptive_quality: true,
+            quality_shed_order: QualityEffect::default_order(),
+            rng_seed: None,
+            matrix_rain_duration_secs: 10.0,
+            fire_enabled: false,
+            waveform_enabled: false,
+            waveform_smoothed: true,
+            burst_enabled: false,
+            burst_ring_speed: 30.0,
+            burst_ring_width: 2.0,
+            spectrum_enabled: false,
+            spectrum_bands: 8,
+            spectrum_y: 0,
+            pulse_breathing_enabled: false,
+            nose_breathing: true,
+            mouth_emotion_curve: 4.0,
+            mouth_blend: MouthBlend::Override,
+            mouth_blend_weight: 0.5,
+            stereo_mouth: false,
+            mirror_offset: 0,
+            right_panel_brightness: 1.0,
+            right_panel_color_shift: 0.0,
+            indicator_style: IndicatorStyle::Spinner,
+            indicator_x: 46,
+            indicator_y: 28,
+            show_temp_warning: true,
+            show_clock: false,
+            show_wifi: false,
+            show_battery: false,
+            battery_gauge: BatteryGauge::Ip5306,
+            mouth_trigger_range: TriggerRange::Auto,
+            mouth_trigger_invert: false,
+            mouth_trigger_deadzone: 0.05,
+            mouth_trigger_axis_code: 10,
+            gamepad_mappings: Vec::new(),
+            gamepad_map_path: DEFAULT_BUTTON_MAP_PATH.to_string(),
+            gamepad_roles: HashMap::new(),
+            emotes: Emote::defaults(),
+            standby_after_secs: 0.0,
+            standby_wake_level: 0.2,
+            standby_fade_in_secs: 1.0,
+            standby_dot: true,
+            fade_speed: 2.0,
+            heart_rate_device: None,
+            heart_rate_rest_bpm: 70,
+            heart_rate_excited_bpm: 120,
+            schedule: Vec::new(),
+            schedule_fade_minutes: 30.0,
+            expression_logging: false,
+        }
+    }
+}
+
+impl Config {
+    /// Mouth trigger normalization built from the mouth_trigger_* settings
+    pub fn mouth_trigger(&self) -> TriggerAxis {
+        TriggerAxis::new(self.mouth_trigger_range, self.mouth_trigger_invert,
+                         self.mouth_trigger_deadzone, self.mouth_trigger_axis_code)
+    }
+
+    /// Parse a config file
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Per-gamepad control groups from gamepad_roles (keys are gamepad ids)
+    pub fn gamepad_roles(&self) -> GamepadRoles {
+        let roles = self.gamepad_roles.iter()
+            .filter_map(|(id, groups)| match id.parse::<usize>() {
+                Ok(id) => Some((id, groups.clone())),
+                Err(_) => {
+                    warn!("⚠️  Ignoring gamepad_roles entry {:?}: not a gamepad id", id);
+                    None
+                }
+            })
+            .collect();
+        GamepadRoles::new(roles)
+    }
+
+    /// Load the config file, falling back to defaults if it is missing or invalid
+    pub fn load_or_default(path: &str) -> Self {
+        match Self::load(path) {
+            Ok(config) => {
+                info!("⚙️  Loaded config from {}", path);
+                config
+            }
+            Err(e) => {
+                let missing = e.downcast_ref::<std::io::Error>()
+                    .map(|io| io.kind() == ErrorKind::NotFound)
+                    .unwrap_or(false);
+                if !missing {
+                    warn!("⚠️  Could not load config {}: {}", path, e);
+                }
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Write learned trigger settings into the config file, keeping everything else
+/// (including comments) as it was; the file is created if it doesn't exist
+pub fn save_learned_trigger(path: &str, learned: &LearnedTrigger) -> std::io::Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let contents = set_keys(&contents, &[
+        ("mouth_trigger_axis_code", learned.axis_code.to_string()),
+        ("mouth_trigger_range", format!("\"{}\"", learned.range.name())),
+        ("mouth_trigger_invert", learned.invert.to_string()),
+    ]);
+    fs::write(path, contents)
+}
+
+// Replace top-level `key = value` lines, adding keys that aren't there yet
+// Only the lines above the first `[table]` / `[[array]]` header are top-level, so new
+// keys go in before that header (and the comments directly above it), never into a table
+fn set_keys(contents: &str, updates: &[(&str, String)]) -> String {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    for (key, value) in updates {
+        let top_level = lines.iter()
+            .position(|line| line.trim_start().starts_with('['))
+            .unwrap_or(lines.len());
+        let existing = lines[..top_level].iter().position(|line| {
+            line.split('=').next().map(|k| k.trim() == *key).unwrap_or(false)
+                && line.contains('=')
+        });
+        let line = format!("{} = {}", key, value);
+        match existing {
+            Some(i) => lines[i] = line,
+            None => {
+                let mut insert_at = top_level;
+                while insert_at > 0 && insert_at < lines.len() {
+                    let above = lines[insert_at - 1].trim();
+                    if !above.is_empty() && !above.starts_with('#') {
+                        break;
+                    }
+                    insert_at -= 1;
+                }
+                lines.insert(insert_at, line);
+            }
+        }
+    }
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_keys_replaces_existing_values_and_keeps_comments() {
+        let contents = "# Mouth trigger\nmouth_trigger_invert = false\nmirror_offset = 1\n";
+        let updated = set_keys(contents, &[
+            ("mouth_trigger_invert", "true".to_string()),
+            ("mouth_trigger_axis_code", "2".to_string()),
+        ]);
+        assert_eq!(updated, "# Mouth trigger\nmouth_trigger_invert = true\nmirror_offset = 1\n\
+                             mouth_trigger_axis_code = 2\n");
+    }
+
+    #[test]
+    fn set_keys_stays_above_tables() {
+        let contents = "mirror_offset = 1\n\n# Roles\n[gamepad_roles]\n0 = [\"face\"]\n\
+                        \n[[emotes]]\nname = \"wink\"\nduration_secs = 1.5\n";
+        let updated = set_keys(contents, &[
+            ("mouth_trigger_invert", "true".to_string()),
+            ("mouth_trigger_axis_code", "2".to_string()),
+        ]);
+        assert_eq!(updated, "mirror_offset = 1\nmouth_trigger_invert = true\n\
+                             mouth_trigger_axis_code = 2\n\n# Roles\n[gamepad_roles]\n\
+                             0 = [\"face\"]\n\n[[emotes]]\nname = \"wink\"\n\
+                             duration_secs = 1.5\n");
+        let config: Config = toml::from_str(&updated).unwrap();
+        assert!(config.mouth_trigger_invert);
+        assert_eq!(config.mouth_trigger_axis_code, 2);
+        assert_eq!(config.emotes.len(), 1);
+    }
+}
@@ -0,0 +1,207 @@
+// Config hot reload
+// Polls the config file's modification time and re-parses it after an edit, so settings
+// can be tuned on a running face. Only changed keys are applied; a file that doesn't
+// parse is reported and ignored, and the face keeps the settings it has.
+
+use std::fs;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::Config;
+use crate::face::ProtogenFace;
+use crate::gamepad::MaskState;
+use tracing::warn;
+
+// How often the config file's modification time is checked
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings only read at startup; edits to these are reported and wait for a restart
+pub const RESTART_KEYS: &[&str] = &[
+    "rng_seed", "gamepad_mappings", "gamepad_roles",
+    "indicator_style", "indicator_x", "indicator_y", "show_battery", "battery_gauge",
+    "heart_rate_device",
+    "slideshow_dwell_secs", "slideshow_crossfade_secs", "slideshow_auto_advance",
+];
+
+/// A successfully re-parsed config and the top-level keys that changed
+pub struct ConfigReload {
+    pub config: Config,
+    pub changed: Vec<String>,
+}
+
+/// Watches the config file for edits
+pub struct ConfigWatcher {
+    path: String,
+    modified: Option<SystemTime>,
+    table: toml::Table,  // Last version that parsed, to tell which keys changed
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            modified: modified_time(path),
+            table: fs::read_to_string(path).ok()
+                .and_then(|contents| contents.parse().ok())
+                .unwrap_or_default(),
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Check for an edit (at most once per poll interval), returning the new config
+    /// if the file changed and still parses
+    pub fn poll(&mut self) -> Option<ConfigReload> {
+        if self.last_check.elapsed() < CONFIG_POLL_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+
+        // A deleted file keeps the running settings, like a broken one
+        let contents = fs::read_to_string(&self.path).ok()?;
+        let parsed = contents.parse::<toml::Table>()
+            .map_err(|e| e.to_string())
+            .and_then(|table| {
+                let config: Config = toml::from_str(&contents).map_err(|e| e.to_string())?;
+                Ok((table, config))
+            });
+        let (table, config) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!("⚠️  Config {} not reloaded, keeping the running settings: {}", self.path, e);
+                return None;
+            }
+        };
+
+        let changed = changed_keys(&self.table, &table);
+        self.table = table;
+        if changed.is_empty() {
+            return None;
+        }
+        Some(ConfigReload { config, changed })
+    }
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Top-level keys added, removed or edited between two versions of the file, sorted
+fn changed_keys(old: &toml::Table, new: &toml::Table) -> Vec<String> {
+    let mut changed: Vec<String> = old.keys()
+        .chain(new.keys().filter(|key| !old.contains_key(*key)))
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Apply one changed key that only touches the face or mask state, returns false if
+/// the key isn't one of them (main handles the rest)
+pub fn apply_face_setting(protogen: &mut ProtogenFace, state: &Mutex<MaskState>,
+                          config: &Config, key: &str) -> bool {
+    match key {
+        "idle_animation_interval_secs" => {
+            protogen.set_idle_animation_interval(config.idle_animation_interval_secs);
+        }
+        "blep_auto_retract_secs" => protogen.set_blep_auto_retract(config.blep_auto_retract_secs),
+        "background_brightness" => protogen.set_background_brightness(config.background_brightness),
+        "glitch_intensity" | "glitch_frequency" => {
+            protogen.set_glitch(config.glitch_intensity, config.glitch_frequency);
+        }
+        "eye_pose_distance" => protogen.set_eye_pose_distance(config.eye_pose_distance),
+        "color_order" => protogen.set_color_order(config.color_order),
+        "shimmer_mode" => protogen.set_shimmer_mode(config.shimmer_mode),
+        "anti_aliasing" => protogen.set_anti_aliasing(config.anti_aliasing),
+        "brightness_oscillation_amplitude" | "brightness_oscillation_hz" => {
+            protogen.set_brightness_oscillation(config.brightness_oscillation_amplitude,
+                                                config.brightness_oscillation_hz);
+        }
+        "bloom_enabled" | "bloom_radius" | "bloom_intensity" => {
+            protogen.set_bloom(config.bloom_enabled, config.bloom_radius, config.bloom_intensity);
+        }
+        "eye_glow_enabled" | "eye_glow_radius" | "eye_glow_intensity" => {
+            protogen.set_eye_glow(config.eye_glow_enabled, config.eye_glow_radius,
+                                  config.eye_glow_intensity);
+        }
+        "adaptive_quality" | "quality_shed_order" => {
+            protogen.set_adaptive_quality(config.adaptive_quality, config.quality_shed_order.clone());
+        }
+        "matrix_rain_duration_secs" => {
+            protogen.set_matrix_rain_duration(config.matrix_rain_duration_secs);
+        }
+        "fire_enabled" => {
+            protogen.set_element_enabled("Fire", config.fire_enabled);
+        }
+        "waveform_enabled" => {
+            protogen.set_element_enabled("Waveform", config.waveform_enabled);
+        }
+        "waveform_smoothed" => protogen.set_waveform_smoothed(config.waveform_smoothed),
+        "burst_enabled" => {
+            protogen.set_element_enabled("Color Burst", config.burst_enabled);
+        }
+        "burst_ring_speed" | "burst_ring_width" => {
+            protogen.set_burst_rings(config.burst_ring_speed, config.burst_ring_width);
+        }
+        "spectrum_enabled" => {
+            protogen.set_element_enabled("Spectrum Bars", config.spectrum_enabled);
+        }
+        "spectrum_bands" | "spectrum_y" => {
+            protogen.set_spectrum(config.spectrum_bands, config.spectrum_y);
+        }
+        "heart_rate_rest_bpm" | "heart_rate_excited_bpm" => {
+            protogen.set_heart_rate_range(config.heart_rate_rest_bpm, config.heart_rate_excited_bpm);
+        }
+        "show_clock" => {
+            protogen.set_element_enabled("Clock", config.show_clock);
+        }
+        "show_wifi" => {
+            protogen.set_element_enabled("WiFi Signal", config.show_wifi);
+        }
+        "show_temp_warning" => {
+            protogen.set_element_enabled("Temperature Warning", config.show_temp_warning);
+        }
+        "pulse_breathing_enabled" => {
+            protogen.set_element_enabled("Pulse Breathing", config.pulse_breathing_enabled);
+        }
+        "nose_breathing" => protogen.set_nose_breathing(config.nose_breathing),
+        "mouth_emotion_curve" => protogen.set_mouth_emotion_curve(config.mouth_emotion_curve),
+        "mouth_blend" | "mouth_blend_weight" => {
+            protogen.set_mouth_blend(config.mouth_blend, config.mouth_blend_weight);
+        }
+        "stereo_mouth" => protogen.set_stereo_mouth(config.stereo_mouth),
+        "mirror_offset" => protogen.set_mirror_offset(config.mirror_offset),
+        "right_panel_brightness" | "right_panel_color_shift" => {
+            protogen.set_right_panel_bias(config.right_panel_brightness,
+                                          config.right_panel_color_shift);
+        }
+        "min_pixel_brightness" => protogen.set_min_pixel_brightness(config.min_pixel_brightness),
+        "emotes" => protogen.set_emotes(config.emotes.clone()),
+        "night_vision" => state.lock().unwrap().night_vision = config.night_vision,
+        "palette_shift_speed" => {
+            state.lock().unwrap().palette_shift_speed = config.palette_shift_speed;
+        }
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_keys_finds_edits_additions_and_removals() {
+        let old: toml::Table = "a = 1\nb = 2\nc = 3".parse().unwrap();
+        let new: toml::Table = "a = 1\nb = 5\nd = 4".parse().unwrap();
+        assert_eq!(changed_keys(&old, &new), vec!["b", "c", "d"]);
+        assert!(changed_keys(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// Debug overlay
+// Draws each active element's bounding box and a short name label on top of the face
+// so element positions can be checked on real hardware
+
+use std::cell::Cell;
+use rpi_led_matrix::LedColor;
+
+use crate::canvas::VirtualLedCanvas;
+
+use crate::color::ColorPalette;
+use crate::face::DrawPixelFn;
+use crate::font::BitmapFont;
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Dim colors cycled per element (independent of the active palette)
+const OVERLAY_COLORS: [(u8, u8, u8); 6] = [
+    (48, 0, 0),
+    (0, 0, 48),
+    (48, 48, 0),
+    (0, 48, 48),
+    (48, 0, 48),
+    (40, 40, 40),
+];
+
+// Number of name characters shown in each label
+const LABEL_CHARS: usize = 4;
+
+/// Bounding box of the pixels an element drew, in element coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementBounds {
+    pub min_x: i32,
+    pub min_y: i32,
+    pub max_x: i32,
+    pub max_y: i32,
+}
+
+impl ElementBounds {
+    fn include(self, x: i32, y: i32) -> Self {
+        Self {
+            min_x: self.min_x.min(x),
+            min_y: self.min_y.min(y),
+            max_x: self.max_x.max(x),
+            max_y: self.max_y.max(y),
+        }
+    }
+}
+
+/// Pixel drawer wrapper that forwards to another drawer while recording
+/// the extent of every visible pixel drawn
+pub struct BoundsRecorder<'a> {
+    inner: &'a dyn DrawPixelFn,
+    bounds: Cell<Option<ElementBounds>>,
+}
+
+impl<'a> BoundsRecorder<'a> {
+    pub fn new(inner: &'a dyn DrawPixelFn) -> Self {
+        Self {
+            inner,
+            bounds: Cell::new(None),
+        }
+    }
+
+    pub fn bounds(&self) -> Option<ElementBounds> {
+        self.bounds.get()
+    }
+}
+
+impl<'a> DrawPixelFn for BoundsRecorder<'a> {
+    fn draw(&self, canvas: &mut VirtualLedCanvas, bright: f64, color_index: f64,
+            x: i32, y: i32, brightness: f64, palette: ColorPalette) {
+        self.inner.draw(canvas, bright, color_index, x, y, brightness, palette);
+
+        // Only count pixels that actually land on the panel
+        if bright <= 0.0 || x < 0 || x >= PANEL_WIDTH || y < 0 || y >= PANEL_HEIGHT {
+            return;
+        }
+
+        let updated = match self.bounds.get() {
+            Some(bounds) => bounds.include(x, y),
+            None => ElementBounds { min_x: x, min_y: y, max_x: x, max_y: y },
+        };
+        self.bounds.set(Some(updated));
+    }
+}
+
+/// Renders recorded element bounds and labels
+pub struct DebugOverlay;
+
+impl DebugOverlay {
+    /// Draw a box and label for each element (elements that drew nothing are skipped)
+    pub fn draw(canvas: &mut VirtualLedCanvas, elements: &[(String, Option<ElementBounds>)]) {
+        for (i, (name, bounds)) in elements.iter().enumerate() {
+            let bounds = match bounds {
+                Some(b) => b,
+                None => continue,
+            };
+            let color = OVERLAY_COLORS[i % OVERLAY_COLORS.len()];
+
+            // Rectangle outline (mirrored like the face itself)
+            Self::draw_line(canvas, bounds.min_x, bounds.min_y, bounds.max_x, bounds.min_y, color);
+            Self::draw_line(canvas, bounds.min_x, bounds.max_y, bounds.max_x, bounds.max_y, color);
+            Self::draw_line(canvas, bounds.min_x, bounds.min_y, bounds.min_x, bounds.max_y, color);
+            Self::draw_line(canvas, bounds.max_x, bounds.min_y, bounds.max_x, bounds.max_y, color);
+
+            // Label inside the top-left corner (left panel only so the text stays readable)
+            let label: String = name.chars().take(LABEL_CHARS).collect();
+            let left = bounds.min_x + 1;
+            let top = bounds.max_y - 1;
+            BitmapFont::for_each_pixel(&label, |dx, dy| {
+                // Element y grows upward, font rows grow downward
+                Self::set_pixel(canvas, left + dx, top - dy, color, false);
+            });
+        }
+    }
+
+    /// Bresenham line between two points in element coordinates
+    fn draw_line(canvas: &mut VirtualLedCanvas, x0: i32, y0: i32, x1: i32, y1: i32, color: (u8, u8, u8)) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+
+        loop {
+            Self::set_pixel(canvas, x, y, color, true);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Write a pixel using the same flip/mirror transform as `PixelDrawer`
+    fn set_pixel(canvas: &mut VirtualLedCanvas, x: i32, y: i32, color: (u8, u8, u8), mirror: bool) {
+        let flipped_y = PANEL_HEIGHT - 1 - y;
+        if x < 0 || x >= PANEL_WIDTH || flipped_y < 0 || flipped_y >= PANEL_HEIGHT {
+            return;
+        }
+
+        let (red, green, blue) = color;
+        canvas.set(x, flipped_y, &LedColor { red, green, blue });
+        if mirror {
+            canvas.set((PANEL_WIDTH * 2) - 1 - x, flipped_y, &LedColor { red, green, blue });
+        }
+    }
+}
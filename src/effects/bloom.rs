@@ -0,0 +1,146 @@
+use rpi_led_matrix::LedColor;
+use crate::canvas::VirtualLedCanvas;
+use crate::PANEL_WIDTH;
+
+// Largest supported bleed distance, keeps the per-frame cost bounded
+const MAX_RADIUS: i32 = 4;
+
+/// Glow post-process: every lit pixel bleeds a fraction of its color into the
+/// pixels around it, fading with distance, so the face looks soft instead of blocky
+/// Glow stays on its own panel, the halves never bleed into each other at the seam
+pub struct BloomEffect {
+    radius: i32,                 // How many pixels the glow reaches
+    intensity: f64,              // Fraction of a pixel's color added to its direct neighbours
+    glow: Vec<(f64, f64, f64)>,  // Per-frame accumulation buffer, reused between frames
+}
+
+impl BloomEffect {
+    pub fn new(radius: i32, intensity: f64) -> Self {
+        Self {
+            radius: radius.clamp(0, MAX_RADIUS),
+            intensity: intensity.clamp(0.0, 1.0),
+            glow: Vec::new(),
+        }
+    }
+
+    pub fn set_radius(&mut self, radius: i32) {
+        self.radius = radius.clamp(0, MAX_RADIUS);
+    }
+
+    pub fn set_intensity(&mut self, intensity: f64) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Spread light from every lit pixel into its neighbours
+    pub fn apply(&mut self, canvas: &mut VirtualLedCanvas) {
+        if self.accumulate(canvas) {
+            self.add_glow(canvas);
+        }
+    }
+
+    /// Spread light from the lit pixels of `source` (e.g. only the eyes) onto `canvas`
+    pub fn apply_from(&mut self, source: &VirtualLedCanvas, canvas: &mut VirtualLedCanvas) {
+        if self.accumulate(source) {
+            self.add_glow(canvas);
+        }
+    }
+
+    // Collect the glow from every lit source pixel, false if there is nothing to add
+    // Accumulating first means glow is only ever taken from the original pixels
+    fn accumulate(&mut self, source: &VirtualLedCanvas) -> bool {
+        if self.radius == 0 || self.intensity <= 0.0 {
+            return false;
+        }
+
+        let (width, height) = (source.width(), source.height());
+        self.glow.clear();
+        self.glow.resize((width * height) as usize, (0.0, 0.0, 0.0));
+
+        for y in 0..height {
+            for x in 0..width {
+                let (r, g, b) = source.get(x, y);
+                if (r, g, b) == (0, 0, 0) {
+                    continue;
+                }
+                let panel = x / PANEL_WIDTH;
+                for dy in -self.radius..=self.radius {
+                    for dx in -self.radius..=self.radius {
+                        let (nx, ny) = (x + dx, y + dy);
+                        if (dx, dy) == (0, 0) || nx < 0 || ny < 0 || nx >= width || ny >= height
+                            || nx / PANEL_WIDTH != panel {
+                            continue;
+                        }
+                        let weight = self.falloff(dx, dy);
+                        if weight <= 0.0 {
+                            continue;
+                        }
+                        let cell = &mut self.glow[(ny * width + nx) as usize];
+                        cell.0 += r as f64 * weight;
+                        cell.1 += g as f64 * weight;
+                        cell.2 += b as f64 * weight;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    // Add the accumulated glow onto a canvas the same size as the source
+    fn add_glow(&self, canvas: &mut VirtualLedCanvas) {
+        let (width, height) = (canvas.width(), canvas.height());
+        if self.glow.len() != (width * height) as usize {
+            return;
+        }
+        for y in 0..height {
+            for x in 0..width {
+                let (gr, gg, gb) = self.glow[(y * width + x) as usize];
+                if gr + gg + gb < 1.0 {
+                    continue;
+                }
+                let (r, g, b) = canvas.get(x, y);
+                canvas.set(x, y, &LedColor {
+                    red: (r as f64 + gr).min(255.0) as u8,
+                    green: (g as f64 + gg).min(255.0) as u8,
+                    blue: (b as f64 + gb).min(255.0) as u8,
+                });
+            }
+        }
+    }
+
+    // Glow weight at an offset: full intensity next to the pixel, fading to zero
+    // just past the radius (round, so the glow doesn't look square)
+    fn falloff(&self, dx: i32, dy: i32) -> f64 {
+        let distance = ((dx * dx + dy * dy) as f64).sqrt();
+        let fade = 1.0 - (distance - 1.0) / self.radius as f64;
+        self.intensity * fade.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glow_spreads_within_radius_only() {
+        let mut canvas = VirtualLedCanvas::new(16, 16);
+        canvas.set(8, 8, &LedColor { red: 200, green: 0, blue: 0 });
+
+        BloomEffect::new(2, 0.5).apply(&mut canvas);
+
+        assert_eq!(canvas.get(8, 8), (200, 0, 0));
+        assert_eq!(canvas.get(9, 8), (100, 0, 0));
+        assert!(canvas.get(10, 8).0 > 0 && canvas.get(10, 8).0 < 100);
+        assert_eq!(canvas.get(11, 8), (0, 0, 0));
+    }
+
+    #[test]
+    fn glow_stops_at_the_panel_seam() {
+        let mut canvas = VirtualLedCanvas::new(PANEL_WIDTH * 2, 4);
+        canvas.set(PANEL_WIDTH - 1, 2, &LedColor { red: 200, green: 0, blue: 0 });
+
+        BloomEffect::new(2, 0.5).apply(&mut canvas);
+
+        assert_eq!(canvas.get(PANEL_WIDTH - 2, 2), (100, 0, 0));
+        assert_eq!(canvas.get(PANEL_WIDTH, 2), (0, 0, 0));
+    }
+}
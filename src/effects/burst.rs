@@ -0,0 +1,129 @@
+use std::any::Any;
+use std::sync::Arc;
+use crate::audio::{AudioLevel, BeatDetector};
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState,
+                  ConfigField};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Rings start at the middle of each panel
+const CENTER_X: f64 = PANEL_WIDTH as f64 / 2.0;
+const CENTER_Y: f64 = PANEL_HEIGHT as f64 / 2.0;
+
+// Palette index of the brightest color (see get_palette_color_for_luminance)
+const BRIGHTEST_COLOR: f64 = 50.0;
+
+// How far above the running average the level must jump to count as a beat
+const BEAT_SENSITIVITY: f64 = 1.5;
+
+/// Concentric rings bursting out from the center of the panel on every beat
+/// Each ring grows at `speed` pixels per second and fades as it grows,
+/// disappearing once it's past the panel corners
+#[derive(Clone)]
+pub struct ColorBurstEffect {
+    audio_level: Arc<AudioLevel>,
+    beat_detector: BeatDetector,
+    rings: Vec<f64>,  // Radius of each live ring
+    speed: f64,       // Ring growth, pixels per second
+    width: f64,       // Ring thickness in pixels
+}
+
+impl ColorBurstEffect {
+    pub fn new(audio_level: Arc<AudioLevel>, speed: f64, width: f64) -> Self {
+        Self {
+            audio_level,
+            beat_detector: BeatDetector::new(BEAT_SENSITIVITY),
+            rings: Vec::new(),
+            speed: speed.max(1.0),
+            width: width.max(1.0),
+        }
+    }
+
+    pub fn set_ring(&mut self, speed: f64, width: f64) {
+        self.speed = speed.max(1.0);
+        self.width = width.max(1.0);
+    }
+
+    // Distance from the center to a panel corner
+    fn max_radius() -> f64 {
+        (CENTER_X * CENTER_X + CENTER_Y * CENTER_Y).sqrt()
+    }
+}
+
+impl FaceElement for ColorBurstEffect {
+    fn name(&self) -> &str {
+        "Color Burst"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Rings of color bursting from the center on each beat"
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("burst_ring_speed", self.speed, "Ring growth, pixels per second"),
+            ConfigField::new("burst_ring_width", self.width, "Ring thickness in pixels"),
+        ]
+    }
+
+    // Behind the face, over the background and fire
+    fn z_order(&self) -> i32 {
+        -40
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        for radius in self.rings.iter_mut() {
+            *radius += self.speed * dt;
+        }
+        let max_radius = Self::max_radius() + self.width;
+        self.rings.retain(|&radius| radius < max_radius);
+
+        if self.beat_detector.update(self.audio_level.get_level(), dt) {
+            self.rings.push(0.0);
+        }
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.rings.is_empty() {
+            return;
+        }
+        let max_radius = Self::max_radius();
+        let half_width = self.width / 2.0;
+
+        for x in 0..PANEL_WIDTH {
+            for y in 0..PANEL_HEIGHT {
+                let dx = x as f64 + 0.5 - CENTER_X;
+                let dy = y as f64 + 0.5 - CENTER_Y;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                // Overlapping rings add up
+                let bright: f64 = self.rings.iter()
+                    .filter(|&&radius| (distance - radius).abs() <= half_width)
+                    .map(|&radius| 255.0 * (1.0 - radius / max_radius).max(0.0))
+                    .sum();
+                if bright > 0.0 {
+                    draw_pixel_fn.draw(canvas, bright.min(255.0), BRIGHTEST_COLOR, x, y,
+                                      context.brightness, context.palette);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,72 @@
+/// Brightness multiplier that eases toward a target at a fixed speed, so switching to
+/// video or into standby dims the face out instead of cutting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FadeTransition {
+    pub target_brightness: f64,  // 0.0 (faded out) or 1.0 (fully shown)
+    pub speed: f64,              // Brightness change per second, 0 = cut straight to the target
+    pub current: f64,
+}
+
+impl FadeTransition {
+    /// Fully shown, fading at `speed`
+    pub fn new(speed: f64) -> Self {
+        Self { target_brightness: 1.0, speed: speed.max(0.0), current: 1.0 }
+    }
+
+    pub fn fade_in(&mut self) {
+        self.target_brightness = 1.0;
+    }
+
+    pub fn fade_out(&mut self) {
+        self.target_brightness = 0.0;
+    }
+
+    /// Jump straight to a brightness (and stay there)
+    pub fn set(&mut self, brightness: f64) {
+        self.target_brightness = brightness;
+        self.current = brightness;
+    }
+
+    /// Move toward the target by `speed * dt` and return the brightness multiplier
+    pub fn update(&mut self, dt: f64) -> f64 {
+        let step = if self.speed > 0.0 { self.speed * dt } else { f64::INFINITY };
+        self.current = if self.current < self.target_brightness {
+            (self.current + step).min(self.target_brightness)
+        } else {
+            (self.current - step).max(self.target_brightness)
+        };
+        self.current
+    }
+
+    /// Heading for black and already there
+    pub fn is_faded_out(&self) -> bool {
+        self.target_brightness <= 0.0 && self.current <= 0.0
+    }
+
+    /// Seconds a full fade takes
+    pub fn duration(&self) -> f64 {
+        if self.speed > 0.0 { 1.0 / self.speed } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fades_at_a_fixed_speed_and_cuts_at_zero() {
+        let mut fade = FadeTransition::new(2.0);
+        fade.fade_out();
+        assert_eq!(fade.update(0.25), 0.5);
+        assert!(!fade.is_faded_out());
+        assert_eq!(fade.update(0.5), 0.0);
+        assert!(fade.is_faded_out());
+        fade.fade_in();
+        assert_eq!(fade.update(0.1), 0.2);
+
+        let mut cut = FadeTransition::new(0.0);
+        cut.fade_out();
+        assert_eq!(cut.update(0.0), 0.0);
+        assert_eq!(cut.duration(), 0.0);
+    }
+}
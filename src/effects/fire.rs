@@ -0,0 +1,119 @@
+use std::any::Any;
+use std::sync::Arc;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::rng;
+use crate::audio::AudioLevel;
+use crate::canvas::VirtualLedCanvas;
+use crate::color::ColorPalette;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Fire fills each panel (it takes over the display while enabled)
+const FIRE_WIDTH: usize = PANEL_WIDTH as usize;
+const FIRE_HEIGHT: usize = PANEL_HEIGHT as usize;
+
+// Heat lost per generation as it rises (higher = shorter flames)
+const COOLING: f64 = 0.025;
+
+// Heat fed into the bottom row: idle flicker plus a boost from the audio level
+const BASE_HEAT: f64 = 0.55;
+const AUDIO_HEAT: f64 = 0.45;
+
+// Cells cooler than this aren't drawn
+const MIN_VISIBLE_HEAT: f64 = 0.05;
+
+/// Cellular-automaton fire burning up from the bottom of the panels
+/// Every generation the bottom row is reseeded with random heat (hotter when loud),
+/// and each cell above takes the average of the cells below it minus a little cooling
+#[derive(Clone)]
+pub struct FireSimulation {
+    heat: [[f64; FIRE_WIDTH]; FIRE_HEIGHT],  // Row 0 is the bottom, 0.0 to 1.0
+    audio_level: Arc<AudioLevel>,
+    rng: StdRng,
+}
+
+impl FireSimulation {
+    pub fn new(audio_level: Arc<AudioLevel>) -> Self {
+        Self {
+            heat: [[0.0; FIRE_WIDTH]; FIRE_HEIGHT],
+            audio_level,
+            rng: rng::seeded_rng(),
+        }
+    }
+
+    // Advance one generation
+    fn step(&mut self) {
+        let fuel = BASE_HEAT + AUDIO_HEAT * self.audio_level.get_smoothed_level().clamp(0.0, 1.0);
+        for x in 0..FIRE_WIDTH {
+            self.heat[0][x] = fuel * self.rng.gen_range(0.3..1.0);
+        }
+
+        // Top-down so every row reads the previous generation of the rows below it
+        for y in (1..FIRE_HEIGHT).rev() {
+            for x in 0..FIRE_WIDTH {
+                let left = self.heat[y - 1][x.saturating_sub(1)];
+                let right = self.heat[y - 1][(x + 1).min(FIRE_WIDTH - 1)];
+                let below = self.heat[y - 1][x];
+                let below2 = if y >= 2 { self.heat[y - 2][x] } else { below };
+                let average = (left + below + right + below2) / 4.0;
+                self.heat[y][x] = (average - COOLING * self.rng.gen_range(0.5..1.5)).max(0.0);
+            }
+        }
+    }
+}
+
+impl FaceElement for FireSimulation {
+    fn name(&self) -> &str {
+        "Fire"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Full-screen procedural fire, fed by the audio level"
+    }
+
+    fn z_order(&self) -> i32 {
+        -50
+    }
+
+    // Replaces the face while enabled
+    fn wants_exclusive_render(&self) -> bool {
+        true
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        self.step();
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        for (y, row) in self.heat.iter().enumerate() {
+            for (x, &heat) in row.iter().enumerate() {
+                if heat < MIN_VISIBLE_HEAT {
+                    continue;
+                }
+                // Hot cells take the bright end of the Fire palette, cooling embers the dark end
+                let heat = heat.min(1.0);
+                draw_pixel_fn.draw(canvas, heat * 255.0, heat * 50.0, x as i32, y as i32,
+                                  context.brightness, ColorPalette::Fire);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
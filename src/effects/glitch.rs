@@ -0,0 +1,102 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::rng;
+use rpi_led_matrix::LedColor;
+use crate::canvas::VirtualLedCanvas;
+
+// Length range of one glitch burst (seconds)
+const MIN_BURST_SECS: f64 = 0.08;
+const MAX_BURST_SECS: f64 = 0.3;
+
+/// Digital glitch post-process: short bursts of shifted rows, color channel
+/// separation and flicker over whatever the face rendered
+pub struct GlitchEffect {
+    intensity: f64,       // 0.0 to 1.0, how strong each burst is
+    frequency: f64,       // Average bursts per second
+    burst_remaining: f64, // Time left in the current burst (0 = idle)
+    rng: StdRng,
+}
+
+impl GlitchEffect {
+    pub fn new(intensity: f64, frequency: f64) -> Self {
+        Self {
+            intensity: intensity.clamp(0.0, 1.0),
+            frequency: frequency.max(0.0),
+            burst_remaining: 0.0,
+            rng: rng::seeded_rng(),
+        }
+    }
+
+    pub fn set_intensity(&mut self, intensity: f64) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    pub fn set_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency.max(0.0);
+    }
+
+    /// Advance the burst timer and distort the frame while a burst is running
+    pub fn apply(&mut self, canvas: &mut VirtualLedCanvas, dt: f64) {
+        if self.burst_remaining > 0.0 {
+            self.burst_remaining -= dt;
+        } else if self.rng.gen_bool((self.frequency * dt).clamp(0.0, 1.0)) {
+            self.burst_remaining = self.rng.gen_range(MIN_BURST_SECS..MAX_BURST_SECS);
+        }
+        if self.burst_remaining <= 0.0 || self.intensity <= 0.0 {
+            return;
+        }
+
+        self.shift_rows(canvas);
+        if self.rng.gen_bool(0.5 * self.intensity) {
+            self.separate_channels(canvas);
+        }
+        if self.rng.gen_bool(0.2 * self.intensity) {
+            self.flicker(canvas);
+        }
+    }
+
+    // Slide a few random rows sideways (wrapping around the row)
+    fn shift_rows(&mut self, canvas: &mut VirtualLedCanvas) {
+        let width = canvas.width();
+        let rows = 1 + (self.intensity * 4.0) as usize;
+        let max_shift = 1 + (self.intensity * 6.0) as i32;
+
+        for _ in 0..rows {
+            let y = self.rng.gen_range(0..canvas.height());
+            let shift = self.rng.gen_range(1..=max_shift) * if self.rng.gen_bool(0.5) { 1 } else { -1 };
+            let row: Vec<_> = (0..width).map(|x| canvas.get(x, y)).collect();
+            for x in 0..width {
+                let (red, green, blue) = row[(x - shift).rem_euclid(width) as usize];
+                canvas.set(x, y, &LedColor { red, green, blue });
+            }
+        }
+    }
+
+    // Offset the red channel horizontally from green/blue
+    fn separate_channels(&mut self, canvas: &mut VirtualLedCanvas) {
+        let offset = 1 + (self.intensity * 2.0) as i32;
+        for y in 0..canvas.height() {
+            let row: Vec<_> = (0..canvas.width()).map(|x| canvas.get(x, y)).collect();
+            for x in 0..canvas.width() {
+                let red = if x >= offset { row[(x - offset) as usize].0 } else { 0 };
+                let (_, green, blue) = row[x as usize];
+                canvas.set(x, y, &LedColor { red, green, blue });
+            }
+        }
+    }
+
+    // Dim the whole frame for one frame
+    fn flicker(&mut self, canvas: &mut VirtualLedCanvas) {
+        let level = self.rng.gen_range(0.0..0.5);
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let (r, g, b) = canvas.get(x, y);
+                canvas.set(x, y, &LedColor {
+                    red: (r as f64 * level) as u8,
+                    green: (g as f64 * level) as u8,
+                    blue: (b as f64 * level) as u8,
+                });
+            }
+        }
+    }
+}
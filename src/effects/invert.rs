@@ -0,0 +1,11 @@
+use crate::canvas::VirtualLedCanvas;
+
+/// Negative-image post-process: every channel becomes 255 minus itself
+/// Black background turns white and the face draws dark on top of it
+pub struct InvertEffect;
+
+impl InvertEffect {
+    pub fn apply(canvas: &mut VirtualLedCanvas) {
+        canvas.map_pixels(|(r, g, b)| (255 - r, 255 - g, 255 - b));
+    }
+}
@@ -0,0 +1,199 @@
+use std::any::Any;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::rng;
+use rpi_led_matrix::LedColor;
+use tracing::info;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::font::{BitmapFont, GLYPH_WIDTH, GLYPH_HEIGHT, GLYPH_SPACING};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Grid of glyph cells covering both panels (1 px gap below each glyph)
+const CELL_WIDTH: i32 = GLYPH_WIDTH + GLYPH_SPACING;
+const CELL_HEIGHT: i32 = GLYPH_HEIGHT + 1;
+const COLUMNS: usize = (PANEL_WIDTH * 2 / CELL_WIDTH) as usize;
+const ROWS: usize = ((PANEL_HEIGHT + CELL_HEIGHT - 1) / CELL_HEIGHT) as usize;
+
+// Fall speed (cells per second) and trail length (cells) ranges
+const MIN_SPEED: f64 = 3.0;
+const MAX_SPEED: f64 = 10.0;
+const MIN_TRAIL: usize = 2;
+const MAX_TRAIL: usize = 6;
+
+// Chance per second that a glyph changes to another character
+const CHAR_CHANGE_RATE: f64 = 1.5;
+const CHARSET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ@#$%&*+=<>?";
+
+// One falling stream of glyphs
+#[derive(Clone)]
+struct RainColumn {
+    head: f64,        // Row of the leading glyph (negative = still above the panel)
+    speed: f64,       // Cells per second
+    trail: usize,     // Glyphs drawn behind the head
+    chars: [char; ROWS],
+}
+
+/// Matrix-style digital rain over the whole display
+/// Inactive until started; while running it replaces the face entirely
+/// (z_order 200 puts it above every other element) and stops after `duration` seconds
+#[derive(Clone)]
+pub struct MatrixRainEffect {
+    columns: Vec<RainColumn>,
+    duration: f64,   // How long one run lasts (seconds)
+    remaining: f64,  // Time left in the current run (0 = inactive)
+    rng: StdRng,
+}
+
+impl MatrixRainEffect {
+    pub fn new(duration: f64) -> Self {
+        let mut rng = rng::seeded_rng();
+        let columns = (0..COLUMNS).map(|_| Self::spawn_column(&mut rng)).collect();
+        Self {
+            columns,
+            duration: duration.max(0.0),
+            remaining: 0.0,
+            rng,
+        }
+    }
+
+    pub fn set_duration(&mut self, duration: f64) {
+        self.duration = duration.max(0.0);
+    }
+
+    /// Start (or restart) the rain for `duration` seconds
+    pub fn start(&mut self) {
+        for column in self.columns.iter_mut() {
+            *column = Self::spawn_column(&mut self.rng);
+        }
+        self.remaining = self.duration;
+        info!("🟩 Matrix rain for {:.0}s", self.duration);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    fn random_char(rng: &mut StdRng) -> char {
+        CHARSET[rng.gen_range(0..CHARSET.len())] as char
+    }
+
+    // New column starting somewhere above the panel, so columns don't all arrive together
+    fn spawn_column(rng: &mut StdRng) -> RainColumn {
+        let mut chars = [' '; ROWS];
+        for c in chars.iter_mut() {
+            *c = Self::random_char(rng);
+        }
+        RainColumn {
+            head: -rng.gen_range(0.0..ROWS as f64 * 2.0),
+            speed: rng.gen_range(MIN_SPEED..MAX_SPEED),
+            trail: rng.gen_range(MIN_TRAIL..=MAX_TRAIL),
+            chars,
+        }
+    }
+
+    // Draw one glyph at a grid cell in raw RGB (canvas rows grow downward)
+    fn draw_glyph(canvas: &mut VirtualLedCanvas, c: char, column: usize, row: usize,
+                  color: &LedColor) {
+        let origin_x = column as i32 * CELL_WIDTH;
+        let origin_y = row as i32 * CELL_HEIGHT;
+        for dy in 0..GLYPH_HEIGHT {
+            for dx in 0..GLYPH_WIDTH {
+                if BitmapFont::is_lit(c, dx, dy) {
+                    canvas.set(origin_x + dx, origin_y + dy, color);
+                }
+            }
+        }
+    }
+}
+
+impl FaceElement for MatrixRainEffect {
+    fn name(&self) -> &str {
+        "Matrix Rain"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Full-screen falling green characters (hold L2 + R2)"
+    }
+
+    fn z_order(&self) -> i32 {
+        200
+    }
+
+    fn wants_exclusive_render(&self) -> bool {
+        self.is_active()
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        if !self.is_active() {
+            return;
+        }
+        self.remaining -= dt;
+        if !self.is_active() {
+            info!("🟩 Matrix rain finished");
+            return;
+        }
+
+        let change_chance = (CHAR_CHANGE_RATE * dt).clamp(0.0, 1.0);
+        for i in 0..self.columns.len() {
+            let column = &mut self.columns[i];
+            column.head += column.speed * dt;
+            // Whole trail has left the bottom, respawn above the panel
+            if column.head - column.trail as f64 >= ROWS as f64 {
+                self.columns[i] = Self::spawn_column(&mut self.rng);
+                continue;
+            }
+            for c in self.columns[i].chars.iter_mut() {
+                if self.rng.gen_bool(change_chance) {
+                    *c = Self::random_char(&mut self.rng);
+                }
+            }
+        }
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {
+        if !self.is_active() {
+            return;
+        }
+
+        // Always green regardless of palette, so this writes the canvas directly
+        // (the face isn't drawn while the rain runs, see wants_exclusive_render)
+        let scale = context.brightness.clamp(0.0, 1.0);
+        for (x, column) in self.columns.iter().enumerate() {
+            let head = column.head.floor() as i32;
+            for i in 0..=column.trail as i32 {
+                let row = head - i;
+                if row < 0 || row >= ROWS as i32 {
+                    continue;
+                }
+                // Pale head, trail fading out behind it
+                let color = if i == 0 {
+                    LedColor { red: (180.0 * scale) as u8, green: (255.0 * scale) as u8,
+                               blue: (180.0 * scale) as u8 }
+                } else {
+                    let fade = 1.0 - i as f64 / (column.trail + 1) as f64;
+                    LedColor { red: 0, green: (220.0 * fade * scale) as u8, blue: 0 }
+                };
+                Self::draw_glyph(canvas, column.chars[row as usize], x, row as usize, &color);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,29 @@
+// Full-face effects
+// Effects work on the whole rendered frame rather than a single element, either as a
+// post-process pass over the framebuffer after all elements have drawn (glitch, invert,
+// night vision, bloom, strobe), as a brightness fade of the whole face (fade) or as a full-panel element drawn with the face (matrix rain
+// and the waveform on top of it, fire, color bursts and the breathing glow beneath it).
+
+mod bloom;
+mod burst;
+mod fade;
+mod fire;
+mod glitch;
+mod invert;
+mod matrix_rain;
+mod night_vision;
+mod pulse_breathing;
+mod strobe;
+mod waveform;
+
+pub use bloom::BloomEffect;
+pub use burst::ColorBurstEffect;
+pub use fade::FadeTransition;
+pub use fire::FireSimulation;
+pub use glitch::GlitchEffect;
+pub use invert::InvertEffect;
+pub use matrix_rain::MatrixRainEffect;
+pub use night_vision::NightVisionEffect;
+pub use pulse_breathing::PulseBreathingEffect;
+pub use strobe::{StrobeEffect, MAX_STROBE_HZ};
+pub use waveform::WaveformDisplay;
@@ -0,0 +1,12 @@
+use crate::canvas::VirtualLedCanvas;
+use crate::color::luminance;
+
+/// Monochrome green post-process for an IR / night-vision look
+/// Every pixel keeps its brightness but loses its color
+pub struct NightVisionEffect;
+
+impl NightVisionEffect {
+    pub fn apply(canvas: &mut VirtualLedCanvas) {
+        canvas.map_pixels(|(r, g, b)| (0, luminance(r, g, b).round().min(255.0) as u8, 0));
+    }
+}
@@ -0,0 +1,104 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::{MOUTH_MAX_OPENING, PANEL_WIDTH, PANEL_HEIGHT};
+
+// Glow centered on each panel
+const CENTER_X: f64 = PANEL_WIDTH as f64 / 2.0;
+const CENTER_Y: f64 = PANEL_HEIGHT as f64 / 2.0;
+
+// Radius at the bottom of a breath, and how much it grows at the top
+const MIN_RADIUS: f64 = 8.0;
+const PULSE_RADIUS: f64 = 10.0;
+
+// Brightness at the center (kept low so the face stays readable on top)
+const GLOW_BRIGHTNESS: f64 = 90.0;
+
+// How fast the glow fades in when idle starts and out when audio resumes (per second)
+const FADE_RATE: f64 = 1.5;
+
+/// Soft circular glow that swells and shrinks with the idle breathing
+/// Follows the mouth opening, so it breathes in step with the mouth, and only
+/// shows while the face is in breathing mode
+#[derive(Clone)]
+pub struct PulseBreathingEffect {
+    radius: f64,
+    fade: f64,  // 0.0 (hidden) to 1.0 (fully shown)
+}
+
+impl PulseBreathingEffect {
+    pub fn new() -> Self {
+        Self {
+            radius: MIN_RADIUS,
+            fade: 0.0,
+        }
+    }
+}
+
+impl FaceElement for PulseBreathingEffect {
+    fn name(&self) -> &str {
+        "Pulse Breathing"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Glow from the center of the panel that pulses with idle breathing"
+    }
+
+    // Behind the face
+    fn z_order(&self) -> i32 {
+        -30
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        let target = if shared_state.idle_breathing { 1.0 } else { 0.0 };
+        let step = FADE_RATE * dt;
+        self.fade = if self.fade < target {
+            (self.fade + step).min(target)
+        } else {
+            (self.fade - step).max(target)
+        };
+
+        let breath = (shared_state.mouth_opening / MOUTH_MAX_OPENING).clamp(0.0, 1.0);
+        self.radius = MIN_RADIUS + PULSE_RADIUS * breath;
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.fade <= 0.0 {
+            return;
+        }
+
+        for x in 0..PANEL_WIDTH {
+            for y in 0..PANEL_HEIGHT {
+                let dx = x as f64 + 0.5 - CENTER_X;
+                let dy = y as f64 + 0.5 - CENTER_Y;
+                let distance = (dx * dx + dy * dy).sqrt();
+                if distance >= self.radius {
+                    continue;
+                }
+                // Bright center fading smoothly to black at the edge
+                let falloff = 1.0 - distance / self.radius;
+                let bright = GLOW_BRIGHTNESS * falloff * falloff * self.fade;
+                draw_pixel_fn.draw(canvas, bright, 20.0 + distance, x, y,
+                                  context.brightness, context.palette);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::canvas::VirtualLedCanvas;
+
+/// Highest strobe rate allowed, whatever is requested
+/// Flashing above ~3 Hz is a photosensitive seizure risk, so this is a hard cap
+pub const MAX_STROBE_HZ: f64 = 3.0;
+
+/// Strobe post-process: blanks the whole frame for the second half of every cycle
+pub struct StrobeEffect {
+    elapsed: f64,  // Seconds since the strobe was switched on
+}
+
+impl StrobeEffect {
+    pub fn new() -> Self {
+        Self { elapsed: 0.0 }
+    }
+
+    /// Limit a requested rate to the safe range (0 = off)
+    pub fn safe_hz(hz: f64) -> f64 {
+        if hz.is_finite() { hz.clamp(0.0, MAX_STROBE_HZ) } else { 0.0 }
+    }
+
+    /// Advance the strobe and blank the frame during the off half of the cycle
+    /// None stops the strobe, so the next start begins with a visible frame
+    pub fn apply(&mut self, canvas: &mut VirtualLedCanvas, hz: Option<f64>, dt: f64) {
+        let hz = match hz.map(Self::safe_hz) {
+            Some(hz) if hz > 0.0 => hz,
+            _ => {
+                self.elapsed = 0.0;
+                return;
+            }
+        };
+
+        if (self.elapsed * hz).fract() >= 0.5 {
+            canvas.clear();
+        }
+        self.elapsed += dt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strobe_rate_is_capped() {
+        assert_eq!(StrobeEffect::safe_hz(2.0), 2.0);
+        assert_eq!(StrobeEffect::safe_hz(50.0), MAX_STROBE_HZ);
+        assert_eq!(StrobeEffect::safe_hz(-1.0), 0.0);
+        assert_eq!(StrobeEffect::safe_hz(f64::NAN), 0.0);
+    }
+}
@@ -0,0 +1,130 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use crate::audio::{AudioLevel, WAVEFORM_LEN};
+use crate::canvas::VirtualLedCanvas;
+use crate::color::get_shimmer_color;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState,
+                  ConfigField};
+use crate::PANEL_HEIGHT;
+
+// Microphone samples are quiet, scale them up so speech fills the panel height
+const WAVEFORM_GAIN: f64 = 4.0;
+
+// Weight of the new frame when smoothing (lower = calmer line)
+const SMOOTHING: f64 = 0.35;
+
+/// Oscilloscope-style line of the live microphone signal across both panels
+/// Replaces the face while enabled (enable with `waveform_enabled` or the HTTP API)
+#[derive(Clone)]
+pub struct WaveformDisplay {
+    audio_level: Arc<AudioLevel>,
+    samples: VecDeque<f64>,  // One per column, oldest (left) first
+    smoothed: bool,          // Average neighbours and blend with the last frame
+}
+
+impl WaveformDisplay {
+    pub fn new(audio_level: Arc<AudioLevel>, smoothed: bool) -> Self {
+        Self {
+            audio_level,
+            samples: VecDeque::from(vec![0.0; WAVEFORM_LEN]),
+            smoothed,
+        }
+    }
+
+    pub fn set_smoothed(&mut self, smoothed: bool) {
+        self.smoothed = smoothed;
+    }
+
+    // Canvas row for a sample (y up from the bottom, then flipped like PixelDrawer)
+    fn row(sample: f64) -> i32 {
+        let sample = (sample * WAVEFORM_GAIN).clamp(-1.0, 1.0);
+        let y = (sample * (PANEL_HEIGHT / 2) as f64 + (PANEL_HEIGHT / 2) as f64) as i32;
+        PANEL_HEIGHT - 1 - y.clamp(0, PANEL_HEIGHT - 1)
+    }
+}
+
+impl FaceElement for WaveformDisplay {
+    fn name(&self) -> &str {
+        "Waveform"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Live audio waveform across both panels"
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        vec![ConfigField::new("waveform_smoothed", self.smoothed,
+                              "Smooth the waveform (false = raw samples)")]
+    }
+
+    // Over the face, under the matrix rain
+    fn z_order(&self) -> i32 {
+        150
+    }
+
+    // Replaces the face while enabled
+    fn wants_exclusive_render(&self) -> bool {
+        true
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        let latest = self.audio_level.get_samples();
+        // Right-align so a partly filled buffer scrolls in from the right
+        let start = WAVEFORM_LEN - latest.len().min(WAVEFORM_LEN);
+
+        if !self.smoothed {
+            for (i, sample) in latest.iter().take(WAVEFORM_LEN).enumerate() {
+                self.samples[start + i] = *sample;
+            }
+            return;
+        }
+
+        for i in 0..latest.len().min(WAVEFORM_LEN) {
+            let previous = latest[i.saturating_sub(1)];
+            let next = latest[(i + 1).min(latest.len() - 1)];
+            let averaged = (previous + latest[i] + next) / 3.0;
+            let column = &mut self.samples[start + i];
+            *column += (averaged - *column) * SMOOTHING;
+        }
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {
+        // Runs across both panels left to right rather than mirrored, so this
+        // writes the canvas directly
+        let bright = 255.0 * context.brightness;
+        let mut last_row = None;
+        for (x, &sample) in self.samples.iter().enumerate() {
+            let color = get_shimmer_color(x as f64 + context.time_counter * 0.5, bright,
+                                          context.palette);
+            let row = Self::row(sample);
+            // Fill the gap to the previous column so steep edges stay connected
+            let (top, bottom) = match last_row {
+                Some(last) => (row.min(last), row.max(last)),
+                None => (row, row),
+            };
+            for y in top..=bottom {
+                canvas.set(x as i32, y, &color);
+            }
+            last_row = Some(row);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
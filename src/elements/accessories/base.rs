@@ -0,0 +1,45 @@
+use std::any::Any;
+use gilrs::Button;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ElementCategory, ConfigField};
+
+/// Base trait for all accessory implementations
+/// Accessories are optional effects layered on top of the face
+pub trait Accessory: Send + Sync {
+    /// Get the name of this accessory
+    fn name(&self) -> &str;
+
+    /// Get a description of this accessory
+    fn description(&self) -> &str;
+
+    /// Update accessory state (particles, animation phase, etc.)
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64);
+
+    /// Draw the accessory to the canvas
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn);
+
+    /// Handle a gamepad button, returns true if the button was consumed
+    fn handle_button(&mut self, _button: Button, _shared_state: &mut SharedFaceState) -> bool {
+        false
+    }
+
+    /// Render layer, higher draws on top (see `FaceElement::z_order`)
+    fn z_order(&self) -> i32 {
+        ElementCategory::Accessory.default_z_order()
+    }
+
+    /// Settings reported by GET /elements (see `FaceElement::config_fields`)
+    fn config_fields(&self) -> Vec<ConfigField> {
+        Vec::new()
+    }
+
+    /// Access the concrete type for downcasting (see `FaceElementRegistry::get_element_mut`)
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Clone this accessory into a Box
+    fn clone_box(&self) -> Box<dyn Accessory>;
+}
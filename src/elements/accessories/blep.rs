@@ -0,0 +1,131 @@
+use std::any::Any;
+use gilrs::Button;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ConfigField};
+
+pub const DEFAULT_AUTO_RETRACT_SECS: f64 = 2.0;
+
+// Tongue hangs from the lowest point of the default mouth's chevron
+const TONGUE_CENTER_X: f64 = 54.0;
+const TONGUE_HALF_WIDTH: f64 = 2.5;
+const TONGUE_TOP_Y: f64 = 7.0;
+const TONGUE_LENGTH: f64 = 4.0;
+const SLIDE_SPEED: f64 = 8.0; // Extension per second (0 = hidden, 1 = fully out)
+
+/// Small tongue sticking out below the closed mouth, toggled with the left stick click
+#[derive(Clone)]
+pub struct BlepAccessory {
+    shown: bool,
+    extension: f64,                 // 0.0 (retracted) to 1.0 (fully out)
+    auto_retract: Option<f64>,      // Hide again after this many seconds (None = stay out)
+    remaining: f64,                 // Time left before auto-retract
+}
+
+impl BlepAccessory {
+    pub fn new() -> Self {
+        Self::with_auto_retract(Some(DEFAULT_AUTO_RETRACT_SECS))
+    }
+
+    pub fn with_auto_retract(auto_retract: Option<f64>) -> Self {
+        Self {
+            shown: false,
+            extension: 0.0,
+            auto_retract,
+            remaining: 0.0,
+        }
+    }
+
+    /// Change the auto-retract time (None = stay out until pressed again)
+    pub fn set_auto_retract(&mut self, auto_retract: Option<f64>) {
+        self.auto_retract = auto_retract;
+    }
+}
+
+impl Accessory for BlepAccessory {
+    fn name(&self) -> &str {
+        "Blep"
+    }
+
+    fn description(&self) -> &str {
+        "Tongue sticking out below a closed mouth"
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        if self.shown && self.auto_retract.is_some() {
+            self.remaining -= dt;
+            if self.remaining <= 0.0 {
+                self.shown = false;
+            }
+        }
+
+        let target = if self.shown { 1.0 } else { 0.0 };
+        if self.extension < target {
+            self.extension = (self.extension + SLIDE_SPEED * dt).min(target);
+        } else {
+            self.extension = (self.extension - SLIDE_SPEED * dt).max(target);
+        }
+
+        // Accessories update after the mouth, so this keeps the mouth closed while blepping
+        if self.extension > 0.0 {
+            shared_state.mouth_opening = 0.0;
+        }
+    }
+
+    fn handle_button(&mut self, button: Button, _shared_state: &mut SharedFaceState) -> bool {
+        if button != Button::LeftThumb {
+            return false;
+        }
+        self.shown = !self.shown;
+        if let Some(secs) = self.auto_retract {
+            self.remaining = secs;
+        }
+        true
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        vec![ConfigField::new("blep_auto_retract_secs", self.auto_retract.unwrap_or(0.0),
+                              "Seconds before the tongue goes back in (0 = stays out until pressed again)")]
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.extension <= 0.0 {
+            return;
+        }
+        let bright = 255.0;
+        let length = TONGUE_LENGTH * self.extension;
+
+        let min_x = (TONGUE_CENTER_X - TONGUE_HALF_WIDTH).ceil() as i32;
+        let max_x = (TONGUE_CENTER_X + TONGUE_HALF_WIDTH).floor() as i32;
+        for x in min_x..=max_x {
+            // Rounded tip: shorter toward the edges
+            let dx = (x as f64 - TONGUE_CENTER_X) / TONGUE_HALF_WIDTH;
+            let depth = length * (1.0 - dx * dx).max(0.0).sqrt();
+
+            let mut y = TONGUE_TOP_Y;
+            while y >= TONGUE_TOP_Y - depth {
+                // Dimmer center line for the tongue groove
+                let groove = x as f64 == TONGUE_CENTER_X && y < TONGUE_TOP_Y - 1.0;
+                let pixel_bright = if groove { bright * 0.4 } else { bright };
+                let color = context.shimmer_index(x, y as i32);
+                draw_pixel_fn.draw(canvas, pixel_bright, color, x, y as i32,
+                                  context.brightness, context.palette);
+                y -= 1.0;
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
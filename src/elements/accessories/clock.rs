@@ -0,0 +1,109 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::color::get_shimmer_color;
+use crate::font::{BitmapFont, GLYPH_HEIGHT, GLYPH_SPACING, GLYPH_WIDTH};
+use crate::schedule::local_time;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Clock area in the top-right corner of the right panel (panel coordinates, y down)
+const CLOCK_WIDTH: i32 = 15;
+const CLOCK_HEIGHT: i32 = 7;
+
+// Fraction of full brightness, so the digits sit behind the face instead of competing
+const CLOCK_DIM: f64 = 0.35;
+
+/// Local time as HH:MM in the top-right corner of the right panel
+#[derive(Clone)]
+pub struct ClockOverlay {
+    digits: [char; 4],
+    colon_on: bool,    // Colon blinks once a second
+    since_read: f64,   // Seconds since the time was last read
+}
+
+impl ClockOverlay {
+    pub fn new() -> Self {
+        Self {
+            digits: ['0'; 4],
+            colon_on: true,
+            since_read: f64::INFINITY,  // Read on the first update
+        }
+    }
+}
+
+impl Accessory for ClockOverlay {
+    fn name(&self) -> &str {
+        "Clock"
+    }
+
+    fn description(&self) -> &str {
+        "Current time (HH:MM) in the top-right corner of the right panel"
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        self.since_read += dt;
+        if self.since_read < 1.0 {
+            return;
+        }
+        self.since_read = 0.0;
+        let (hours, minutes, seconds) = local_time();
+        let text = format!("{:02}{:02}", hours, minutes);
+        for (digit, c) in self.digits.iter_mut().zip(text.chars()) {
+            *digit = c;
+        }
+        self.colon_on = seconds % 2 == 0;
+    }
+
+    // Written straight to the canvas, only on the right panel: the mirrored copy on the
+    // left panel would read backwards
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {
+        let left = PANEL_WIDTH * 2 - CLOCK_WIDTH;
+        let top = (CLOCK_HEIGHT - GLYPH_HEIGHT) / 2;
+        let mut plot = |x: i32, y: i32| {
+            // Palette position of the mirrored face pixel at this spot, like the rest of the face
+            let color_index = context.shimmer_index(PANEL_WIDTH * 2 - 1 - x, PANEL_HEIGHT - 1 - y);
+            let color = get_shimmer_color(color_index, 255.0 * CLOCK_DIM * context.brightness,
+                                          context.palette);
+            canvas.set(x, y, &color);
+        };
+
+        // Two digits, a one-pixel colon column, two digits: exactly CLOCK_WIDTH wide
+        let colon_x = left + 2 * (GLYPH_WIDTH + GLYPH_SPACING) - GLYPH_SPACING;
+        for (i, &digit) in self.digits.iter().enumerate() {
+            let origin_x = if i < 2 {
+                left + i as i32 * (GLYPH_WIDTH + GLYPH_SPACING)
+            } else {
+                colon_x + 1 + (i as i32 - 2) * (GLYPH_WIDTH + GLYPH_SPACING)
+            };
+            for row in 0..GLYPH_HEIGHT {
+                for col in 0..GLYPH_WIDTH {
+                    if BitmapFont::is_lit(digit, col, row) {
+                        plot(origin_x + col, top + row);
+                    }
+                }
+            }
+        }
+        if self.colon_on {
+            for row in 0..GLYPH_HEIGHT {
+                if BitmapFont::is_lit(':', 1, row) {
+                    plot(colon_x, top + row);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
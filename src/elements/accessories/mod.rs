@@ -0,0 +1,40 @@
+// Accessory base trait
+pub mod base;
+
+// Individual accessory implementations
+mod sparkle;
+mod snow;
+mod plasma;
+mod blep;
+mod spectrum;
+mod clock;
+
+// Re-export the base trait
+pub use base::Accessory;
+
+// Re-export all accessory implementations
+pub use sparkle::SparkleAccessory;
+pub use snow::SnowAccessory;
+pub use plasma::PlasmaWaveAccessory;
+pub use blep::{BlepAccessory, DEFAULT_AUTO_RETRACT_SECS};
+pub use spectrum::SpectrumBarsAccessory;
+pub use clock::ClockOverlay;
+
+use crate::audio::AudioLevel;
+use crate::config::Config;
+use std::sync::Arc;
+
+/// Get all available accessory types as boxed trait objects
+/// This allows the registry to auto-discover all accessory implementations
+pub fn get_all_accessory_types(audio_level: Arc<AudioLevel>) -> Vec<Box<dyn Accessory>> {
+    let defaults = Config::default();
+    vec![
+        Box::new(SparkleAccessory::new()),
+        Box::new(SnowAccessory::new()),
+        Box::new(PlasmaWaveAccessory::new()),
+        Box::new(BlepAccessory::new()),
+        Box::new(SpectrumBarsAccessory::new(audio_level, defaults.spectrum_bands,
+                                            defaults.spectrum_y)),
+        Box::new(ClockOverlay::new()),
+    ]
+}
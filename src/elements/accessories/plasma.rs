@@ -0,0 +1,74 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+
+// Horizontal span of the wave (open area between the eyes and the nose)
+const WAVE_MIN_X: i32 = 27;
+const WAVE_MAX_X: i32 = 50;
+const WAVE_CENTER_Y: f64 = 23.0;
+const WAVE_AMPLITUDE: f64 = 3.0;
+const WAVE_THICKNESS: f64 = 1.5;
+
+/// Soft flowing sine wave with plasma-style color
+#[derive(Clone)]
+pub struct PlasmaWaveAccessory {
+    phase: f64,
+}
+
+impl PlasmaWaveAccessory {
+    pub fn new() -> Self {
+        Self { phase: 0.0 }
+    }
+}
+
+impl Accessory for PlasmaWaveAccessory {
+    fn name(&self) -> &str {
+        "Plasma Wave"
+    }
+
+    fn description(&self) -> &str {
+        "Gentle flowing plasma wave"
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        self.phase += dt * 1.5;
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        // Kept dim so it reads as ambient rather than part of the face
+        let bright = 255.0 * 0.4;
+
+        for x in WAVE_MIN_X..=WAVE_MAX_X {
+            let x_f = x as f64;
+            let center = WAVE_CENTER_Y + WAVE_AMPLITUDE * (x_f * 0.3 + self.phase).sin();
+            let plasma = (x_f * 0.15 + self.phase).sin() + (x_f * 0.07 - self.phase * 0.6).sin();
+
+            let min_y = (center - WAVE_THICKNESS).floor() as i32;
+            let max_y = (center + WAVE_THICKNESS).ceil() as i32;
+            for y in min_y..=max_y {
+                let distance = (y as f64 - center).abs();
+                if distance >= WAVE_THICKNESS {
+                    continue;
+                }
+                let color = context.time_counter + plasma * 20.0 + (y as f64) * 5.0;
+                draw_pixel_fn.draw(canvas, bright * (1.0 - distance / WAVE_THICKNESS), color,
+                                  x, y, context.brightness, context.palette);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
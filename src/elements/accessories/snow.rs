@@ -0,0 +1,90 @@
+use std::any::Any;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::rng;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+const FLAKE_COUNT: usize = 14;
+
+#[derive(Clone)]
+struct Flake {
+    x: f64,
+    y: f64,
+    speed: f64,  // Pixels per second
+    phase: f64,  // Sideways drift phase
+}
+
+/// Gently falling snowflakes across the whole panel
+#[derive(Clone)]
+pub struct SnowAccessory {
+    flakes: Vec<Flake>,
+    rng: StdRng,
+}
+
+impl SnowAccessory {
+    pub fn new() -> Self {
+        let mut rng = rng::seeded_rng();
+        let flakes = (0..FLAKE_COUNT)
+            .map(|_| Flake {
+                x: rng.gen_range(1.0..=PANEL_WIDTH as f64),
+                y: rng.gen_range(0.0..=PANEL_HEIGHT as f64),
+                speed: rng.gen_range(3.0..7.0),
+                phase: rng.gen_range(0.0..std::f64::consts::TAU),
+            })
+            .collect();
+
+        Self { flakes, rng }
+    }
+}
+
+impl Accessory for SnowAccessory {
+    fn name(&self) -> &str {
+        "Snow"
+    }
+
+    fn description(&self) -> &str {
+        "Slowly falling snowflakes"
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        for flake in self.flakes.iter_mut() {
+            // Element y grows upward, so falling means decreasing y
+            flake.y -= flake.speed * dt;
+            flake.phase += dt * 2.0;
+
+            if flake.y < 0.0 {
+                flake.y = PANEL_HEIGHT as f64;
+                flake.x = self.rng.gen_range(1.0..=PANEL_WIDTH as f64);
+            }
+        }
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 180.0;
+
+        for flake in &self.flakes {
+            let x = (flake.x + flake.phase.sin()).round() as i32;
+            let y = flake.y.round() as i32;
+            let color = context.shimmer_index(x, y);
+            draw_pixel_fn.draw(canvas, bright, color, x, y,
+                              context.brightness, context.palette);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,102 @@
+use std::any::Any;
+use std::f64::consts::PI;
+use rand::Rng;
+use rand::rngs::StdRng;
+use crate::rng;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+
+const MAX_SPARKLES: usize = 6;
+const SPARKLE_LIFETIME: f64 = 0.8; // Seconds from fade-in to fade-out
+
+// Open area between the eyes and the nose where sparkles appear
+const AREA_MIN_X: i32 = 26;
+const AREA_MAX_X: i32 = 50;
+const AREA_MIN_Y: i32 = 15;
+const AREA_MAX_Y: i32 = 30;
+
+#[derive(Clone)]
+struct Sparkle {
+    x: i32,
+    y: i32,
+    age: f64,
+}
+
+/// Twinkling sparkles that fade in and out at random positions
+#[derive(Clone)]
+pub struct SparkleAccessory {
+    sparkles: Vec<Sparkle>,
+    spawn_timer: f64,
+    rng: StdRng,
+}
+
+impl SparkleAccessory {
+    pub fn new() -> Self {
+        Self {
+            sparkles: Vec::new(),
+            spawn_timer: 0.0,
+            rng: rng::seeded_rng(),
+        }
+    }
+}
+
+impl Accessory for SparkleAccessory {
+    fn name(&self) -> &str {
+        "Sparkle"
+    }
+
+    fn description(&self) -> &str {
+        "Twinkling sparkles around the face"
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        for sparkle in self.sparkles.iter_mut() {
+            sparkle.age += dt;
+        }
+        self.sparkles.retain(|s| s.age < SPARKLE_LIFETIME);
+
+        self.spawn_timer -= dt;
+        if self.spawn_timer <= 0.0 && self.sparkles.len() < MAX_SPARKLES {
+            self.sparkles.push(Sparkle {
+                x: self.rng.gen_range(AREA_MIN_X..=AREA_MAX_X),
+                y: self.rng.gen_range(AREA_MIN_Y..=AREA_MAX_Y),
+                age: 0.0,
+            });
+            self.spawn_timer = self.rng.gen_range(0.1..0.4);
+        }
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 255.0;
+
+        for sparkle in &self.sparkles {
+            // Fade in then out over the sparkle's lifetime
+            let intensity = (PI * sparkle.age / SPARKLE_LIFETIME).sin();
+            let color = context.shimmer_index(sparkle.x, sparkle.y);
+
+            // Bright center with a dimmer plus-shaped glint
+            draw_pixel_fn.draw(canvas, bright * intensity, color, sparkle.x, sparkle.y,
+                              context.brightness, context.palette);
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                draw_pixel_fn.draw(canvas, bright * intensity * 0.4, color,
+                                  sparkle.x + dx, sparkle.y + dy,
+                                  context.brightness, context.palette);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
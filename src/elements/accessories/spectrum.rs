@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::sync::Arc;
+use crate::audio::{AudioLevel, spectrum_bands};
+use crate::canvas::VirtualLedCanvas;
+use super::base::Accessory;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ConfigField};
+use crate::PANEL_WIDTH;
+
+const MAX_BANDS: usize = 32;
+const BAR_HEIGHT: f64 = 6.0;       // Rows a full-scale band reaches
+const BAND_GAIN: f64 = 6.0;        // Voice-level bands are small, boost before the sqrt curve
+const PEAK_HOLD_SECS: f64 = 0.4;   // How long a peak cap stays before falling
+const PEAK_FALL_RATE: f64 = 8.0;   // Rows per second a cap falls after the hold
+
+/// Music-visualizer bars along the bottom of the face, one per frequency band,
+/// with peak caps that hang for a moment and then drift down
+/// Draws nothing without microphone samples
+#[derive(Clone)]
+pub struct SpectrumBarsAccessory {
+    audio_level: Arc<AudioLevel>,
+    base_y: i32,          // Bottom row of the bars (element coordinates, y up)
+    heights: Vec<f64>,    // Current bar heights in rows
+    peaks: Vec<f64>,      // Peak cap heights in rows
+    peak_age: Vec<f64>,   // Seconds since each peak was set
+}
+
+impl SpectrumBarsAccessory {
+    pub fn new(audio_level: Arc<AudioLevel>, bands: usize, base_y: i32) -> Self {
+        let mut spectrum = Self {
+            audio_level,
+            base_y,
+            heights: Vec::new(),
+            peaks: Vec::new(),
+            peak_age: Vec::new(),
+        };
+        spectrum.set_bands(bands);
+        spectrum
+    }
+
+    /// Number of bars across each panel (1 to 32)
+    pub fn set_bands(&mut self, bands: usize) {
+        let bands = bands.clamp(1, MAX_BANDS);
+        self.heights = vec![0.0; bands];
+        self.peaks = vec![0.0; bands];
+        self.peak_age = vec![0.0; bands];
+    }
+
+    pub fn set_base_y(&mut self, base_y: i32) {
+        self.base_y = base_y;
+    }
+}
+
+impl Accessory for SpectrumBarsAccessory {
+    fn name(&self) -> &str {
+        "Spectrum Bars"
+    }
+
+    fn description(&self) -> &str {
+        "Audio spectrum bars with falling peak caps along the bottom edge"
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        vec![
+            ConfigField::new("spectrum_bands", self.heights.len(), "Number of bars per panel (1-32)"),
+            ConfigField::new("spectrum_y", self.base_y, "Row the bars stand on (0 = bottom edge)"),
+        ]
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        let bands = spectrum_bands(&self.audio_level.get_samples(), self.heights.len());
+        for (i, band) in bands.iter().enumerate() {
+            let height = (band * BAND_GAIN).sqrt().min(1.0) * BAR_HEIGHT;
+            self.heights[i] = height;
+
+            self.peak_age[i] += dt;
+            if height >= self.peaks[i] {
+                self.peaks[i] = height;
+                self.peak_age[i] = 0.0;
+            } else if self.peak_age[i] > PEAK_HOLD_SECS {
+                self.peaks[i] = (self.peaks[i] - PEAK_FALL_RATE * dt).max(height);
+            }
+        }
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bands = self.heights.len() as i32;
+        let slot = PANEL_WIDTH / bands;
+        let bar_width = (slot - 1).max(1);
+
+        for band in 0..bands {
+            let height = self.heights[band as usize].round() as i32;
+            let peak = self.peaks[band as usize].round() as i32;
+            let x0 = band * slot;
+            let color = context.time_counter + band as f64 * 8.0;
+
+            for x in x0..x0 + bar_width {
+                for row in 0..height {
+                    draw_pixel_fn.draw(canvas, 200.0, color + row as f64 * 6.0, x, self.base_y + row,
+                                      context.brightness, context.palette);
+                }
+                // Cap sits on top of the bar, or above it while the peak is held
+                if peak > 0 {
+                    draw_pixel_fn.draw(canvas, 255.0, color + 40.0, x, self.base_y + peak,
+                                      context.brightness, context.palette);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Accessory> {
+        Box::new(self.clone())
+    }
+}
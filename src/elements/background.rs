@@ -0,0 +1,83 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::color::hue_to_color;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState,
+                  ConfigField};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+/// Faint rainbow that slowly cycles behind the face
+/// Drawn first (z_order -100) so every other element renders on top of it
+#[derive(Clone)]
+pub struct RainbowCycleBackground {
+    brightness: f64,  // Fraction of full brightness, 0.0 = off
+}
+
+impl RainbowCycleBackground {
+    pub fn new(brightness: f64) -> Self {
+        Self { brightness: brightness.clamp(0.0, 1.0) }
+    }
+
+    pub fn set_brightness(&mut self, brightness: f64) {
+        self.brightness = brightness.clamp(0.0, 1.0);
+    }
+}
+
+impl FaceElement for RainbowCycleBackground {
+    fn name(&self) -> &str {
+        "Rainbow Background"
+    }
+
+    // Accessory rather than Mouth so it never competes with the mouth variants;
+    // z_order is what puts it behind everything
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Faint slowly cycling rainbow behind the face"
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        vec![ConfigField::new("background_brightness", self.brightness,
+                              "Fraction of full brightness (0 = off)")]
+    }
+
+    fn z_order(&self) -> i32 {
+        -100
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {}
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.brightness <= 0.0 {
+            return;
+        }
+        let brightness = self.brightness * context.brightness;
+
+        // Full-panel fill in raw RGB, so this writes the canvas directly instead of going
+        // through the palette-based pixel drawer (flipped and mirrored the same way)
+        for x in 0..PANEL_WIDTH {
+            for y in 0..PANEL_HEIGHT {
+                let hue = (x as f64 + y as f64 + context.time_counter * 0.1) % 360.0;
+                let color = hue_to_color(hue, brightness);
+                let flipped_y = PANEL_HEIGHT - 1 - y;
+                canvas.set(x, flipped_y, &color);
+                canvas.set(PANEL_WIDTH * 2 - 1 - x, flipped_y, &color);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,94 @@
+use std::any::Any;
+use crate::battery::{BatteryLevel, BATTERY_LOW_PERCENT};
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::font::{BitmapFont, BATTERY_LOW_ICON, GLYPH_WIDTH};
+use crate::{PANEL_HEIGHT, PANEL_WIDTH};
+
+// How long the icon flashes up each time, and how often while the battery stays low
+const WARNING_SHOW_SECS: f64 = 2.0;
+const WARNING_REPEAT_SECS: f64 = 30.0;
+
+/// Empty battery icon in the top corner, beside the thermometer, while the UPS HAT
+/// battery is almost flat
+#[derive(Clone)]
+pub struct BatteryWarningElement {
+    level: BatteryLevel,
+    show_secs: f64,      // Time left showing the icon
+    since_warning: f64,  // Seconds since the icon last came up
+}
+
+impl BatteryWarningElement {
+    pub fn new(level: BatteryLevel) -> Self {
+        Self {
+            level,
+            show_secs: 0.0,
+            since_warning: WARNING_REPEAT_SECS,
+        }
+    }
+}
+
+impl FaceElement for BatteryWarningElement {
+    fn name(&self) -> &str {
+        "Battery Warning"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Battery icon flashed for 2 seconds every 30 seconds while the battery is under 10%"
+    }
+
+    // Above the other accessories so it is never hidden
+    fn z_order(&self) -> i32 {
+        ElementCategory::Accessory.default_z_order() + 10
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        let low = *self.level.lock().unwrap() < BATTERY_LOW_PERCENT;
+        if low {
+            self.since_warning += dt;
+            if self.since_warning >= WARNING_REPEAT_SECS {
+                self.since_warning = 0.0;
+                self.show_secs = WARNING_SHOW_SECS;
+            }
+        } else {
+            // Show straight away if it drops again (e.g. unplugged from the charger)
+            self.since_warning = WARNING_REPEAT_SECS;
+        }
+        self.show_secs = (self.show_secs - dt).max(0.0);
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.show_secs <= 0.0 {
+            return;
+        }
+        let bright = 255.0;
+        // One icon width further from the nose than the thermometer, so both fit
+        let left = PANEL_WIDTH - 2 * (GLYPH_WIDTH + 1);
+        let top = PANEL_HEIGHT - 2;
+
+        BitmapFont::for_each_icon_pixel(&BATTERY_LOW_ICON, |dx, dy| {
+            let x = left + dx;
+            let y = top - dy;
+            draw_pixel_fn.draw(canvas, bright, context.shimmer_index(x, y), x, y,
+                               context.brightness, context.palette);
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
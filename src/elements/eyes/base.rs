@@ -1,5 +1,6 @@
-use rpi_led_matrix::LedCanvas;
-use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ElementCategory};
 
 /// Base trait for all eye implementations
 /// Defines the common interface for eye rendering and animation
@@ -11,12 +12,28 @@ pub trait Eye: Send + Sync {
     fn description(&self) -> &str;
 
     /// Update eye state (blinking, animation, etc.)
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64);
 
     /// Draw the eye to the canvas
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn);
 
+    /// Eye center, used to slide smoothly between variants with different positions
+    fn position(&self) -> EyePosition {
+        EyePosition::default()
+    }
+
+    /// Render layer, higher draws on top (see `FaceElement::z_order`)
+    fn z_order(&self) -> i32 {
+        ElementCategory::Eyes.default_z_order()
+    }
+
+    /// Access the concrete type for downcasting (see `FaceElementRegistry::get_element_mut`)
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
     /// Clone this eye into a Box
     fn clone_box(&self) -> Box<dyn Eye>;
 }
@@ -1,4 +1,5 @@
-use rpi_led_matrix::LedCanvas;
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
 use super::base::{Eye, EyePosition};
 use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
 use crate::{PANEL_WIDTH, PANEL_HEIGHT};
@@ -30,13 +31,14 @@ impl Eye for CrossEyes {
         "X-shaped eyes - dizzy expression"
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, _dt: f64) {
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
         // X eyes don't blink
         shared_state.eye_top = 9.0;
         shared_state.eye_bottom = 1.45;
     }
 
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         let bright = 255.0;
         let offset_x = context.offset_x;
@@ -47,10 +49,8 @@ impl Eye for CrossEyes {
         let cy = self.position.center_y + offset_y;
 
         for x in 1..=PANEL_WIDTH {
-            let mut color = context.time_counter + (x as f64) * 5.0;
-
             for y in 0..=PANEL_HEIGHT {
-                color += 5.0;
+                let color = context.shimmer_index(x, y);
                 let dx = (x as f64 - cx).abs();
                 let dy = (y as f64 - cy).abs();
 
@@ -67,6 +67,18 @@ impl Eye for CrossEyes {
         }
     }
 
+    fn position(&self) -> EyePosition {
+        self.position
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn clone_box(&self) -> Box<dyn Eye> {
         Box::new(self.clone())
     }
@@ -1,5 +1,6 @@
+use std::any::Any;
 use std::time::Instant;
-use rpi_led_matrix::LedCanvas;
+use crate::canvas::VirtualLedCanvas;
 use super::base::{Eye, BlinkConfig};
 use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
 use crate::{PANEL_WIDTH, PANEL_HEIGHT};
@@ -10,6 +11,7 @@ pub struct DefaultEyes {
     blink_sec: i32,
     blink_frame: i32,
     blink_flag: bool,
+    blink_now: bool,  // Blink requested by trigger_blink, runs even with blinking turned off
     last_second: u64,
     start_time: Instant,
     config: BlinkConfig,
@@ -21,6 +23,7 @@ impl DefaultEyes {
             blink_sec: 0,
             blink_frame: 0,
             blink_flag: true,
+            blink_now: false,
             last_second: 0,
             start_time: Instant::now(),
             config: BlinkConfig::default(),
@@ -32,11 +35,20 @@ impl DefaultEyes {
             blink_sec: 0,
             blink_frame: 0,
             blink_flag: true,
+            blink_now: false,
             last_second: 0,
             start_time: Instant::now(),
             config,
         }
     }
+
+    /// Start a blink right away instead of waiting for the interval
+    pub fn trigger_blink(&mut self) {
+        self.blink_now = true;
+        self.blink_sec = self.config.interval_secs;
+        self.blink_frame = 0;
+        self.blink_flag = true;
+    }
 }
 
 impl Eye for DefaultEyes {
@@ -48,7 +60,8 @@ impl Eye for DefaultEyes {
         "Original protogen eyes with blinking animation"
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, _dt: f64) {
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
         // Update second counter
         let current_second = self.start_time.elapsed().as_secs();
         if current_second != self.last_second {
@@ -57,7 +70,7 @@ impl Eye for DefaultEyes {
         }
 
         // Blinking logic (Arduino code)
-        if !shared_state.blink_enabled {
+        if !shared_state.blink_enabled && !self.blink_now {
             shared_state.eye_top = 9.0;
             shared_state.eye_bottom = 1.45;
             return;
@@ -104,10 +117,11 @@ impl Eye for DefaultEyes {
             self.blink_sec = 0;
             self.blink_frame = 0;
             self.blink_flag = true;
+            self.blink_now = false;
         }
     }
 
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         let bright = 255.0;
         let offset_x = context.offset_x;
@@ -127,23 +141,22 @@ impl Eye for DefaultEyes {
         let angle_y_b = shared_state.eye_top;
         let angle_y_c = -0.6;
 
-        let color_zero = context.time_counter;
-
         // Render eyes (Arduino rendering logic)
         for x in 1..=PANEL_WIDTH {
-            let mut color = color_zero + (x as f64) * 5.0;
-
             let y_a = (cord_y_a_x - x as f64) / angle_y_a + cord_y_a_y;
             let y_b = (cord_y_b_x - x as f64) / angle_y_b + cord_y_b_y;
             let y_c = (cord_y_c_x - x as f64) / angle_y_c + cord_y_c_y;
             let y_d = 0.8 * (x as f64 - cord_y_d_x).powi(2) + cord_y_d_y;
 
             for y in 0..=PANEL_HEIGHT {
-                color += 5.0;
+                let color = context.shimmer_index(x, y);
                 let y_f = y as f64;
 
                 if y_a < y_f && y_b > y_f && y_c < y_f && y_d > y_f {
-                    let brightness = if y_a < y_f - 1.0 && y_b > y_f + 1.0 &&
+                    // Edge pixels fade by how far inside the edge they are (unless turned off)
+                    let brightness = if !context.anti_aliasing {
+                        bright
+                    } else if y_a < y_f - 1.0 && y_b > y_f + 1.0 &&
                                         y_c < y_f - 1.0 && y_d > y_f + 1.0 {
                         bright
                     } else if y_a > y_f - 1.0 {
@@ -164,6 +177,14 @@ impl Eye for DefaultEyes {
         }
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn clone_box(&self) -> Box<dyn Eye> {
         Box::new(self.clone())
     }
@@ -1,5 +1,6 @@
+use std::any::Any;
 use std::time::Instant;
-use rpi_led_matrix::LedCanvas;
+use crate::canvas::VirtualLedCanvas;
 use super::base::{Eye, EyePosition};
 use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
 use crate::{PANEL_WIDTH, PANEL_HEIGHT};
@@ -72,7 +73,8 @@ impl Eye for HeartEyes {
         "Heart-shaped eyes - cute expression"
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, _dt: f64) {
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
         // Update second counter
         let current_second = self.start_time.elapsed().as_secs();
         if current_second != self.last_second {
@@ -108,7 +110,7 @@ impl Eye for HeartEyes {
         }
     }
 
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         let bright = 255.0;
         let offset_x = context.offset_x;
@@ -141,7 +143,7 @@ impl Eye for HeartEyes {
                     // Check bounds
                     if x >= 1 && x <= PANEL_WIDTH && y >= 0 && y <= PANEL_HEIGHT {
                         // Calculate color with shimmer effect
-                        let color = context.time_counter + (x as f64) * 5.0 + (y as f64) * 5.0;
+                        let color = context.shimmer_index(x, y);
                         draw_pixel_fn.draw(canvas, bright, color, x, y,
                                           context.brightness, context.palette);
                     }
@@ -150,6 +152,18 @@ impl Eye for HeartEyes {
         }
     }
 
+    fn position(&self) -> EyePosition {
+        self.position
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn clone_box(&self) -> Box<dyn Eye> {
         Box::new(self.clone())
     }
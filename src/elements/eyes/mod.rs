@@ -6,6 +6,7 @@ mod default;
 mod heart;
 mod circle;
 mod cross;
+mod pupil;
 
 // Re-export the base trait and types
 pub use base::{Eye, EyePosition, BlinkConfig};
@@ -15,14 +16,19 @@ pub use default::DefaultEyes;
 pub use heart::HeartEyes;
 pub use circle::CircleEyes;
 pub use cross::CrossEyes;
+pub use pupil::PupilEyes;
+
+use crate::audio::AudioLevel;
+use std::sync::Arc;
 
 /// Get all available eye types as boxed trait objects
 /// This allows the registry to auto-discover all eye implementations
-pub fn get_all_eye_types() -> Vec<Box<dyn Eye>> {
+pub fn get_all_eye_types(audio_level: Arc<AudioLevel>) -> Vec<Box<dyn Eye>> {
     vec![
         Box::new(DefaultEyes::new()),
         Box::new(HeartEyes::new()),
         Box::new(CircleEyes::new()),
         Box::new(CrossEyes::new()),
+        Box::new(PupilEyes::new(audio_level)),
     ]
 }
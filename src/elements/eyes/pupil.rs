@@ -0,0 +1,106 @@
+use std::any::Any;
+use std::sync::Arc;
+use crate::canvas::VirtualLedCanvas;
+use super::base::{Eye, EyePosition};
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use crate::audio::{AudioLevel, SILENT_LIMIT};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+// Eye white (dim disc) and pupil size range
+const EYE_RADIUS: f64 = 6.5;
+const MIN_PUPIL_RADIUS: f64 = 1.5;
+const MAX_PUPIL_RADIUS: f64 = EYE_RADIUS - 1.0; // Always leave a rim of eye white
+
+// Smoothed level at which the pupil is fully dilated
+const FULL_DILATION_LEVEL: f64 = 0.3;
+
+// How quickly the pupil follows the target size (per second)
+const DILATION_SPEED: f64 = 6.0;
+
+/// Round eyes whose pupil dilates with audio loudness
+#[derive(Clone)]
+pub struct PupilEyes {
+    position: EyePosition,
+    audio_level: Arc<AudioLevel>,
+    pupil_radius: f64,
+}
+
+impl PupilEyes {
+    pub fn new(audio_level: Arc<AudioLevel>) -> Self {
+        Self::with_position(audio_level, EyePosition::default())
+    }
+
+    pub fn with_position(audio_level: Arc<AudioLevel>, position: EyePosition) -> Self {
+        Self {
+            position,
+            audio_level,
+            pupil_radius: MIN_PUPIL_RADIUS,
+        }
+    }
+}
+
+impl Eye for PupilEyes {
+    fn name(&self) -> &str {
+        "Pupil Eyes"
+    }
+
+    fn description(&self) -> &str {
+        "Round eyes with pupils that dilate when it's loud"
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        // Pupils don't blink
+        shared_state.eye_top = 9.0;
+        shared_state.eye_bottom = 1.45;
+
+        // Map loudness above the silence threshold onto the pupil size
+        let level = self.audio_level.get_smoothed_level();
+        let amount = ((level - SILENT_LIMIT) / (FULL_DILATION_LEVEL - SILENT_LIMIT)).clamp(0.0, 1.0);
+        let target = MIN_PUPIL_RADIUS + (MAX_PUPIL_RADIUS - MIN_PUPIL_RADIUS) * amount;
+
+        self.pupil_radius += (target - self.pupil_radius) * (DILATION_SPEED * dt).min(1.0);
+        self.pupil_radius = self.pupil_radius.clamp(MIN_PUPIL_RADIUS, MAX_PUPIL_RADIUS);
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 255.0;
+        let cx = self.position.center_x + context.offset_x;
+        let cy = self.position.center_y + context.offset_y;
+
+        for x in 1..=PANEL_WIDTH {
+            for y in 0..=PANEL_HEIGHT {
+                let color = context.shimmer_index(x, y);
+                let dx = x as f64 - cx;
+                let dy = y as f64 - cy;
+                let dist = (dx * dx + dy * dy).sqrt();
+
+                // Bright pupil inside a dim eye white
+                if dist < self.pupil_radius {
+                    draw_pixel_fn.draw(canvas, bright, color, x, y,
+                                      context.brightness, context.palette);
+                } else if dist < EYE_RADIUS {
+                    draw_pixel_fn.draw(canvas, bright * 0.25, color, x, y,
+                                      context.brightness, context.palette);
+                }
+            }
+        }
+    }
+
+    fn position(&self) -> EyePosition {
+        self.position
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Eye> {
+        Box::new(self.clone())
+    }
+}
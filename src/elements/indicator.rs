@@ -0,0 +1,153 @@
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+use serde::Deserialize;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+
+// Reasons used by the built-in subsystems
+pub const NO_AUDIO_DEVICE: &str = "no audio device";
+pub const API_UNAVAILABLE: &str = "http api unavailable";
+
+// Spinner ring (8 positions around the center) and how fast it turns
+const SPINNER_RING: [(i32, i32); 8] = [(0, 2), (1, 1), (2, 0), (1, -1),
+                                       (0, -2), (-1, -1), (-2, 0), (-1, 1)];
+const SPINNER_STEPS_PER_SEC: f64 = 10.0;
+const PULSE_PER_SEC: f64 = 1.0;
+
+/// How the status indicator is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndicatorStyle {
+    Spinner, // Dot with a fading tail circling the center
+    Pulse,   // Small disc fading in and out
+}
+
+/// Thread-safe set of reasons the indicator should be shown
+/// Cheap to clone; subsystems keep a copy and call show/hide from any thread
+#[derive(Clone, Default)]
+pub struct StatusIndicators {
+    reasons: Arc<Mutex<Vec<String>>>,
+}
+
+impl StatusIndicators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start showing the indicator for a reason (no-op if already shown for it)
+    pub fn show(&self, reason: &str) {
+        let mut reasons = self.reasons.lock().unwrap();
+        if !reasons.iter().any(|r| r == reason) {
+            reasons.push(reason.to_string());
+        }
+    }
+
+    /// Stop showing the indicator for a reason
+    pub fn hide(&self, reason: &str) {
+        self.reasons.lock().unwrap().retain(|r| r != reason);
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.reasons.lock().unwrap().is_empty()
+    }
+
+    /// Reasons currently shown, oldest first
+    pub fn reasons(&self) -> Vec<String> {
+        self.reasons.lock().unwrap().clone()
+    }
+}
+
+/// Small busy/problem indicator drawn on top of the face while any reason is active
+#[derive(Clone)]
+pub struct StatusIndicatorElement {
+    indicators: StatusIndicators,
+    style: IndicatorStyle,
+    x: i32,
+    y: i32,
+    phase: f64,      // Seconds of animation
+    visible: bool,   // Cached each update so render doesn't need the lock
+}
+
+impl StatusIndicatorElement {
+    pub fn new(indicators: StatusIndicators, style: IndicatorStyle, x: i32, y: i32) -> Self {
+        Self {
+            indicators,
+            style,
+            x,
+            y,
+            phase: 0.0,
+            visible: false,
+        }
+    }
+}
+
+impl FaceElement for StatusIndicatorElement {
+    fn name(&self) -> &str {
+        "Status Indicator"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Spinner/pulse shown while a subsystem is connecting or missing"
+    }
+
+    // Above the other accessories so it is never hidden
+    fn z_order(&self) -> i32 {
+        ElementCategory::Accessory.default_z_order() + 10
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        self.visible = self.indicators.is_active();
+        self.phase = if self.visible { self.phase + dt } else { 0.0 };
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if !self.visible {
+            return;
+        }
+        let bright = 255.0;
+        let color = context.time_counter;
+
+        match self.style {
+            IndicatorStyle::Spinner => {
+                // Head at full brightness, trailed by three dimmer dots
+                let head = (self.phase * SPINNER_STEPS_PER_SEC) as usize;
+                for tail in 0..4 {
+                    let (dx, dy) = SPINNER_RING[(head + SPINNER_RING.len() - tail) % SPINNER_RING.len()];
+                    let fade = 1.0 - tail as f64 * 0.25;
+                    draw_pixel_fn.draw(canvas, bright * fade, color, self.x + dx, self.y + dy,
+                                      context.brightness, context.palette);
+                }
+            }
+            IndicatorStyle::Pulse => {
+                let pulse = 0.5 - 0.5 * (self.phase * PULSE_PER_SEC * std::f64::consts::TAU).cos();
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        // Dimmer corners for a rounder dot
+                        let edge = if dx != 0 && dy != 0 { 0.4 } else { 1.0 };
+                        draw_pixel_fn.draw(canvas, bright * pulse * edge, color,
+                                          self.x + dx, self.y + dy,
+                                          context.brightness, context.palette);
+                    }
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -2,17 +2,45 @@
 pub mod eyes;
 pub mod mouth;
 pub mod nose;
-// TODO: Create accessories module
-// pub mod accessories;
+pub mod accessories;
+pub mod text;
+pub mod background;
+pub mod indicator;
+pub mod thermometer;
+pub mod battery_low;
+pub mod wifi_signal;
 
 // Re-export eye module
 pub use eyes::{Eye, EyePosition, BlinkConfig, get_all_eye_types};
 pub use eyes::{DefaultEyes, HeartEyes, CircleEyes, CrossEyes};
 
 // Re-export mouth module
-pub use mouth::{Mouth, MouthMode, get_all_mouth_types};
+pub use mouth::{Mouth, MouthBlend, MouthMode, get_all_mouth_types};
 pub use mouth::DefaultMouth;
 
 // Re-export nose module
 pub use nose::{Nose, NosePosition, get_all_nose_types};
 pub use nose::DefaultNose;
+
+// Re-export accessory module
+pub use accessories::{Accessory, get_all_accessory_types};
+pub use accessories::{SparkleAccessory, SnowAccessory, PlasmaWaveAccessory, BlepAccessory,
+                      SpectrumBarsAccessory};
+
+// Re-export text overlay
+pub use text::TextElement;
+
+// Re-export background
+pub use background::RainbowCycleBackground;
+
+// Re-export status indicator
+pub use indicator::{StatusIndicators, StatusIndicatorElement, IndicatorStyle};
+
+// Re-export CPU temperature warning
+pub use thermometer::TemperatureWarningElement;
+
+// Re-export battery-low warning
+pub use battery_low::BatteryWarningElement;
+
+// Re-export Wi-Fi signal bars
+pub use wifi_signal::WiFiSignalIndicator;
@@ -1,5 +1,7 @@
-use rpi_led_matrix::LedCanvas;
-use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use std::any::Any;
+use serde::Deserialize;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ElementCategory};
 
 /// Base trait for all mouth implementations
 /// Defines the common interface for mouth rendering and animation
@@ -11,12 +13,23 @@ pub trait Mouth: Send + Sync {
     fn description(&self) -> &str;
 
     /// Update mouth state (opening, breathing, etc.)
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64);
 
     /// Draw the mouth to the canvas
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn);
 
+    /// Render layer, higher draws on top (see `FaceElement::z_order`)
+    fn z_order(&self) -> i32 {
+        ElementCategory::Mouth.default_z_order()
+    }
+
+    /// Access the concrete type for downcasting (see `FaceElementRegistry::get_element_mut`)
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
     /// Clone this mouth into a Box
     fn clone_box(&self) -> Box<dyn Mouth>;
 }
@@ -31,3 +44,52 @@ pub enum MouthMode {
     /// Manual control via gamepad
     Manual,
 }
+
+/// How manual mouth mode combines the trigger with the microphone
+/// ("override", "max" or "mix" in config files)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MouthBlend {
+    /// The trigger alone drives the mouth, audio is ignored
+    Override,
+    /// The trigger holds the mouth at least that far open, speech can open it further
+    Max,
+    /// Weighted sum of both, the weight is the trigger's share
+    Mix,
+}
+
+impl MouthBlend {
+    pub fn name(&self) -> &str {
+        match self {
+            MouthBlend::Override => "Override",
+            MouthBlend::Max => "Max",
+            MouthBlend::Mix => "Mix",
+        }
+    }
+
+    /// Final opening from the audio-driven and trigger openings (same units)
+    pub fn combine(&self, audio: f64, manual: f64, weight: f64) -> f64 {
+        match self {
+            MouthBlend::Override => manual,
+            MouthBlend::Max => audio.max(manual),
+            MouthBlend::Mix => {
+                let weight = weight.clamp(0.0, 1.0);
+                audio * (1.0 - weight) + manual * weight
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blends_combine_audio_and_trigger() {
+        assert_eq!(MouthBlend::Override.combine(5.0, 2.0, 0.5), 2.0);
+        assert_eq!(MouthBlend::Max.combine(5.0, 2.0, 0.5), 5.0);
+        assert_eq!(MouthBlend::Max.combine(1.0, 2.0, 0.5), 2.0);
+        assert_eq!(MouthBlend::Mix.combine(4.0, 2.0, 0.25), 3.5);
+        assert_eq!(MouthBlend::Mix.combine(4.0, 2.0, 7.0), 2.0);
+    }
+}
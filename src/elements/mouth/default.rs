@@ -1,9 +1,11 @@
+use std::any::Any;
 use std::sync::Arc;
-use rpi_led_matrix::LedCanvas;
+use crate::canvas::VirtualLedCanvas;
 use super::base::Mouth;
 use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
 use crate::{PANEL_WIDTH, PANEL_HEIGHT};
 use crate::audio::{AudioLevel, SILENT_LIMIT};
+use tracing::warn;
 
 const MOUTH_MAX_OPENING: f64 = 6.0;
 const IDLE_TIMEOUT_SECS: u64 = 30;
@@ -35,7 +37,8 @@ impl Mouth for DefaultMouth {
         "Audio-reactive mouth with microphone input and breathing animation"
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, _dt: f64) {
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
         // Skip update if manual mouth control is active
         if shared_state.manual_mouth_active {
             return;
@@ -67,17 +70,22 @@ impl Mouth for DefaultMouth {
             }
         }
 
-        // Clamp
+        // Clamp (NaN passes through a clamp, so a broken opening starts over closed)
+        if !self.mouth_opening.is_finite() {
+            warn!("⚠️  Default Mouth opening was {}, resetting it", self.mouth_opening);
+            self.mouth_opening = 0.0;
+        }
         self.mouth_opening = self.mouth_opening.clamp(0.0, MOUTH_MAX_OPENING);
         shared_state.mouth_opening = self.mouth_opening;
     }
 
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         let bright = 255.0;
         let offset_x = context.offset_x;
         let offset_y = context.offset_y;
         let mouth = shared_state.mouth_opening;
+        let curve = shared_state.mouth_curve;
 
         // Mouth coordinates (Arduino original)
         let cord_m_a_x = 7.0 + offset_x;
@@ -103,12 +111,8 @@ impl Mouth for DefaultMouth {
         let angle_m_f = 1.2;
         let angle_m_g = -1.6;
 
-        let color_zero = context.time_counter;
-
         // Render mouth
         for x in 1..=PANEL_WIDTH {
-            let mut color = color_zero + (x as f64) * 5.0;
-
             let m_a = (cord_m_a_x - x as f64) / angle_m_a + cord_m_a_y;
             let m_b = (cord_m_b_x - x as f64) / angle_m_b + cord_m_b_y;
             let m_c = (cord_m_c_x - x as f64) / angle_m_c + cord_m_c_y;
@@ -116,10 +120,13 @@ impl Mouth for DefaultMouth {
             let m_e = (cord_m_e_x - x as f64) / angle_m_e + cord_m_e_y;
             let m_f = (cord_m_f_x - x as f64) / angle_m_f + cord_m_f_y;
             let m_g = (cord_m_g_x - x as f64) / angle_m_g + cord_m_g_y;
+            // Smile/frown: bend the mouth up or down toward its back corner (x = 0),
+            // leaving the front end at the snout in place
+            let bend = curve * (1.0 - x as f64 / PANEL_WIDTH as f64).powi(2);
 
             for y in 0..=PANEL_HEIGHT {
-                color += 5.0;
-                let y_f = y as f64;
+                let color = context.shimmer_index(x, y);
+                let y_f = y as f64 - bend;
 
                 if (m_e > y_f && m_f < y_f && m_c > y_f) ||
                    (m_c > y_f && m_d < y_f && m_e > y_f && m_b < y_f) ||
@@ -131,6 +138,14 @@ impl Mouth for DefaultMouth {
         }
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn clone_box(&self) -> Box<dyn Mouth> {
         Box::new(self.clone())
     }
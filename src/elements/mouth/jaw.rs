@@ -0,0 +1,132 @@
+use std::any::Any;
+use std::sync::Arc;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Mouth;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use crate::PANEL_WIDTH;
+use crate::audio::{AudioLevel, SILENT_LIMIT};
+
+const MOUTH_MAX_OPENING: f64 = 6.0;
+const IDLE_TIMEOUT_SECS: u64 = 30;
+
+// Jaw geometry (element coordinates, y grows upward)
+const HINGE_X: f64 = 22.0;       // Back corner of the mouth where the jaw pivots
+const LIP_HINGE_Y: f64 = 13.0;   // Upper lip height at the hinge
+const LIP_FRONT_Y: f64 = 9.0;    // Upper lip height at the front (panel center)
+const LIP_THICKNESS: f64 = 2.0;
+const JAW_THICKNESS: f64 = 2.0;
+const MAX_JAW_DROP: f64 = 6.0;   // How far the front of the jaw drops when fully open
+
+/// Audio-reactive mouth drawn as a fixed upper lip and a hinged lower jaw
+/// The jaw swings open from the back corner so the motion reads from a distance
+#[derive(Clone)]
+pub struct JawMouth {
+    mouth_opening: f64,
+    breathing_phase: f64,
+    audio_level: Arc<AudioLevel>,
+}
+
+impl JawMouth {
+    pub fn new(audio_level: Arc<AudioLevel>) -> Self {
+        Self {
+            mouth_opening: 0.0,
+            breathing_phase: 0.0,
+            audio_level,
+        }
+    }
+}
+
+impl Mouth for JawMouth {
+    fn name(&self) -> &str {
+        "Jaw Mouth"
+    }
+
+    fn description(&self) -> &str {
+        "Talking jaw that drops open with microphone input and breathing animation"
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        // Skip update if manual mouth control is active
+        if shared_state.manual_mouth_active {
+            return;
+        }
+
+        // Determine if using mic or breathing
+        let seconds_idle = self.audio_level.seconds_since_audio();
+        let use_breathing = seconds_idle >= IDLE_TIMEOUT_SECS;
+
+        if use_breathing {
+            // Breathing animation (kept shallow so the jaw doesn't look like it's talking)
+            self.breathing_phase += 0.05;
+            let breathing = (self.breathing_phase.sin() + 1.0) / 2.0;
+            let target_mouth = breathing * MOUTH_MAX_OPENING * 0.4;
+
+            if self.mouth_opening < target_mouth {
+                self.mouth_opening += 0.1;
+            } else {
+                self.mouth_opening -= 0.1;
+            }
+        } else {
+            // Microphone input
+            let mic_level = self.audio_level.get_level();
+
+            if mic_level > SILENT_LIMIT {
+                self.mouth_opening += 1.5;
+            } else {
+                self.mouth_opening -= 0.8;
+            }
+        }
+
+        // Clamp
+        self.mouth_opening = self.mouth_opening.clamp(0.0, MOUTH_MAX_OPENING);
+        shared_state.mouth_opening = self.mouth_opening;
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 255.0;
+        let offset_x = context.offset_x;
+        let offset_y = context.offset_y;
+        let drop = (shared_state.mouth_opening / MOUTH_MAX_OPENING).clamp(0.0, 1.0) * MAX_JAW_DROP;
+
+        let hinge_x = HINGE_X + offset_x;
+        let span = PANEL_WIDTH as f64 - hinge_x;
+        for x in (hinge_x.ceil() as i32)..=PANEL_WIDTH {
+            // 0.0 at the hinge, 1.0 at the front of the mouth
+            let t = (x as f64 - hinge_x) / span;
+
+            // Upper lip stays put
+            let lip_top = LIP_HINGE_Y + (LIP_FRONT_Y - LIP_HINGE_Y) * t + offset_y;
+            let lip_bottom = lip_top - LIP_THICKNESS;
+
+            // Lower jaw rotates down around the hinge
+            let jaw_top = lip_bottom - drop * t;
+            let jaw_bottom = jaw_top - JAW_THICKNESS;
+
+            for y in (jaw_bottom.floor() as i32).max(0)..=(lip_top.ceil() as i32) {
+                let color = context.shimmer_index(x, y);
+                let y_f = y as f64;
+
+                let in_lip = y_f <= lip_top && y_f > lip_bottom;
+                let in_jaw = y_f <= jaw_top && y_f > jaw_bottom;
+                if in_lip || in_jaw {
+                    draw_pixel_fn.draw(canvas, bright, color, x, y,
+                                      context.brightness, context.palette);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Mouth> {
+        Box::new(self.clone())
+    }
+}
@@ -3,12 +3,16 @@ pub mod base;
 
 // Individual mouth implementations
 mod default;
+mod jaw;
+mod smile;
 
 // Re-export the base trait and types
-pub use base::{Mouth, MouthMode};
+pub use base::{Mouth, MouthBlend, MouthMode};
 
 // Re-export all mouth implementations
 pub use default::DefaultMouth;
+pub use jaw::JawMouth;
+pub use smile::SmileMouth;
 
 use crate::audio::AudioLevel;
 use std::sync::Arc;
@@ -17,6 +21,8 @@ use std::sync::Arc;
 /// This allows the registry to auto-discover all mouth implementations
 pub fn get_all_mouth_types(audio_level: Arc<AudioLevel>) -> Vec<Box<dyn Mouth>> {
     vec![
-        Box::new(DefaultMouth::new(audio_level)),
+        Box::new(DefaultMouth::new(audio_level.clone())),
+        Box::new(JawMouth::new(audio_level.clone())),
+        Box::new(SmileMouth::new(audio_level)),
     ]
 }
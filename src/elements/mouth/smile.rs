@@ -0,0 +1,137 @@
+use std::any::Any;
+use std::sync::Arc;
+use crate::canvas::VirtualLedCanvas;
+use super::base::Mouth;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use crate::PANEL_WIDTH;
+use crate::audio::{AudioLevel, SILENT_LIMIT};
+
+const MOUTH_MAX_OPENING: f64 = 6.0;
+const IDLE_TIMEOUT_SECS: u64 = 30;
+
+// Smile geometry (element coordinates, y grows upward)
+const CORNER_X: f64 = 22.0;      // Back corner of the mouth
+const LIP_FRONT_Y: f64 = 9.0;    // Upper lip height at the front (panel center)
+const LIP_THICKNESS: f64 = 2.0;
+const MAX_LIP_DROP: f64 = 5.0;   // How far the lower lip drops at the front when fully open
+
+// Corner lift in pixels: the resting smile, plus how far smile intensity moves it
+// either way (intensity -1.0 turns it into a slight frown)
+const BASE_CURVE: f64 = 2.0;
+const CURVE_RANGE: f64 = 3.0;
+
+/// Audio-reactive mouth drawn as a curved smile whose depth follows
+/// `RenderContext::smile_intensity` (right stick in manual mouth mode)
+#[derive(Clone)]
+pub struct SmileMouth {
+    mouth_opening: f64,
+    breathing_phase: f64,
+    audio_level: Arc<AudioLevel>,
+}
+
+impl SmileMouth {
+    pub fn new(audio_level: Arc<AudioLevel>) -> Self {
+        Self {
+            mouth_opening: 0.0,
+            breathing_phase: 0.0,
+            audio_level,
+        }
+    }
+}
+
+impl Mouth for SmileMouth {
+    fn name(&self) -> &str {
+        "Smile Mouth"
+    }
+
+    fn description(&self) -> &str {
+        "Curved smile with adjustable depth that opens with microphone input"
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        // Skip update if manual mouth control is active
+        if shared_state.manual_mouth_active {
+            return;
+        }
+
+        // Determine if using mic or breathing
+        let seconds_idle = self.audio_level.seconds_since_audio();
+        let use_breathing = seconds_idle >= IDLE_TIMEOUT_SECS;
+
+        if use_breathing {
+            // Shallow breathing so the smile stays a smile
+            self.breathing_phase += 0.05;
+            let breathing = (self.breathing_phase.sin() + 1.0) / 2.0;
+            let target_mouth = breathing * MOUTH_MAX_OPENING * 0.3;
+
+            if self.mouth_opening < target_mouth {
+                self.mouth_opening += 0.1;
+            } else {
+                self.mouth_opening -= 0.1;
+            }
+        } else {
+            // Microphone input
+            let mic_level = self.audio_level.get_level();
+
+            if mic_level > SILENT_LIMIT {
+                self.mouth_opening += 1.5;
+            } else {
+                self.mouth_opening -= 0.8;
+            }
+        }
+
+        // Clamp
+        self.mouth_opening = self.mouth_opening.clamp(0.0, MOUTH_MAX_OPENING);
+        shared_state.mouth_opening = self.mouth_opening;
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+            shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 255.0;
+        let offset_x = context.offset_x;
+        let offset_y = context.offset_y;
+        let drop = (shared_state.mouth_opening / MOUTH_MAX_OPENING).clamp(0.0, 1.0) * MAX_LIP_DROP;
+        let curve = BASE_CURVE + context.smile_intensity.clamp(-1.0, 1.0) * CURVE_RANGE;
+
+        let corner_x = CORNER_X + offset_x;
+        let span = PANEL_WIDTH as f64 - corner_x;
+        for x in (corner_x.ceil() as i32)..=PANEL_WIDTH {
+            // 0.0 at the corner, 1.0 at the front of the mouth
+            let t = (x as f64 - corner_x) / span;
+
+            // Corners lift (or sag) on a parabola, flat at the front
+            let lift = curve * (1.0 - t) * (1.0 - t);
+            let lip_top = LIP_FRONT_Y + lift + offset_y;
+            let lip_bottom = lip_top - LIP_THICKNESS;
+
+            // Lower lip follows the curve and drops open toward the front
+            let lower_top = lip_bottom - drop * t;
+            let lower_bottom = lower_top - LIP_THICKNESS;
+
+            for y in (lower_bottom.floor() as i32).max(0)..=(lip_top.ceil() as i32) {
+                let color = context.shimmer_index(x, y);
+                let y_f = y as f64;
+
+                let in_upper = y_f <= lip_top && y_f > lip_bottom;
+                let in_lower = y_f <= lower_top && y_f > lower_bottom;
+                if in_upper || in_lower {
+                    draw_pixel_fn.draw(canvas, bright, color, x, y,
+                                      context.brightness, context.palette);
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Mouth> {
+        Box::new(self.clone())
+    }
+}
@@ -1,5 +1,6 @@
-use rpi_led_matrix::LedCanvas;
-use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{RenderContext, DrawPixelFn, SharedFaceState, ElementCategory};
 
 /// Base trait for all nose implementations
 /// Defines the common interface for nose rendering
@@ -11,12 +12,26 @@ pub trait Nose: Send + Sync {
     fn description(&self) -> &str;
 
     /// Update nose state (if animated)
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64);
 
     /// Draw the nose to the canvas
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn);
 
+    /// Scale the nose around its tip (1.0 = normal size), ignored by noses that can't scale
+    fn set_scale(&mut self, _scale: f64) {}
+
+    /// Render layer, higher draws on top (see `FaceElement::z_order`)
+    fn z_order(&self) -> i32 {
+        ElementCategory::Nose.default_z_order()
+    }
+
+    /// Access the concrete type for downcasting (see `FaceElementRegistry::get_element_mut`)
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
     /// Clone this nose into a Box
     fn clone_box(&self) -> Box<dyn Nose>;
 }
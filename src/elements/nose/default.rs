@@ -1,23 +1,39 @@
-use rpi_led_matrix::LedCanvas;
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
 use super::base::{Nose, NosePosition};
 use crate::face::{RenderContext, DrawPixelFn, SharedFaceState};
-use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+use crate::{PANEL_WIDTH, PANEL_HEIGHT, MOUTH_MAX_OPENING};
+
+// How much bigger the nose gets with the mouth fully open (0.1 = 10%)
+const BREATHING_GROWTH: f64 = 0.1;
 
 /// Default protogen nose - simple parabolic curves
 #[derive(Clone, Copy)]
 pub struct DefaultNose {
     position: NosePosition,
+    scale: f64,       // Size around the tip, 1.0 = the original shape
+    breathing: bool,  // Grow slightly as the mouth opens
 }
 
 impl DefaultNose {
     pub fn new() -> Self {
+        Self::with_position(NosePosition::default())
+    }
+
+    pub fn with_position(position: NosePosition) -> Self {
         Self {
-            position: NosePosition::default(),
+            position,
+            scale: 1.0,
+            breathing: true,
         }
     }
 
-    pub fn with_position(position: NosePosition) -> Self {
-        Self { position }
+    /// Grow the nose with the mouth opening (off puts it back to normal size)
+    pub fn set_breathing(&mut self, enabled: bool) {
+        self.breathing = enabled;
+        if !enabled {
+            self.scale = 1.0;
+        }
     }
 }
 
@@ -30,11 +46,19 @@ impl Nose for DefaultNose {
         "Original protogen nose with parabolic curves"
     }
 
-    fn update(&mut self, _shared_state: &mut SharedFaceState, _dt: f64) {
-        // Nose is static, no update needed
+    fn update(&mut self, shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        if self.breathing {
+            let opening = (shared_state.mouth_opening / MOUTH_MAX_OPENING).clamp(0.0, 1.0);
+            self.set_scale(1.0 + opening * BREATHING_GROWTH);
+        }
     }
 
-    fn draw(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.1);
+    }
+
+    fn draw(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
             _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         let bright = 255.0;
         let offset_x = context.offset_x;
@@ -46,18 +70,16 @@ impl Nose for DefaultNose {
         let cord_n_b_x = 53.0 + offset_x;
         let cord_n_b_y = 23.0 + offset_y;
 
-        let color_zero = context.time_counter;
-
         // Render nose
         for x in 1..=PANEL_WIDTH {
-            let mut color = color_zero + (x as f64) * 5.0;
-
-            let n_a = -0.5 * (x as f64 - cord_n_a_x).powi(2) + cord_n_a_y;
-            let n_b = -0.1 * (x as f64 - cord_n_b_x).powi(2) + cord_n_b_y;
+            // Scaling around the tip: test where this pixel lands on the unscaled nose
+            let x_f = cord_n_a_x + (x as f64 - cord_n_a_x) / self.scale;
+            let n_a = -0.5 * (x_f - cord_n_a_x).powi(2) + cord_n_a_y;
+            let n_b = -0.1 * (x_f - cord_n_b_x).powi(2) + cord_n_b_y;
 
             for y in 0..=PANEL_HEIGHT {
-                color += 5.0;
-                let y_f = y as f64;
+                let color = context.shimmer_index(x, y);
+                let y_f = cord_n_a_y + (y as f64 - cord_n_a_y) / self.scale;
 
                 if n_b < y_f && n_a > y_f {
                     draw_pixel_fn.draw(canvas, bright, color, x, y,
@@ -67,6 +89,14 @@ impl Nose for DefaultNose {
         }
     }
 
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn clone_box(&self) -> Box<dyn Nose> {
         Box::new(*self)
     }
@@ -0,0 +1,101 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::font::BitmapFont;
+use crate::PANEL_WIDTH;
+
+/// Scrolling text overlay rendered with the 3x5 bitmap font
+#[derive(Clone)]
+pub struct TextElement {
+    text: String,
+    x: f64,              // Left edge of the text (element coordinates)
+    y: f64,              // Top row of the text (element coordinates, y grows upward)
+    scroll_speed: f64,   // Pixels per second, 0.0 = static
+    scroll_offset: f64,
+}
+
+impl TextElement {
+    pub fn new(text: &str, x: f64, y: f64, scroll_speed: f64) -> Self {
+        Self {
+            text: text.to_string(),
+            x,
+            y,
+            scroll_speed,
+            scroll_offset: 0.0,
+        }
+    }
+
+    /// Message entering from the right edge and scrolling across the panel
+    pub fn scrolling(text: &str) -> Self {
+        Self::new(text, PANEL_WIDTH as f64, 6.0, 20.0)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.scroll_offset = 0.0;
+    }
+}
+
+impl FaceElement for TextElement {
+    fn name(&self) -> &str {
+        "Text"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Scrolling text message overlay"
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        if self.scroll_speed <= 0.0 {
+            return;
+        }
+
+        self.scroll_offset += self.scroll_speed * dt;
+
+        // Once the text has fully left the panel, wrap it back in from the right edge
+        let width = BitmapFont::text_width(&self.text) as f64;
+        if self.x - self.scroll_offset + width < 0.0 {
+            self.scroll_offset -= PANEL_WIDTH as f64 + width;
+        }
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        let bright = 255.0;
+        let left = (self.x - self.scroll_offset).round() as i32;
+        let top = self.y.round() as i32;
+
+        BitmapFont::for_each_pixel(&self.text, |dx, dy| {
+            let x = left + dx;
+            let y = top - dy;
+            let color = context.shimmer_index(x, y);
+            draw_pixel_fn.draw(canvas, bright, color, x, y,
+                              context.brightness, context.palette);
+        });
+    }
+
+    fn status(&self) -> String {
+        format!("Text: \"{}\"", self.text)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,93 @@
+use std::any::Any;
+use crate::canvas::VirtualLedCanvas;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::font::{BitmapFont, GLYPH_WIDTH, THERMOMETER_ICON};
+use crate::thermal::{CpuTemperature, TEMP_WARNING_C};
+use crate::{PANEL_HEIGHT, PANEL_WIDTH};
+
+// How long the icon stays up each time, and how often it comes back while still hot
+const WARNING_SHOW_SECS: f64 = 5.0;
+const WARNING_REPEAT_SECS: f64 = 10.0;
+
+/// Thermometer icon in the top corner (next to the nose bridge) while the CPU is too hot
+#[derive(Clone)]
+pub struct TemperatureWarningElement {
+    temperature: CpuTemperature,
+    show_secs: f64,      // Time left showing the icon
+    since_warning: f64,  // Seconds since the icon last came up
+}
+
+impl TemperatureWarningElement {
+    pub fn new(temperature: CpuTemperature) -> Self {
+        Self {
+            temperature,
+            show_secs: 0.0,
+            since_warning: WARNING_REPEAT_SECS,
+        }
+    }
+}
+
+impl FaceElement for TemperatureWarningElement {
+    fn name(&self) -> &str {
+        "Temperature Warning"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Thermometer icon shown for a few seconds at a time while the CPU is over 80°C"
+    }
+
+    // Above the other accessories so it is never hidden
+    fn z_order(&self) -> i32 {
+        ElementCategory::Accessory.default_z_order() + 10
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              dt: f64) {
+        // NaN (no reading yet) compares false, so it never warns
+        let hot = *self.temperature.lock().unwrap() > TEMP_WARNING_C;
+        if hot {
+            self.since_warning += dt;
+            if self.since_warning >= WARNING_REPEAT_SECS {
+                self.since_warning = 0.0;
+                self.show_secs = WARNING_SHOW_SECS;
+            }
+        } else {
+            // Show straight away the next time it gets hot
+            self.since_warning = WARNING_REPEAT_SECS;
+        }
+        self.show_secs = (self.show_secs - dt).max(0.0);
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        if self.show_secs <= 0.0 {
+            return;
+        }
+        let bright = 255.0;
+        let left = PANEL_WIDTH - GLYPH_WIDTH - 1;
+        let top = PANEL_HEIGHT - 2;
+
+        BitmapFont::for_each_icon_pixel(&THERMOMETER_ICON, |dx, dy| {
+            let x = left + dx;
+            let y = top - dy;
+            draw_pixel_fn.draw(canvas, bright, context.shimmer_index(x, y), x, y,
+                               context.brightness, context.palette);
+        });
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,86 @@
+use std::any::Any;
+use rpi_led_matrix::LedColor;
+use crate::canvas::VirtualLedCanvas;
+use crate::color::get_shimmer_color;
+use crate::face::{FaceElement, ElementCategory, RenderContext, DrawPixelFn, SharedFaceState};
+use crate::wifi::{signal_bars, WifiSignal, SIGNAL_BARS};
+use crate::{PANEL_HEIGHT, PANEL_WIDTH};
+
+// Level of the unfilled bars, before the face brightness is applied
+const EMPTY_BAR_GREY: f64 = 40.0;
+
+/// Phone-style signal bars in the bottom-right corner of the right panel
+#[derive(Clone)]
+pub struct WiFiSignalIndicator {
+    signal: WifiSignal,
+    bars: u8,  // Filled bars, refreshed each frame from the monitor's reading
+}
+
+impl WiFiSignalIndicator {
+    pub fn new(signal: WifiSignal) -> Self {
+        Self { signal, bars: 0 }
+    }
+}
+
+impl FaceElement for WiFiSignalIndicator {
+    fn name(&self) -> &str {
+        "WiFi Signal"
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn description(&self) -> &str {
+        "Wi-Fi signal strength as four bars in the corner of the right panel"
+    }
+
+    // Above the other accessories so it is never hidden
+    fn z_order(&self) -> i32 {
+        ElementCategory::Accessory.default_z_order() + 10
+    }
+
+    fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+              _dt: f64) {
+        self.bars = signal_bars(*self.signal.lock().unwrap());
+    }
+
+    // Only the right panel shows the icon (a mirrored copy would read backwards), so this
+    // writes the canvas directly in panel coordinates instead of going through the drawer
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {
+        let grey = (EMPTY_BAR_GREY * context.brightness) as u8;
+        let right = PANEL_WIDTH * 2 - 2;
+        let bottom = PANEL_HEIGHT - 2;
+
+        // Bars grow one pixel taller from left to right, one pixel in from the corner
+        for bar in 0..SIGNAL_BARS as i32 {
+            let x = right - (SIGNAL_BARS as i32 - 1 - bar);
+            for dy in 0..=bar {
+                let y = bottom - dy;
+                let color = if bar < self.bars as i32 {
+                    // Same palette position as the mirrored face pixel at this spot
+                    let face_x = PANEL_WIDTH * 2 - 1 - x;
+                    let face_y = PANEL_HEIGHT - 1 - y;
+                    get_shimmer_color(context.shimmer_index(face_x, face_y),
+                                      255.0 * context.brightness, context.palette)
+                } else {
+                    LedColor { red: grey, green: grey, blue: grey }
+                };
+                canvas.set(x, y, &color);
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(self.clone())
+    }
+}
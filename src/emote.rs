@@ -0,0 +1,138 @@
+// Emotes
+// Short one-shot expressions ("surprised" for 1.5s) that temporarily override the
+// eyes, palette and/or an accessory and then put the face back the way it was.
+// Emotes triggered while another is playing wait their turn.
+
+use std::collections::VecDeque;
+use serde::Deserialize;
+
+use crate::color::ColorPalette;
+use crate::face::PanelSide;
+
+/// Name of the wink-and-smirk emote (Select + R2)
+pub const WINK_EMOTE: &str = "wink";
+
+/// A named emote as defined in the config file
+/// Fields left out keep whatever the face is currently showing
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Emote {
+    pub name: String,
+    pub eyes: Option<String>,            // Eye style to show
+    pub palette: Option<ColorPalette>,   // Color palette to use
+    pub accessory: Option<String>,       // Accessory to switch on
+    pub duration_secs: f64,
+    pub wink: Option<PanelSide>,         // Panel whose eye closes ("left" or "right")
+    pub smirk: Option<PanelSide>,        // Panel whose mouth corner lifts, the other stays straight
+}
+
+impl Emote {
+    pub fn new(name: &str, eyes: Option<&str>, palette: Option<ColorPalette>,
+               accessory: Option<&str>, duration_secs: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            eyes: eyes.map(String::from),
+            palette,
+            accessory: accessory.map(String::from),
+            duration_secs,
+            wink: None,
+            smirk: None,
+        }
+    }
+
+    /// Cheeky wink with a one-sided smile on the same panel, on the Default Eyes
+    /// (the only eyes with eyelids)
+    pub fn wink() -> Self {
+        Self {
+            wink: Some(PanelSide::Left),
+            smirk: Some(PanelSide::Left),
+            ..Emote::new(WINK_EMOTE, Some("Default Eyes"), None, None, 1.5)
+        }
+    }
+
+    /// Emotes available when the config doesn't define any
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Emote::new("surprised", Some("O Eyes"), None, None, 1.5),
+            Emote::new("love", Some("Heart Eyes"), Some(ColorPalette::Purple), None, 3.0),
+            Emote::new("dizzy", Some("X Eyes"), None, None, 2.0),
+            Emote::new("sparkle", None, None, Some("Sparkle"), 3.0),
+            Emote::wink(),
+        ]
+    }
+}
+
+/// What changed in one queue update
+#[derive(Debug, Default, PartialEq)]
+pub struct EmoteChange {
+    pub finished: Option<Emote>,  // Restore the face from before this emote
+    pub started: Option<Emote>,   // Remember the face, then apply this emote
+}
+
+/// Emotes waiting to play, and the one playing now with its time left
+#[derive(Debug, Default)]
+pub struct EmoteQueue {
+    pending: VecDeque<Emote>,
+    active: Option<(Emote, f64)>,
+}
+
+impl EmoteQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, emote: Emote) {
+        self.pending.push_back(emote);
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Advance the active emote by dt seconds, ending it and starting the next as needed
+    pub fn update(&mut self, dt: f64) -> EmoteChange {
+        let mut change = EmoteChange::default();
+
+        if let Some((_, remaining)) = self.active.as_mut() {
+            *remaining -= dt;
+            if *remaining <= 0.0 {
+                change.finished = self.active.take().map(|(emote, _)| emote);
+            }
+        }
+        if self.active.is_none() {
+            if let Some(emote) = self.pending.pop_front() {
+                self.active = Some((emote.clone(), emote.duration_secs));
+                change.started = Some(emote);
+            }
+        }
+        change
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queued_emotes_play_one_after_another() {
+        let mut queue = EmoteQueue::new();
+        let surprised = Emote::new("surprised", Some("O Eyes"), None, None, 1.0);
+        let love = Emote::new("love", Some("Heart Eyes"), None, None, 1.0);
+        queue.push(surprised.clone());
+        queue.push(love.clone());
+
+        assert_eq!(queue.update(0.5), EmoteChange { finished: None, started: Some(surprised.clone()) });
+        assert_eq!(queue.update(0.5), EmoteChange::default());
+        assert_eq!(queue.update(0.5), EmoteChange { finished: Some(surprised), started: Some(love.clone()) });
+        assert_eq!(queue.update(1.0), EmoteChange { finished: Some(love), started: None });
+        assert!(!queue.is_playing());
+    }
+
+    #[test]
+    fn config_emotes_can_wink_one_side() {
+        let emote: Emote = toml::from_str(
+            "name = \"cheeky\"\nduration_secs = 2.0\nwink = \"right\"\nsmirk = \"right\"").unwrap();
+        assert_eq!(emote.wink, Some(PanelSide::Right));
+        assert_eq!(emote.smirk, Some(PanelSide::Right));
+        assert_eq!(emote.eyes, None);
+    }
+}
@@ -0,0 +1,92 @@
+// Idle expression scheduling
+// While the face is in breathing mode (no audio for IDLE_TIMEOUT_SECS) a randomly
+// chosen accessory animation is shown, and a new one is picked every interval.
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use crate::rng;
+
+/// An accessory that can be shown while idle, with its relative pick weight
+#[derive(Debug, Clone)]
+pub struct IdleAnimation {
+    pub element_name: String,
+    pub weight: u32,
+}
+
+impl IdleAnimation {
+    pub fn new(element_name: &str, weight: u32) -> Self {
+        Self {
+            element_name: element_name.to_string(),
+            weight,
+        }
+    }
+}
+
+/// Picks weighted random idle animations at a fixed interval
+pub struct IdleAnimationScheduler {
+    animations: Vec<IdleAnimation>,
+    interval_secs: f64,
+    timer: f64,
+    active: Option<usize>,
+    rng: StdRng,
+}
+
+impl IdleAnimationScheduler {
+    pub fn new(animations: Vec<IdleAnimation>, interval_secs: f64) -> Self {
+        Self {
+            animations,
+            interval_secs,
+            timer: 0.0,
+            active: None,
+            rng: rng::seeded_rng(),
+        }
+    }
+
+    /// Scheduler with the built-in accessory animations
+    pub fn with_default_animations(interval_secs: f64) -> Self {
+        Self::new(vec![
+            IdleAnimation::new("Sparkle", 3),
+            IdleAnimation::new("Snow", 2),
+            IdleAnimation::new("Plasma Wave", 1),
+        ], interval_secs)
+    }
+
+    pub fn set_interval(&mut self, interval_secs: f64) {
+        self.interval_secs = interval_secs;
+    }
+
+    pub fn animations(&self) -> &[IdleAnimation] {
+        &self.animations
+    }
+
+    /// Name of the element that should currently be shown, if any
+    pub fn active(&self) -> Option<&str> {
+        self.active.map(|i| self.animations[i].element_name.as_str())
+    }
+
+    /// Advance the timer, returns true if the active animation changed
+    pub fn update(&mut self, idle: bool, dt: f64) -> bool {
+        if !idle {
+            // Audio resumed - stop the idle animation right away
+            self.timer = 0.0;
+            return self.active.take().is_some();
+        }
+
+        self.timer -= dt;
+        if self.active.is_some() && self.timer > 0.0 {
+            return false;
+        }
+
+        self.timer = self.interval_secs;
+        let previous = self.active;
+        self.active = self.pick();
+        self.active != previous
+    }
+
+    fn pick(&mut self) -> Option<usize> {
+        let weights = self.animations.iter().map(|a| a.weight);
+        // Errors only if there are no animations or every weight is zero
+        let dist = WeightedIndex::new(weights).ok()?;
+        Some(dist.sample(&mut self.rng))
+    }
+}
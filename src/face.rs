@@ -2,22 +2,45 @@
 // Contains all face-related types, traits, and the main ProtogenFace struct
 
 use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
-use rpi_led_matrix::LedCanvas;
+use std::time::Instant;
+use rpi_led_matrix::{LedCanvas, LedColor};
+use crate::canvas::VirtualLedCanvas;
 use gilrs::Button;
+use serde::{Deserialize, Serialize};
 
-use crate::audio::AudioLevel;
-use crate::color::{ColorPalette, get_shimmer_color};
+use crate::audio::{stereo_balance, AudioLevel};
+use crate::config::Config;
+use crate::color::{ColorOrder, ColorPalette, ShimmerMode, PALETTE_CYCLE, apply_brightness_floor,
+                   get_shimmer_color};
 use crate::gamepad::{MaskState, CycleEyes};
 use crate::elements;
+use crate::elements::MouthBlend;
+use crate::effects::{BloomEffect, ColorBurstEffect, FadeTransition, FireSimulation, GlitchEffect, InvertEffect,
+                     MatrixRainEffect, NightVisionEffect, PulseBreathingEffect,
+                     StrobeEffect, WaveformDisplay};
+use crate::debug_overlay::{BoundsRecorder, DebugOverlay, ElementBounds};
+use crate::emote::{Emote, EmoteQueue, WINK_EMOTE};
+use crate::expression::IdleAnimationScheduler;
+use crate::metrics::FaceMetrics;
+use crate::quality::{QualityAction, QualityEffect, QualityManager};
+use crate::rng;
+use crate::profile::{ProfileManager, PROFILES_PATH};
+use crate::snapshot::{FaceSnapshot, SnapshotStore, SNAPSHOT_PATH};
+use crate::telemetry::{FaceMode, FaceTelemetry};
+use crate::thermal::CpuTemperature;
+use crate::ble::{excitement, HeartRate};
 use crate::{PANEL_WIDTH, PANEL_HEIGHT, MOUTH_MAX_OPENING};
+use tracing::{info, warn};
 
 // ============================================================================
 // FACE ELEMENT SYSTEM
 // ============================================================================
 
-// Element categories for organization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+// Element categories for organization ("eyes", "mouth", "nose", "accessory" in GET /elements)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ElementCategory {
     Eyes,
     Mouth,
@@ -25,51 +48,255 @@ pub enum ElementCategory {
     Accessory, // Blush, tears, etc.
 }
 
-// Context passed to elements during rendering
+impl ElementCategory {
+    /// Default layer for elements of this category (higher draws on top)
+    pub fn default_z_order(&self) -> i32 {
+        match self {
+            ElementCategory::Mouth => 0,
+            ElementCategory::Nose => 10,
+            ElementCategory::Eyes => 20,
+            ElementCategory::Accessory => 30,
+        }
+    }
+}
+
+// Context passed to elements during update and rendering
+#[derive(Clone)]
 pub struct RenderContext {
     pub offset_x: f64,
     pub offset_y: f64,
     pub time_counter: f64,
     pub brightness: f64,
     pub palette: ColorPalette,
+    pub smile_intensity: f64,  // -1.0 (frown) to 1.0 (deepest smile), right stick in manual mouth mode
+    pub shimmer_mode: ShimmerMode,  // How the palette flows over the face, see `shimmer_index`
+    pub anti_aliasing: bool,  // Fade shape edges; false draws every pixel at full brightness (cheaper)
+    pub left_audio: f64,   // Smoothed level of the left microphone channel (0.0 to 1.0)
+    pub right_audio: f64,  // Smoothed level of the right channel (same as left for a mono mic)
+    // Per-frame values elements share with each other: written in `update`, read in
+    // `render` (by any element, since all updates run before any rendering).
+    // Cleared at the start of every frame. Prefix keys with the writing element's name
+    // in snake_case (e.g. "jaw_mouth.at_max") so elements don't overwrite each other.
+    pub user_data: HashMap<String, f64>,
+}
+
+impl RenderContext {
+    /// Palette color index for the pixel at (x, y), following the configured shimmer mode
+    /// Elements pass this to `DrawPixelFn::draw` so every element shimmers the same way
+    pub fn shimmer_index(&self, x: i32, y: i32) -> f64 {
+        self.shimmer_mode.color_index(self.time_counter, x as f64, y as f64)
+    }
+}
+
+/// One of the two panels (the right one shows the mirrored half)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelSide {
+    Left,
+    Right,
 }
 
+// Eyelids of the Default Eyes at the closed point of a blink, used for a wink
+const SHUT_EYE_TOP: f64 = 0.1;
+const SHUT_EYE_BOTTOM: f64 = 7.0;
+
+// Eyelids with the eyes open, the starting point and what a broken value is reset to
+const OPEN_EYE_TOP: f64 = 9.0;
+const OPEN_EYE_BOTTOM: f64 = 1.45;
+
 // Shared state that elements can read/write
+#[derive(Clone)]
 pub struct SharedFaceState {
     pub mouth_opening: f64,  // 0.0 to MOUTH_MAX_OPENING
     pub eye_top: f64,        // Top eyelid position
     pub eye_bottom: f64,     // Bottom eyelid position
     pub blink_enabled: bool,
     pub manual_mouth_active: bool,  // Skip mouth updates when true
+    pub idle_breathing: bool,       // Face is in breathing mode (silence or mic muted)
+    pub gaze_offset: (f64, f64),    // Whole-pixel eye shift from the right stick (x right, y up)
+    pub mouth_curve: f64,           // Pixels the mouth corner is lifted (smile) or dropped (frown, negative)
+    pub wink: Option<PanelSide>,    // Panel whose eye is shut (Default Eyes only, they have eyelids)
+    pub smirk: Option<PanelSide>,   // Only this panel's mouth corner bends, the other stays straight
+    pub emotion_intensity: f64,     // 0.0 (calm) to 1.0 (excited), follows the heart rate monitor
+    pub stereo_mouth: Option<(f64, f64)>,  // Left and right panel mouth openings when they differ
+}
+
+impl SharedFaceState {
+    /// Panels that need drawing on their own: None (both at once) unless a wink, smirk
+    /// or stereo mouth makes the halves differ
+    pub fn render_sides(&self) -> Vec<Option<PanelSide>> {
+        if self.wink.is_some() || self.smirk.is_some() || self.stereo_mouth.is_some() {
+            vec![Some(PanelSide::Left), Some(PanelSide::Right)]
+        } else {
+            vec![None]
+        }
+    }
+
+    /// Put any NaN or infinite value back to its resting default (mouth closed, eyes
+    /// open, no gaze or curve), returning the names of the fields that were reset
+    pub fn reset_non_finite(&mut self) -> Vec<&'static str> {
+        let mut reset = Vec::new();
+        let mut check = |value: &mut f64, default: f64, name: &'static str| {
+            if !value.is_finite() {
+                *value = default;
+                reset.push(name);
+            }
+        };
+        check(&mut self.mouth_opening, 0.0, "mouth_opening");
+        check(&mut self.eye_top, OPEN_EYE_TOP, "eye_top");
+        check(&mut self.eye_bottom, OPEN_EYE_BOTTOM, "eye_bottom");
+        check(&mut self.gaze_offset.0, 0.0, "gaze_offset");
+        check(&mut self.gaze_offset.1, 0.0, "gaze_offset");
+        check(&mut self.mouth_curve, 0.0, "mouth_curve");
+        check(&mut self.emotion_intensity, 0.0, "emotion_intensity");
+        if self.stereo_mouth.is_some_and(|(left, right)| !left.is_finite() || !right.is_finite()) {
+            self.stereo_mouth = None;
+            reset.push("stereo_mouth");
+        }
+        reset.dedup();
+        reset
+    }
+
+    /// The state as one panel sees it (None = both panels, unchanged)
+    pub fn for_side(&self, side: Option<PanelSide>) -> SharedFaceState {
+        let mut state = self.clone();
+        if let Some(side) = side {
+            if self.wink == Some(side) {
+                state.eye_top = SHUT_EYE_TOP;
+                state.eye_bottom = SHUT_EYE_BOTTOM;
+            }
+            if self.smirk.is_some_and(|smirk| smirk != side) {
+                state.mouth_curve = 0.0;
+            }
+            if let Some((left, right)) = self.stereo_mouth {
+                state.mouth_opening = if side == PanelSide::Left { left } else { right };
+            }
+        }
+        state
+    }
 }
 
 // Trait for all face elements
-pub trait FaceElement {
+// Send + Sync because each update hands copies of the active elements to the render thread
+pub trait FaceElement: Send + Sync {
     fn name(&self) -> &str;
     fn category(&self) -> ElementCategory;
     fn description(&self) -> &str { "" }
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64);
-    fn render(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64);
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
               shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn);
     fn handle_button(&mut self, _button: Button, _shared_state: &mut SharedFaceState) -> bool {
         false
     }
     fn status(&self) -> String { String::new() }
+    // Anchor point used to slide between eye variants with different centers
+    fn center(&self) -> Option<(f64, f64)> { None }
+    // Render layer, higher draws on top (elements with equal values keep registration order)
+    fn z_order(&self) -> i32 { self.category().default_z_order() }
+    // Full-screen takeover: while true only this element is drawn (the rest keep updating)
+    fn wants_exclusive_render(&self) -> bool { false }
+    // Settings a companion app can show for this element (GET /elements)
+    fn config_fields(&self) -> Vec<ConfigField> { Vec::new() }
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
+    // Copy of the element as it is now, drawn by the render thread (see `FaceFrame`)
+    fn clone_box(&self) -> Box<dyn FaceElement>;
+}
+
+/// One element setting reported by GET /elements
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigField {
+    pub name: String,  // Config file key that sets it
+    pub value: serde_json::Value,
+    pub description: String,
+}
+
+impl ConfigField {
+    pub fn new(name: &str, value: impl Into<serde_json::Value>, description: &str) -> Self {
+        Self { name: name.to_string(), value: value.into(), description: description.to_string() }
+    }
+}
+
+/// Everything GET /elements reports about one registered element
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElementDescription {
+    pub name: String,
+    pub category: ElementCategory,
+    pub description: String,
+    pub enabled: bool,
+    pub active: bool,    // Eyes and mouths: the selected style; other elements: same as enabled
+    pub priority: i32,   // Render layer (z order), higher draws on top
+    pub config_fields: Vec<ConfigField>,
 }
 
 // Helper trait for drawing pixels with state
 pub trait DrawPixelFn {
-    fn draw(&self, canvas: &mut LedCanvas, bright: f64, color_index: f64,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, bright: f64, color_index: f64,
             x: i32, y: i32, brightness: f64, palette: ColorPalette);
 }
 
 // Pixel drawer implementation
-pub struct PixelDrawer;
+#[derive(Clone)]
+pub struct PixelDrawer {
+    // Columns to shift the mirrored (right panel) half, to line it up with the physical
+    // panels at the nose bridge. Positive moves it away from the center, negative toward it.
+    mirror_offset: i32,
+    // Optional bias for the right panel only, for a sense of depth under stage lights.
+    // 1.0 / 0.0 keeps both halves identical.
+    right_brightness: f64,   // Brightness multiplier
+    right_color_shift: f64,  // Offset along the palette gradient (shifts the hue)
+    // Offset added to every color index, advanced each frame for the slow palette drift
+    palette_phase: f64,
+    // Draw only this panel (for asymmetric expressions), None draws both
+    side: Option<PanelSide>,
+    // Lowest value the brightest channel of a lit pixel may have (0 = no floor), so shapes
+    // don't break up at the lowest brightness settings
+    min_pixel_value: u8,
+}
+
+impl PixelDrawer {
+    pub fn new() -> Self {
+        Self {
+            mirror_offset: 0,
+            right_brightness: 1.0,
+            right_color_shift: 0.0,
+            palette_phase: 0.0,
+            side: None,
+            min_pixel_value: 0,
+        }
+    }
+
+    // Drawer limited to one panel (None keeps both)
+    fn for_side(&self, side: Option<PanelSide>) -> Self {
+        Self {
+            side,
+            ..self.clone()
+        }
+    }
+
+    // Shimmer color for a pixel, lifted to the brightness floor if it is meant to be lit
+    fn pixel_color(&self, color_index: f64, brightness: f64, palette: ColorPalette) -> LedColor {
+        let color = get_shimmer_color(color_index, brightness, palette);
+        if self.min_pixel_value == 0 || brightness <= 0.0 {
+            return color;
+        }
+        let full = get_shimmer_color(color_index, 255.0, palette);
+        apply_brightness_floor(color, &full, self.min_pixel_value)
+    }
+
+    // Drawer for eyes looking sideways: the mirrored half moves the same way on screen
+    // as the left half instead of the opposite way, so the eyes don't cross
+    fn with_gaze(&self, gaze_x: f64) -> Self {
+        Self {
+            mirror_offset: self.mirror_offset + 2 * gaze_x as i32,
+            ..self.clone()
+        }
+    }
+}
 
 impl DrawPixelFn for PixelDrawer {
-    fn draw(&self, canvas: &mut LedCanvas, bright_f: f64, color_index: f64,
+    fn draw(&self, canvas: &mut VirtualLedCanvas, bright_f: f64, color_index: f64,
             x: i32, y: i32, brightness: f64, palette: ColorPalette) {
         // Flip vertically only
         let flipped_y = PANEL_HEIGHT - 1 - y;
@@ -78,16 +305,32 @@ impl DrawPixelFn for PixelDrawer {
             return;
         }
 
+        let color_index = color_index + self.palette_phase;
         let adjusted_brightness = bright_f * brightness;
-        let color = get_shimmer_color(color_index, adjusted_brightness, palette);
+        let color = self.pixel_color(color_index, adjusted_brightness, palette);
 
         // Draw on left panel (vertically flipped)
-        canvas.set(x, flipped_y, &color);
+        if self.side != Some(PanelSide::Right) {
+            canvas.set(x, flipped_y, &color);
+        }
+        if self.side == Some(PanelSide::Left) {
+            return;
+        }
 
-        // Mirror on right panel (also vertically flipped)
-        let mirror_x = (PANEL_WIDTH * 2) - 1 - x;
+        // Mirror on right panel (also vertically flipped), reusing the left color
+        // unless a per-side bias is set
+        // Pixels shifted past either edge of the right panel are dropped, so the offset
+        // never spills onto the left half (or anywhere on a single-panel canvas)
+        let mirror_x = (PANEL_WIDTH * 2) - 1 - x + self.mirror_offset;
         if mirror_x >= PANEL_WIDTH && mirror_x < PANEL_WIDTH * 2 {
-            canvas.set(mirror_x, flipped_y, &color);
+            if self.right_brightness == 1.0 && self.right_color_shift == 0.0 {
+                canvas.set(mirror_x, flipped_y, &color);
+            } else {
+                let right_color = self.pixel_color(color_index + self.right_color_shift,
+                                                   adjusted_brightness * self.right_brightness,
+                                                   palette);
+                canvas.set(mirror_x, flipped_y, &right_color);
+            }
         }
     }
 }
@@ -116,25 +359,40 @@ impl FaceElement for EyeElementAdapter {
         ElementCategory::Eyes
     }
 
+    fn z_order(&self) -> i32 {
+        self.eye.z_order()
+    }
+
     fn description(&self) -> &str {
         self.eye.description()
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64) {
-        self.eye.update(shared_state, dt);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64) {
+        self.eye.update(shared_state, context, dt);
     }
 
-    fn render(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
               shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         self.eye.draw(canvas, context, shared_state, draw_pixel_fn);
     }
 
+    fn center(&self) -> Option<(f64, f64)> {
+        let position = self.eye.position();
+        Some((position.center_x, position.center_y))
+    }
+
     fn as_any(&self) -> &dyn Any {
-        self
+        // Expose the wrapped element so callers can downcast to e.g. DefaultEyes
+        self.eye.as_any()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+        self.eye.as_any_mut()
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(EyeElementAdapter::new(self.eye.clone_box()))
     }
 }
 
@@ -158,25 +416,34 @@ impl FaceElement for MouthElementAdapter {
         ElementCategory::Mouth
     }
 
+    fn z_order(&self) -> i32 {
+        self.mouth.z_order()
+    }
+
     fn description(&self) -> &str {
         self.mouth.description()
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64) {
-        self.mouth.update(shared_state, dt);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64) {
+        self.mouth.update(shared_state, context, dt);
     }
 
-    fn render(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
               shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         self.mouth.draw(canvas, context, shared_state, draw_pixel_fn);
     }
 
     fn as_any(&self) -> &dyn Any {
-        self
+        self.mouth.as_any()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+        self.mouth.as_any_mut()
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(MouthElementAdapter::new(self.mouth.clone_box()))
     }
 }
 
@@ -200,25 +467,93 @@ impl FaceElement for NoseElementAdapter {
         ElementCategory::Nose
     }
 
+    fn z_order(&self) -> i32 {
+        self.nose.z_order()
+    }
+
     fn description(&self) -> &str {
         self.nose.description()
     }
 
-    fn update(&mut self, shared_state: &mut SharedFaceState, dt: f64) {
-        self.nose.update(shared_state, dt);
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64) {
+        self.nose.update(shared_state, context, dt);
     }
 
-    fn render(&self, canvas: &mut LedCanvas, context: &RenderContext,
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
               shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
         self.nose.draw(canvas, context, shared_state, draw_pixel_fn);
     }
 
     fn as_any(&self) -> &dyn Any {
-        self
+        self.nose.as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self.nose.as_any_mut()
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(NoseElementAdapter::new(self.nose.clone_box()))
+    }
+}
+
+// Wrapper to adapt Accessory trait to FaceElement trait
+struct AccessoryElementAdapter {
+    accessory: Box<dyn elements::accessories::Accessory>,
+}
+
+impl AccessoryElementAdapter {
+    fn new(accessory: Box<dyn elements::accessories::Accessory>) -> Self {
+        Self { accessory }
+    }
+}
+
+impl FaceElement for AccessoryElementAdapter {
+    fn name(&self) -> &str {
+        self.accessory.name()
+    }
+
+    fn category(&self) -> ElementCategory {
+        ElementCategory::Accessory
+    }
+
+    fn z_order(&self) -> i32 {
+        self.accessory.z_order()
+    }
+
+    fn description(&self) -> &str {
+        self.accessory.description()
+    }
+
+    fn update(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+              dt: f64) {
+        self.accessory.update(shared_state, context, dt);
+    }
+
+    fn render(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+              shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
+        self.accessory.draw(canvas, context, shared_state, draw_pixel_fn);
+    }
+
+    fn handle_button(&mut self, button: Button, shared_state: &mut SharedFaceState) -> bool {
+        self.accessory.handle_button(button, shared_state)
+    }
+
+    fn config_fields(&self) -> Vec<ConfigField> {
+        self.accessory.config_fields()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.accessory.as_any()
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+        self.accessory.as_any_mut()
+    }
+
+    fn clone_box(&self) -> Box<dyn FaceElement> {
+        Box::new(AccessoryElementAdapter::new(self.accessory.clone_box()))
     }
 }
 
@@ -226,10 +561,48 @@ impl FaceElement for NoseElementAdapter {
 // FACE ELEMENT REGISTRY
 // ============================================================================
 
+// How fast a newly selected eye variant slides to its own center (per second)
+const EYE_SLIDE_RATE: f64 = 8.0;
+
+// How fast the old mouth fades into a newly selected one (fraction of the fade per second)
+const MOUTH_FADE_RATE: f64 = 4.0;
+
+// Pixels the eyes move at full right stick deflection
+const GAZE_RANGE: f64 = 4.0;
+
+// How fast the eyes ease into a new look pose (fraction of the remaining distance per second)
+const POSE_EASE_RATE: f64 = 12.0;
+
+// How fast the mouth eases into a smile or frown (fraction of the remaining curve per second)
+const MOUTH_EMOTION_EASE_RATE: f64 = 10.0;
+
+// How fast the expression follows the heart rate (fraction of the remaining change per
+// second), slow so it builds up and calms down over several seconds
+const HEART_RATE_EASE_RATE: f64 = 0.3;
+
+// Brightness swing while breathing, a soft glow at rest even with no oscillation configured
+const BREATHING_OSCILLATION_AMPLITUDE: f64 = 0.1;
+
+// Oscillation never dims the face below this (or lifts it above full brightness)
+const MIN_OSCILLATION_BRIGHTNESS: f64 = 0.1;
+
+// Cross-fade between two mouth variants after switching, `progress` runs 0.0 -> 1.0
+struct MouthTransition {
+    from_index: usize,
+    to_index: usize,
+    progress: f64,
+    speed: f64,  // Progress per second
+}
+
 struct FaceElementRegistry {
     elements: Vec<Box<dyn FaceElement>>,
     active_eyes_index: usize,
     eyes_variants: Vec<String>,
+    active_mouth_index: usize,
+    mouth_variants: Vec<String>,
+    disabled: HashSet<String>,  // Registered but currently hidden (not updated or rendered)
+    eye_offset: (f64, f64),     // Eye slide-in offset after switching variants, eases to 0
+    mouth_transition: Option<MouthTransition>,  // Fade from the previous mouth, if one is running
 }
 
 impl FaceElementRegistry {
@@ -238,55 +611,242 @@ impl FaceElementRegistry {
             elements: Vec::new(),
             active_eyes_index: 0,
             eyes_variants: Vec::new(),
+            active_mouth_index: 0,
+            mouth_variants: Vec::new(),
+            disabled: HashSet::new(),
+            eye_offset: (0.0, 0.0),
+            mouth_transition: None,
         }
     }
 
     fn register(&mut self, element: Box<dyn FaceElement>) {
-        if element.category() == ElementCategory::Eyes {
-            self.eyes_variants.push(element.name().to_string());
+        match element.category() {
+            ElementCategory::Eyes => self.eyes_variants.push(element.name().to_string()),
+            ElementCategory::Mouth => self.mouth_variants.push(element.name().to_string()),
+            _ => {}
         }
         self.elements.push(element);
     }
 
-    fn update_all(&mut self, shared_state: &mut SharedFaceState, dt: f64) {
-        for element in self.elements.iter_mut() {
-            if element.category() == ElementCategory::Eyes {
-                let eye_idx = self.eyes_variants.iter()
-                    .position(|n| n == element.name());
-                if let Some(ei) = eye_idx {
-                    if ei != self.active_eyes_index {
-                        continue;
-                    }
-                }
+    fn unregister(&mut self, name: &str) -> Option<Box<dyn FaceElement>> {
+        let index = self.elements.iter().position(|e| e.name() == name)?;
+        let element = self.elements.remove(index);
+        self.disabled.remove(name);
+
+        match element.category() {
+            ElementCategory::Eyes => {
+                remove_variant(&mut self.eyes_variants, &mut self.active_eyes_index, name);
             }
-            element.update(shared_state, dt);
+            ElementCategory::Mouth => {
+                remove_variant(&mut self.mouth_variants, &mut self.active_mouth_index, name);
+                // The fade's indices may no longer match
+                self.mouth_transition = None;
+            }
+            _ => {}
         }
+        Some(element)
+    }
+
+    // Whether an element should be updated and drawn (only one eye and mouth variant is active)
+    fn is_active(&self, element: &dyn FaceElement) -> bool {
+        is_element_active(element, &self.disabled,
+                          &self.eyes_variants, self.active_eyes_index,
+                          &self.mouth_variants, self.active_mouth_index)
     }
 
-    fn render_all(&self, canvas: &mut LedCanvas, context: &RenderContext,
-                  shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn) {
-        let order = [ElementCategory::Mouth, ElementCategory::Nose,
-                     ElementCategory::Eyes, ElementCategory::Accessory];
+    /// Look up an element by name and downcast it to its concrete type
+    ///
+    /// Elements registered through the category adapters (eyes, mouths, noses,
+    /// accessories) downcast to the wrapped type, not the adapter:
+    ///
+    /// ```ignore
+    /// if let Some(blep) = registry.get_element_mut::<BlepAccessory>("Blep") {
+    ///     blep.set_auto_retract(Some(3.0));
+    /// }
+    /// ```
+    ///
+    /// Returns None if no element has that name or it is a different type.
+    fn get_element<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.elements.iter()
+            .find(|e| e.name() == name)
+            .and_then(|e| e.as_any().downcast_ref::<T>())
+    }
 
-        for category in &order {
-            for element in self.elements.iter() {
-                if element.category() != *category {
-                    continue;
-                }
-                if *category == ElementCategory::Eyes {
-                    let eye_idx = self.eyes_variants.iter()
-                        .position(|n| n == element.name());
-                    if let Some(ei) = eye_idx {
-                        if ei != self.active_eyes_index {
-                            continue;
-                        }
-                    }
+    /// Mutable version of `get_element`
+    fn get_element_mut<T: 'static>(&mut self, name: &str) -> Option<&mut T> {
+        self.elements.iter_mut()
+            .find(|e| e.name() == name)
+            .and_then(|e| e.as_any_mut().downcast_mut::<T>())
+    }
+
+    fn is_enabled(&self, name: &str) -> bool {
+        !self.disabled.contains(name)
+    }
+
+    // Registered and switched on
+    fn is_shown(&self, name: &str) -> bool {
+        self.elements.iter().any(|e| e.name() == name) && self.is_enabled(name)
+    }
+
+    // Every registered element, in registration order
+    fn describe(&self) -> Vec<ElementDescription> {
+        let active_eyes = self.get_active_eyes_name();
+        let active_mouth = self.get_active_mouth_name();
+        self.elements.iter()
+            .map(|e| {
+                let enabled = self.is_enabled(e.name());
+                ElementDescription {
+                    name: e.name().to_string(),
+                    category: e.category(),
+                    description: e.description().to_string(),
+                    enabled,
+                    active: match e.category() {
+                        ElementCategory::Eyes => e.name() == active_eyes,
+                        ElementCategory::Mouth => e.name() == active_mouth,
+                        _ => enabled,
+                    },
+                    priority: e.z_order(),
+                    config_fields: e.config_fields(),
                 }
-                element.render(canvas, context, shared_state, draw_pixel_fn);
+            })
+            .collect()
+    }
+
+    // Names of the accessory elements currently switched on, in registration order
+    fn shown_accessories(&self) -> Vec<String> {
+        self.elements.iter()
+            .filter(|e| e.category() == ElementCategory::Accessory && self.is_enabled(e.name()))
+            .map(|e| e.name().to_string())
+            .collect()
+    }
+
+    // Names of the elements currently switched off, sorted
+    fn hidden_elements(&self) -> Vec<String> {
+        let mut hidden: Vec<String> = self.disabled.iter().cloned().collect();
+        hidden.sort();
+        hidden
+    }
+
+    // Show every element except the given ones (names that aren't registered are ignored)
+    fn set_hidden(&mut self, hidden: &[String]) {
+        self.disabled = self.elements.iter()
+            .map(|e| e.name().to_string())
+            .filter(|name| hidden.contains(name))
+            .collect();
+    }
+
+    // Turn an element on or off without removing it, returns false if it isn't registered
+    fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        if !self.elements.iter().any(|e| e.name() == name) {
+            return false;
+        }
+        if enabled {
+            self.disabled.remove(name);
+        } else {
+            self.disabled.insert(name.to_string());
+        }
+        true
+    }
+
+    fn update_all(&mut self, shared_state: &mut SharedFaceState, context: &mut RenderContext,
+                  dt: f64) {
+        for element in self.elements.iter_mut() {
+            // Field-level call so `elements` can stay mutably borrowed
+            if !is_element_active(element.as_ref(), &self.disabled,
+                                  &self.eyes_variants, self.active_eyes_index,
+                                  &self.mouth_variants, self.active_mouth_index) {
+                continue;
+            }
+            element.update(shared_state, context, dt);
+        }
+
+        // Ease the eye slide offset back to the new variant's own position
+        let keep = 1.0 - (EYE_SLIDE_RATE * dt).min(1.0);
+        self.eye_offset = (self.eye_offset.0 * keep, self.eye_offset.1 * keep);
+        // (a non-finite offset, which would never settle, is dropped right away)
+        let settled = self.eye_offset.0.abs() < 0.05 && self.eye_offset.1.abs() < 0.05;
+        if settled || !self.eye_offset.0.is_finite() || !self.eye_offset.1.is_finite() {
+            self.eye_offset = (0.0, 0.0);
+        }
+
+        if let Some(transition) = self.mouth_transition.as_mut() {
+            transition.progress += transition.speed * dt;
+            if transition.progress >= 1.0 {
+                self.mouth_transition = None;
             }
         }
     }
 
+    fn eye_center(&self, index: usize) -> Option<(f64, f64)> {
+        let name = self.eyes_variants.get(index)?;
+        self.elements.iter().find(|e| e.name() == name)?.center()
+    }
+
+    // Activate another eye variant, starting it where the previous one was drawn
+    fn switch_eyes(&mut self, index: usize) {
+        if let (Some(old), Some(new)) = (self.eye_center(self.active_eyes_index),
+                                         self.eye_center(index)) {
+            self.eye_offset.0 += old.0 - new.0;
+            self.eye_offset.1 += old.1 - new.1;
+        }
+        self.active_eyes_index = index;
+    }
+
+    // Activate another mouth variant, fading over from the current one
+    fn switch_mouth(&mut self, index: usize) {
+        if index != self.active_mouth_index {
+            self.mouth_transition = Some(MouthTransition {
+                from_index: self.active_mouth_index,
+                to_index: index,
+                progress: 0.0,
+                speed: MOUTH_FADE_RATE,
+            });
+        }
+        self.active_mouth_index = index;
+    }
+
+    // Active element that has taken over the screen, the topmost one if several want to
+    fn exclusive_element(&self) -> Option<&dyn FaceElement> {
+        self.elements.iter()
+            .map(|e| e.as_ref())
+            .filter(|e| self.is_active(*e) && e.wants_exclusive_render())
+            .max_by_key(|e| e.z_order())
+    }
+
+    fn has_exclusive_element(&self) -> bool {
+        self.exclusive_element().is_some()
+    }
+
+    // Active elements in render order (inactive variants and disabled elements are skipped)
+    // Sorted by z_order; the sort is stable so equal values keep registration order
+    // An element wanting exclusive render is drawn alone
+    fn render_order(&self) -> Vec<&dyn FaceElement> {
+        if let Some(exclusive) = self.exclusive_element() {
+            return vec![exclusive];
+        }
+        let mut ordered: Vec<&dyn FaceElement> = self.elements.iter()
+            .map(|e| e.as_ref())
+            .filter(|e| self.is_active(*e))
+            .collect();
+        ordered.sort_by_key(|e| e.z_order());
+        ordered
+    }
+
+    // Copies of the active elements in render order, for a FaceFrame
+    fn frame_elements(&self) -> Vec<Box<dyn FaceElement>> {
+        self.render_order().into_iter().map(|e| e.clone_box()).collect()
+    }
+
+    // Copy of the previous mouth and how far the fade to the active one has got, while a
+    // mouth switch settles
+    fn fading_mouth(&self) -> Option<(Box<dyn FaceElement>, f64)> {
+        let transition = self.mouth_transition.as_ref()
+            .filter(|t| t.to_index == self.active_mouth_index)?;
+        let name = self.mouth_variants.get(transition.from_index)?;
+        let from = self.elements.iter().find(|e| e.name() == name)?;
+        Some((from.clone_box(), transition.progress))
+    }
+
     fn handle_button(&mut self, button: Button, shared_state: &mut SharedFaceState) -> bool {
         for element in &mut self.elements {
             if element.handle_button(button, shared_state) {
@@ -298,20 +858,61 @@ impl FaceElementRegistry {
 
     fn cycle_eyes_forward(&mut self) {
         if !self.eyes_variants.is_empty() {
-            self.active_eyes_index = (self.active_eyes_index + 1) % self.eyes_variants.len();
+            self.switch_eyes((self.active_eyes_index + 1) % self.eyes_variants.len());
         }
     }
 
     fn cycle_eyes_backward(&mut self) {
         if !self.eyes_variants.is_empty() {
-            if self.active_eyes_index == 0 {
-                self.active_eyes_index = self.eyes_variants.len() - 1;
+            let index = if self.active_eyes_index == 0 {
+                self.eyes_variants.len() - 1
             } else {
-                self.active_eyes_index -= 1;
+                self.active_eyes_index - 1
+            };
+            self.switch_eyes(index);
+        }
+    }
+
+    fn set_active_eyes(&mut self, name: &str) -> bool {
+        match self.eyes_variants.iter().position(|n| n == name) {
+            Some(index) => {
+                self.switch_eyes(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cycle_mouth(&mut self) {
+        if !self.mouth_variants.is_empty() {
+            self.switch_mouth((self.active_mouth_index + 1) % self.mouth_variants.len());
+        }
+    }
+
+    fn set_active_mouth(&mut self, name: &str) -> bool {
+        match self.mouth_variants.iter().position(|n| n == name) {
+            Some(index) => {
+                self.switch_mouth(index);
+                true
             }
+            None => false,
         }
     }
 
+    fn list_eyes(&self) -> Vec<String> {
+        self.eyes_variants.clone()
+    }
+
+    fn list_mouths(&self) -> Vec<String> {
+        self.mouth_variants.clone()
+    }
+
+    fn get_active_mouth_name(&self) -> String {
+        self.mouth_variants.get(self.active_mouth_index)
+            .cloned()
+            .unwrap_or_else(|| "None".to_string())
+    }
+
     fn get_active_eyes_name(&self) -> String {
         self.eyes_variants.get(self.active_eyes_index)
             .cloned()
@@ -319,24 +920,102 @@ impl FaceElementRegistry {
     }
 }
 
+// Remove a variant name, keeping the same variant active where possible
+fn remove_variant(variants: &mut Vec<String>, active_index: &mut usize, name: &str) {
+    if let Some(index) = variants.iter().position(|n| n == name) {
+        variants.remove(index);
+        // Keep the same variant active if it comes after the removed one
+        if *active_index > index {
+            *active_index -= 1;
+        } else if *active_index >= variants.len() {
+            *active_index = 0;
+        }
+    }
+}
+
+fn is_element_active(element: &dyn FaceElement, disabled: &HashSet<String>,
+                     eyes_variants: &[String], active_eyes_index: usize,
+                     mouth_variants: &[String], active_mouth_index: usize) -> bool {
+    if disabled.contains(element.name()) {
+        return false;
+    }
+    let (variants, active_index) = match element.category() {
+        ElementCategory::Eyes => (eyes_variants, active_eyes_index),
+        ElementCategory::Mouth => (mouth_variants, active_mouth_index),
+        _ => return true,
+    };
+    match variants.iter().position(|n| n == element.name()) {
+        Some(index) => index == active_index,
+        None => true,
+    }
+}
+
 // ============================================================================
 // PROTOGEN FACE
 // ============================================================================
 
+/// Custom drawing run every frame after the face and its effects (see `FaceRenderer::add_render_hook`)
+///
+/// The canvas is the whole display in panel coordinates (both halves, y down, no mirroring)
+pub type RenderHook = Box<dyn FnMut(&mut VirtualLedCanvas, &RenderContext) + Send>;
+
 pub struct ProtogenFace {
     time_counter: f64,
     state: Arc<Mutex<MaskState>>,
     registry: FaceElementRegistry,
     shared_state: SharedFaceState,
     pixel_drawer: PixelDrawer,
+    debug_overlay: bool,
+    audio_level: Arc<AudioLevel>,
+    idle_scheduler: IdleAnimationScheduler,
+    metrics: Arc<FaceMetrics>,
+    frame_secs: f64,  // Time each update advances animations by (1 / target FPS)
+    frames: FaceFrames,  // Where each update's frame goes for the renderer
+    // Post-processing settings, passed to the renderer with every frame
+    glitch: (f64, f64),            // Intensity and bursts per second
+    bloom: Option<(i32, f64)>,     // Soft glow radius and intensity, None when disabled
+    eye_glow: Option<(i32, f64)>,  // Halo around the eyes only, None when disabled
+    color_order: ColorOrder,  // Channel order of the physical panels
+    eye_pose_distance: f64,     // Pixels the eyes move for a look pose
+    shimmer_mode: ShimmerMode,  // How elements spread the palette
+    anti_aliasing: bool,        // Smooth shape edges (off trades looks for speed)
+    oscillation_amplitude: f64,  // Brightness swing around the set brightness (0 = steady)
+    oscillation_hz: f64,
+    oscillation_phase: f64,      // Radians, advanced by 2π·hz every second
+    fade_speed: f64,             // Of the renderer's whole-face fade (video, standby)
+    pose_offset: (f64, f64),    // Current (eased) look pose offset
+    mouth_emotion_curve: f64,   // Pixels the mouth corner moves for a full smile or frown
+    mouth_blend: MouthBlend,    // How manual mouth mode combines the trigger with audio
+    mouth_blend_weight: f64,    // Trigger share of a Mix blend (0.0 to 1.0)
+    stereo_mouth: bool,         // Open each panel's mouth with its own microphone channel
+    emotes: Vec<Emote>,         // Emotes that can be triggered, from the config
+    emote_queue: EmoteQueue,
+    emote_restore: Option<EmoteRestore>,  // Face to go back to when the playing emote ends
+    emote_sides: (Option<PanelSide>, Option<PanelSide>),  // Wink and smirk of the playing emote
+    snapshots: SnapshotStore,       // Saved looks, recalled by slot name
+    profiles: ProfileManager,       // Named looks from profiles.toml, one per D-pad direction
+    quality: QualityManager,        // Drops optional effects while rendering is over budget
+    cpu_temperature: Option<CpuTemperature>,  // Reported in the status line once set
+    heart_rate: Option<HeartRate>,   // BLE heart rate monitor driving emotion_intensity
+    heart_rate_range: (u32, u32),    // Resting and excited BPM
+}
+
+// What an emote changed, so it can be put back afterwards
+struct EmoteRestore {
+    eyes: String,
+    palette: ColorPalette,
+    accessory: Option<(String, bool)>,  // Accessory name and whether it was enabled before
 }
 
 impl ProtogenFace {
-    pub fn new(audio_level: Arc<AudioLevel>, state: Arc<Mutex<MaskState>>) -> Self {
+    pub fn new(audio_level: Arc<AudioLevel>, state: Arc<Mutex<MaskState>>,
+               metrics: Arc<FaceMetrics>) -> Self {
+        // Starting settings until main applies the loaded config
+        let defaults = Config::default();
         let mut registry = FaceElementRegistry::new();
 
         // Auto-register all face element types from elements module
-        for eye in elements::get_all_eye_types() {
+        for eye in elements::get_all_eye_types(audio_level.clone()) {
             registry.register(Box::new(EyeElementAdapter::new(eye)));
         }
 
@@ -348,8 +1027,40 @@ impl ProtogenFace {
             registry.register(Box::new(NoseElementAdapter::new(nose)));
         }
 
-        println!("✨ Registered {} face elements", registry.elements.len());
-        println!("   Eyes: {}", registry.eyes_variants.join(", "));
+        for accessory in elements::get_all_accessory_types(audio_level.clone()) {
+            registry.register(Box::new(AccessoryElementAdapter::new(accessory)));
+        }
+        registry.set_enabled("Spectrum Bars", defaults.spectrum_enabled);
+        registry.set_enabled("Clock", defaults.show_clock);
+
+        registry.register(Box::new(elements::RainbowCycleBackground::new(
+            defaults.background_brightness)));
+        registry.register(Box::new(MatrixRainEffect::new(
+            defaults.matrix_rain_duration_secs)));
+
+        // Fire takes over the whole display, so it's off until enabled (config or API)
+        registry.register(Box::new(FireSimulation::new(audio_level.clone())));
+        registry.set_enabled("Fire", defaults.fire_enabled);
+        registry.register(Box::new(WaveformDisplay::new(audio_level.clone(),
+                                                        defaults.waveform_smoothed)));
+        registry.set_enabled("Waveform", defaults.waveform_enabled);
+        registry.register(Box::new(ColorBurstEffect::new(audio_level.clone(),
+                                                         defaults.burst_ring_speed,
+                                                         defaults.burst_ring_width)));
+        registry.set_enabled("Color Burst", defaults.burst_enabled);
+        registry.register(Box::new(PulseBreathingEffect::new()));
+        registry.set_enabled("Pulse Breathing", defaults.pulse_breathing_enabled);
+
+        // Idle animations stay hidden until the scheduler picks one
+        let idle_scheduler = IdleAnimationScheduler::with_default_animations(
+            defaults.idle_animation_interval_secs);
+        for animation in idle_scheduler.animations() {
+            registry.set_enabled(&animation.element_name, false);
+        }
+
+        info!("✨ Registered {} face elements", registry.elements.len());
+        info!("   Eyes: {}", registry.eyes_variants.join(", "));
+        info!("   Mouths: {}", registry.mouth_variants.join(", "));
 
         Self {
             time_counter: 0.0,
@@ -357,77 +1068,1245 @@ impl ProtogenFace {
             registry,
             shared_state: SharedFaceState {
                 mouth_opening: 0.0,
-                eye_top: 9.0,
-                eye_bottom: 1.45,
+                eye_top: OPEN_EYE_TOP,
+                eye_bottom: OPEN_EYE_BOTTOM,
                 blink_enabled: true,
                 manual_mouth_active: false,
+                idle_breathing: false,
+                gaze_offset: (0.0, 0.0),
+                mouth_curve: 0.0,
+                wink: None,
+                smirk: None,
+                emotion_intensity: 0.0,
+                stereo_mouth: None,
             },
-            pixel_drawer: PixelDrawer,
+            pixel_drawer: PixelDrawer::new(),
+            debug_overlay: false,
+            audio_level,
+            idle_scheduler,
+            metrics,
+            frame_secs: 1.0 / 30.0,
+            frames: FaceFrames::default(),
+            glitch: (defaults.glitch_intensity, defaults.glitch_frequency),
+            bloom: None,
+            eye_glow: None,
+            color_order: ColorOrder::Rgb,
+            eye_pose_distance: defaults.eye_pose_distance,
+            shimmer_mode: defaults.shimmer_mode,
+            anti_aliasing: defaults.anti_aliasing,
+            oscillation_amplitude: defaults.brightness_oscillation_amplitude,
+            oscillation_hz: defaults.brightness_oscillation_hz,
+            oscillation_phase: 0.0,
+            fade_speed: defaults.fade_speed,
+            pose_offset: (0.0, 0.0),
+            mouth_emotion_curve: defaults.mouth_emotion_curve,
+            mouth_blend: defaults.mouth_blend,
+            mouth_blend_weight: defaults.mouth_blend_weight,
+            stereo_mouth: defaults.stereo_mouth,
+            emotes: defaults.emotes,
+            emote_queue: EmoteQueue::new(),
+            emote_restore: None,
+            emote_sides: (None, None),
+            snapshots: SnapshotStore::load(SNAPSHOT_PATH),
+            profiles: ProfileManager::load(PROFILES_PATH),
+            quality: QualityManager::new(defaults.adaptive_quality, defaults.quality_shed_order),
+            cpu_temperature: None,
+            heart_rate: None,
+            heart_rate_range: (defaults.heart_rate_rest_bpm, defaults.heart_rate_excited_bpm),
         }
     }
 
-    pub fn render(&mut self, canvas: &mut LedCanvas) {
-        self.time_counter += 1.0;
-
-        // Get mask state
-        let state = self.state.lock().unwrap();
-        self.shared_state.blink_enabled = state.blink_enabled;
-        let brightness = state.brightness;
-        let palette = state.color_palette;
-        let manual_mouth_mode = state.manual_mouth_mode;
-        let mouth_analog_value = state.mouth_analog_value;
+    /// Replace the emotes that can be triggered by name or gamepad slot
+    pub fn set_emotes(&mut self, emotes: Vec<Emote>) {
+        self.emotes = emotes;
+    }
 
-        self.shared_state.manual_mouth_active = manual_mouth_mode;
-        drop(state);
+    /// Queue an emote by name, returns false if there is no such emote
+    /// ("wink" falls back to the built-in wink-and-smirk when the config has none)
+    pub fn queue_emote(&mut self, name: &str) -> bool {
+        let emote = self.emotes.iter().find(|e| e.name == name).cloned()
+            .or_else(|| (name == WINK_EMOTE).then(Emote::wink));
+        match emote {
+            Some(emote) => {
+                self.emote_queue.push(emote);
+                true
+            }
+            None => false,
+        }
+    }
 
-        // Update all elements
-        self.registry.update_all(&mut self.shared_state, 0.033);
+    // End the playing emote when its time is up and start the next queued one
+    fn update_emotes(&mut self, dt: f64) {
+        let change = self.emote_queue.update(dt);
+
+        if change.finished.is_some() {
+            self.emote_sides = (None, None);
+            if let Some(restore) = self.emote_restore.take() {
+                self.registry.set_active_eyes(&restore.eyes);
+                self.state.lock().unwrap().color_palette = restore.palette;
+                if let Some((name, enabled)) = restore.accessory {
+                    self.registry.set_enabled(&name, enabled);
+                }
+            }
+        }
 
-        // Apply manual mouth control
-        if manual_mouth_mode {
-            self.shared_state.mouth_opening = mouth_analog_value * MOUTH_MAX_OPENING;
+        if let Some(emote) = change.started {
+            info!("😮 Emote: {}", emote.name);
+            let mut state = self.state.lock().unwrap();
+            self.emote_restore = Some(EmoteRestore {
+                eyes: self.registry.get_active_eyes_name(),
+                palette: state.color_palette,
+                accessory: emote.accessory.as_ref()
+                    .map(|name| (name.clone(), self.registry.is_enabled(name))),
+            });
+            if let Some(palette) = emote.palette {
+                state.color_palette = palette;
+            }
+            drop(state);
+            self.emote_sides = (emote.wink, emote.smirk);
+            if let Some(eyes) = &emote.eyes {
+                if !self.registry.set_active_eyes(eyes) {
+                    warn!("⚠️  Emote {}: unknown eyes \"{}\"", emote.name, eyes);
+                }
+            }
+            if let Some(accessory) = &emote.accessory {
+                if !self.registry.set_enabled(accessory, true) {
+                    warn!("⚠️  Emote {}: unknown accessory \"{}\"", emote.name, accessory);
+                }
+            }
         }
+    }
 
-        // Clear canvas
-        canvas.clear();
+    /// Capture the current look into a named slot and write it to the state file
+    /// (while an emote plays, the face it will return to is captured instead)
+    pub fn snapshot(&mut self, slot: &str) -> std::io::Result<()> {
+        let snapshot = self.capture_look();
+        self.snapshots.save(slot, snapshot)
+    }
 
-        // Create render context
-        let context = RenderContext {
-            offset_x: 0.0,
-            offset_y: 0.0,
-            time_counter: self.time_counter,
-            brightness,
-            palette,
+    /// Put the face back to a saved look, returns false if the slot is empty
+    /// The palette and brightness change can be undone like any other
+    pub fn recall(&mut self, slot: &str) -> bool {
+        let Some(snapshot) = self.snapshots.get(slot).cloned() else {
+            return false;
         };
+        self.apply_look(&snapshot, "snapshot");
+        true
+    }
 
-        // Render all elements
-        self.registry.render_all(canvas, &context, &self.shared_state, &self.pixel_drawer)
+    /// Switch to the profile with the given name, returns false if there is none
+    /// (changes made afterwards stay out of the file until `save_profile`)
+    pub fn switch_profile(&mut self, name: &str) -> bool {
+        let Some(slot) = self.profiles.find(name) else {
+            return false;
+        };
+        let look = self.profiles.get(slot).unwrap().look.clone();
+        self.apply_look(&look, "profile");
+        self.profiles.set_active(slot);
+        true
     }
 
-    pub fn handle_element_button(&mut self, button: Button) -> bool {
-        self.registry.handle_button(button, &mut self.shared_state)
+    /// Write the current look to profiles.toml under a name (a new name takes a free slot)
+    pub fn save_profile(&mut self, name: &str) -> std::io::Result<()> {
+        let look = self.capture_look();
+        let slot = self.profiles.save(name, look)?;
+        self.profiles.set_active(slot);
+        Ok(())
     }
 
-    pub fn get_active_eyes_name(&self) -> String {
-        self.registry.get_active_eyes_name()
+    /// Names of the loaded profiles, in D-pad order (up, right, down, left)
+    pub fn list_profiles(&self) -> Vec<String> {
+        self.profiles.names()
     }
 
-    pub fn get_mouth_opening(&self) -> f64 {
-        self.shared_state.mouth_opening
+    // The live look, with whatever a playing emote changed taken back out
+    fn capture_look(&self) -> FaceSnapshot {
+        let state = self.state.lock().unwrap();
+        let mut look = FaceSnapshot {
+            eyes: self.registry.get_active_eyes_name(),
+            mouth: self.registry.get_active_mouth_name(),
+            hidden: self.registry.hidden_elements(),
+            palette: state.color_palette,
+            brightness: state.brightness,
+            blink_enabled: state.blink_enabled,
+            glitch_enabled: state.glitch_enabled,
+            night_vision: state.night_vision,
+            invert: state.invert,
+        };
+        drop(state);
+        if let Some(restore) = &self.emote_restore {
+            look.eyes = restore.eyes.clone();
+            look.palette = restore.palette;
+            if let Some((name, enabled)) = &restore.accessory {
+                look.hidden.retain(|hidden| hidden != name);
+                if !enabled {
+                    look.hidden.push(name.clone());
+                    look.hidden.sort();
+                }
+            }
+        }
+        look
     }
-}
 
-// Implement CycleEyes trait for gamepad controls
-impl CycleEyes for ProtogenFace {
-    fn cycle_eyes_forward(&mut self) {
-        self.registry.cycle_eyes_forward();
-        let eyes_name = self.registry.get_active_eyes_name();
-        println!("👁️  Eyes: {} (→)", eyes_name);
+    // Put a saved look on the face, recorded for undo as `action`
+    fn apply_look(&mut self, look: &FaceSnapshot, action: &'static str) {
+        // A playing emote would put its own saved face back when it ends
+        self.emote_restore = None;
+        if !self.registry.set_active_eyes(&look.eyes) {
+            warn!("⚠️  Unknown eyes \"{}\" in {}", look.eyes, action);
+        }
+        if !self.registry.set_active_mouth(&look.mouth) {
+            warn!("⚠️  Unknown mouth \"{}\" in {}", look.mouth, action);
+        }
+        self.registry.set_hidden(&look.hidden);
+
+        let mut state = self.state.lock().unwrap();
+        state.record(action);
+        state.color_palette = look.palette;
+        state.set_brightness(look.brightness);
+        state.blink_enabled = look.blink_enabled;
+        state.glitch_enabled = look.glitch_enabled;
+        state.night_vision = look.night_vision;
+        state.invert = look.invert;
     }
 
-    fn cycle_eyes_backward(&mut self) {
-        self.registry.cycle_eyes_backward();
-        let eyes_name = self.registry.get_active_eyes_name();
-        println!("👁️  Eyes: {} (←)", eyes_name);
+    /// Turn adaptive quality on or off and set the order effects are dropped in
+    /// (turning it off brings back anything it dropped)
+    pub fn set_adaptive_quality(&mut self, enabled: bool, order: Vec<QualityEffect>) {
+        self.quality.configure(enabled, order);
+        if !enabled {
+            while let Some(effect) = self.quality.pop_shed() {
+                self.set_quality_effect(effect, true);
+            }
+        }
+    }
+
+    // Drop or bring back an optional effect when the render load calls for it
+    fn update_quality(&mut self, render_secs: f64) {
+        match self.quality.update(render_secs, self.frame_secs) {
+            Some(QualityAction::Shed) => {
+                let next = self.quality.next_to_shed(|effect| self.quality_effect_running(effect));
+                if let Some(effect) = next {
+                    self.set_quality_effect(effect, false);
+                    self.quality.mark_shed(effect);
+                    warn!("🐢 Rendering over budget, dropping {}", effect.name());
+                }
+            }
+            Some(QualityAction::Restore) => {
+                if let Some(effect) = self.quality.pop_shed() {
+                    self.set_quality_effect(effect, true);
+                    info!("🐇 Rendering has headroom again, restoring {}", effect.name());
+                }
+            }
+            None => {}
+        }
+    }
+
+    // Whether an optional effect is currently doing work (only those are worth dropping)
+    fn quality_effect_running(&self, effect: QualityEffect) -> bool {
+        match effect {
+            QualityEffect::Bloom => self.bloom.is_some(),
+            QualityEffect::EyeGlow => self.eye_glow.is_some(),
+            QualityEffect::Spectrum => self.registry.is_shown("Spectrum Bars"),
+            QualityEffect::Fire => self.registry.is_shown("Fire"),
+        }
+    }
+
+    // Elements are switched off in the registry; bloom and eye glow are left out of the
+    // frames handed to the renderer while dropped
+    fn set_quality_effect(&mut self, effect: QualityEffect, enabled: bool) {
+        match effect {
+            QualityEffect::Spectrum => {
+                self.registry.set_enabled("Spectrum Bars", enabled);
+            }
+            QualityEffect::Fire => {
+                self.registry.set_enabled("Fire", enabled);
+            }
+            QualityEffect::Bloom | QualityEffect::EyeGlow => {}
+        }
+    }
+
+    /// Set how long the blep stays out after a button press (0 = until pressed again)
+    pub fn set_blep_auto_retract(&mut self, secs: f64) {
+        let auto_retract = if secs > 0.0 { Some(secs) } else { None };
+        if let Some(blep) = self.get_element_mut::<elements::BlepAccessory>("Blep") {
+            blep.set_auto_retract(auto_retract);
+        }
+    }
+
+    /// Set the rainbow background brightness (fraction of full, 0.0 = off)
+    pub fn set_background_brightness(&mut self, brightness: f64) {
+        if let Some(background) = self.get_element_mut::<elements::RainbowCycleBackground>(
+            "Rainbow Background") {
+            background.set_brightness(brightness);
+        }
+    }
+
+    /// Set how many pixels the eyes move for a look pose (Select + D-pad)
+    pub fn set_eye_pose_distance(&mut self, pixels: f64) {
+        self.eye_pose_distance = pixels.max(0.0);
+    }
+
+    /// Pixels the mouth corner moves for a full smile or frown override
+    pub fn set_mouth_emotion_curve(&mut self, pixels: f64) {
+        self.mouth_emotion_curve = pixels.max(0.0);
+    }
+
+    /// How manual mouth mode combines the trigger with the microphone (`weight` is the
+    /// trigger's share for MouthBlend::Mix)
+    pub fn set_mouth_blend(&mut self, blend: MouthBlend, weight: f64) {
+        self.mouth_blend = blend;
+        self.mouth_blend_weight = weight.clamp(0.0, 1.0);
+    }
+
+    /// Open each panel's mouth with its own channel of a stereo microphone (a mono one
+    /// keeps both sides the same)
+    pub fn set_stereo_mouth(&mut self, enabled: bool) {
+        self.stereo_mouth = enabled;
+    }
+
+    /// Let the nose grow slightly as the mouth opens
+    pub fn set_nose_breathing(&mut self, enabled: bool) {
+        if let Some(nose) = self.get_element_mut::<elements::DefaultNose>("Default Nose") {
+            nose.set_breathing(enabled);
+        }
+    }
+
+    /// Set the spectrum bar count and the row the bars stand on
+    pub fn set_spectrum(&mut self, bands: usize, base_y: i32) {
+        if let Some(spectrum) = self.get_element_mut::<elements::SpectrumBarsAccessory>("Spectrum Bars") {
+            spectrum.set_bands(bands);
+            spectrum.set_base_y(base_y);
+        }
+    }
+
+    /// Set the brightness pulse: swing around the set brightness (0.0 = steady) and rate in Hz
+    /// While breathing the swing is at least BREATHING_OSCILLATION_AMPLITUDE
+    pub fn set_brightness_oscillation(&mut self, amplitude: f64, hz: f64) {
+        self.oscillation_amplitude = amplitude.max(0.0);
+        self.oscillation_hz = hz.max(0.0);
+    }
+
+    /// Set how elements spread the palette over the face
+    pub fn set_shimmer_mode(&mut self, mode: ShimmerMode) {
+        self.shimmer_mode = mode;
+    }
+
+    /// Turn edge smoothing on or off (off is a little faster on slow boards)
+    pub fn set_anti_aliasing(&mut self, enabled: bool) {
+        self.anti_aliasing = enabled;
+    }
+
+    /// Set the channel order of the panels, for panels with swapped colors
+    pub fn set_color_order(&mut self, order: ColorOrder) {
+        self.color_order = order;
+    }
+
+    /// Set how far each rendered frame moves animations on, in seconds (1 / target FPS)
+    pub fn set_frame_secs(&mut self, frame_secs: f64) {
+        self.frame_secs = frame_secs;
+    }
+
+    /// Set the glitch effect strength (0.0-1.0) and average bursts per second
+    pub fn set_glitch(&mut self, intensity: f64, frequency: f64) {
+        self.glitch = (intensity, frequency);
+    }
+
+    /// Turn the bloom glow on or off, with its reach in pixels and strength (0.0-1.0)
+    pub fn set_bloom(&mut self, enabled: bool, radius: i32, intensity: f64) {
+        self.bloom = enabled.then_some((radius, intensity));
+    }
+
+    /// Turn the halo around the eyes on or off, with its reach in pixels and strength (0.0-1.0)
+    pub fn set_eye_glow(&mut self, enabled: bool, radius: i32, intensity: f64) {
+        self.eye_glow = enabled.then_some((radius, intensity));
+    }
+
+    /// Brightness change per second when the face fades out or in (0 = cut), see
+    /// `FaceRenderer::fade_out`
+    pub fn set_fade_speed(&mut self, speed: f64) {
+        self.fade_speed = speed.max(0.0);
+    }
+
+    /// Set how long the matrix rain runs once triggered
+    pub fn set_matrix_rain_duration(&mut self, secs: f64) {
+        if let Some(rain) = self.get_element_mut::<MatrixRainEffect>("Matrix Rain") {
+            rain.set_duration(secs);
+        }
+    }
+
+    /// Choose smoothed or raw samples for the waveform display
+    pub fn set_waveform_smoothed(&mut self, smoothed: bool) {
+        if let Some(waveform) = self.get_element_mut::<WaveformDisplay>("Waveform") {
+            waveform.set_smoothed(smoothed);
+        }
+    }
+
+    /// Set how fast color burst rings grow (pixels per second) and how thick they are
+    pub fn set_burst_rings(&mut self, speed: f64, width: f64) {
+        if let Some(burst) = self.get_element_mut::<ColorBurstEffect>("Color Burst") {
+            burst.set_ring(speed, width);
+        }
+    }
+
+    /// Shift the mirrored half by this many columns (see `PixelDrawer`)
+    pub fn set_mirror_offset(&mut self, offset: i32) {
+        self.pixel_drawer.mirror_offset = offset;
+    }
+
+    /// Bias the right panel's brightness (multiplier) and palette position relative to the left
+    pub fn set_right_panel_bias(&mut self, brightness: f64, color_shift: f64) {
+        self.pixel_drawer.right_brightness = brightness.max(0.0);
+        self.pixel_drawer.right_color_shift = color_shift;
+    }
+
+    /// Set the lowest value a lit pixel's brightest channel may drop to (0 = no floor)
+    pub fn set_min_pixel_brightness(&mut self, value: u8) {
+        self.pixel_drawer.min_pixel_value = value;
+    }
+
+    /// Report this CPU temperature in the telemetry
+    pub fn set_cpu_temperature(&mut self, temperature: CpuTemperature) {
+        self.cpu_temperature = Some(temperature);
+    }
+
+    /// Let the heart rate monitor's readings drive the expression (and the status line)
+    pub fn set_heart_rate(&mut self, heart_rate: HeartRate) {
+        self.heart_rate = Some(heart_rate);
+    }
+
+    /// Set the heart rates that count as calm and as fully excited
+    pub fn set_heart_rate_range(&mut self, rest_bpm: u32, excited_bpm: u32) {
+        self.heart_rate_range = (rest_bpm, excited_bpm);
+    }
+
+    /// Set how often a new idle animation is picked while in breathing mode
+    pub fn set_idle_animation_interval(&mut self, secs: f64) {
+        self.idle_scheduler.set_interval(secs);
+    }
+
+    /// Enable or disable the element bounds debug overlay
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        self.debug_overlay = enabled;
+    }
+
+    /// Handle to the frames this face publishes, for a `FaceRenderer` (cheap to clone)
+    pub fn frames(&self) -> FaceFrames {
+        self.frames.clone()
+    }
+
+    /// Renderer for this face's frames, sharing its metrics
+    pub fn renderer(&self) -> FaceRenderer {
+        FaceRenderer::new(self.frames(), self.metrics.clone())
+    }
+
+    /// Advance the face by one frame and publish it for the renderer (see `FaceFrames`)
+    ///
+    /// Reads the controls, plays emotes, updates every element and the shared state, then
+    /// hands copies of what is to be drawn to `frames()`. Nothing is drawn here: the update
+    /// thread calls this at the target frame rate while a `FaceRenderer` draws the latest
+    /// frame on the render thread.
+    pub fn update(&mut self) {
+        self.time_counter += 1.0;
+
+        // Emotes first, so their eye and palette changes show from this frame
+        let (emote_slot, wink_requested) = {
+            let mut state = self.state.lock().unwrap();
+            (state.emote_requested.take(), std::mem::take(&mut state.wink_requested))
+        };
+        if let Some(slot) = emote_slot {
+            match self.emotes.get(slot).cloned() {
+                Some(emote) => self.emote_queue.push(emote),
+                None => warn!("⚠️  No emote in slot {}", slot + 1),
+            }
+        }
+        if wink_requested {
+            self.queue_emote(WINK_EMOTE);
+        }
+        self.update_emotes(self.frame_secs);
+
+        // Get mask state
+        let mut state = self.state.lock().unwrap();
+        self.shared_state.blink_enabled = state.blink_enabled;
+        let brightness = state.brightness;
+        let palette = state.color_palette;
+        let manual_mouth_mode = state.manual_mouth_mode;
+        let mouth_analog_value = state.mouth_analog_value;
+        let glitch_enabled = state.glitch_enabled;
+        let invert = state.invert;
+        let night_vision = state.night_vision;
+        let strobe_hz = state.strobe_hz;
+        // Lava-lamp drift: wrap at one palette cycle so the phase never loses precision
+        self.pixel_drawer.palette_phase = (self.pixel_drawer.palette_phase
+            + state.palette_shift_speed * self.frame_secs).rem_euclid(PALETTE_CYCLE);
+        let smile_intensity = state.smile_intensity;
+        // Ease toward the look pose, then add the stick on top (whole pixels, see with_gaze)
+        let (pose_x, pose_y) = state.eye_pose.direction();
+        let target = (pose_x * self.eye_pose_distance, pose_y * self.eye_pose_distance);
+        let keep = 1.0 - (POSE_EASE_RATE * self.frame_secs).min(1.0);
+        self.pose_offset = (target.0 + (self.pose_offset.0 - target.0) * keep,
+                            target.1 + (self.pose_offset.1 - target.1) * keep);
+        self.shared_state.gaze_offset = ((state.gaze_x * GAZE_RANGE + self.pose_offset.0).round(),
+                                         (state.gaze_y * GAZE_RANGE + self.pose_offset.1).round());
+        // Ease into the smile/frown override so the corner doesn't snap
+        // (a smirking emote holds a full smile on its side)
+        let (wink, smirk) = self.emote_sides;
+        self.shared_state.wink = wink;
+        self.shared_state.smirk = smirk;
+        // A racing heart adds a smile on top of the held expression, fading back at rest
+        if let Some(heart_rate) = &self.heart_rate {
+            let (rest_bpm, excited_bpm) = self.heart_rate_range;
+            let target = excitement(*heart_rate.lock().unwrap(), rest_bpm, excited_bpm);
+            let keep = 1.0 - (HEART_RATE_EASE_RATE * self.frame_secs).min(1.0);
+            self.shared_state.emotion_intensity = target
+                + (self.shared_state.emotion_intensity - target) * keep;
+        }
+        let emotion = if smirk.is_some() {
+            1.0
+        } else {
+            (state.mouth_emotion + self.shared_state.emotion_intensity).clamp(-1.0, 1.0)
+        };
+        let target = emotion * self.mouth_emotion_curve;
+        let keep = 1.0 - (MOUTH_EMOTION_EASE_RATE * self.frame_secs).min(1.0);
+        self.shared_state.mouth_curve = target + (self.shared_state.mouth_curve - target) * keep;
+        let start_matrix_rain = std::mem::take(&mut state.matrix_rain_requested);
+        let mode = FaceMode::from_inputs(manual_mouth_mode, state.mic_muted,
+                                         self.audio_level.seconds_since_audio());
+        let audio_idle = FaceMode::from_inputs(false, state.mic_muted,
+                                               self.audio_level.seconds_since_audio())
+            == FaceMode::Breathing;
+
+        // Blended manual mode keeps the mouths following the microphone under the trigger
+        self.shared_state.manual_mouth_active = manual_mouth_mode
+            && self.mouth_blend == MouthBlend::Override;
+        self.shared_state.idle_breathing = mode == FaceMode::Breathing;
+        drop(state);
+
+        if start_matrix_rain {
+            if let Some(rain) = self.get_element_mut::<MatrixRainEffect>("Matrix Rain") {
+                rain.start();
+            }
+        }
+
+        // Swap idle animations while breathing, hide them once audio resumes
+        let idle = mode == FaceMode::Breathing;
+        if self.idle_scheduler.update(idle, self.frame_secs) {
+            let active = self.idle_scheduler.active();
+            for animation in self.idle_scheduler.animations() {
+                let enabled = active == Some(animation.element_name.as_str());
+                self.registry.set_enabled(&animation.element_name, enabled);
+            }
+            if let Some(name) = active {
+                info!("✨ Idle animation: {}", name);
+            }
+        }
+
+        // Pulse the brightness for a glow at rest
+        self.oscillation_phase = (self.oscillation_phase
+            + std::f64::consts::TAU * self.oscillation_hz * self.frame_secs)
+            .rem_euclid(std::f64::consts::TAU);
+        let amplitude = if idle {
+            self.oscillation_amplitude.max(BREATHING_OSCILLATION_AMPLITUDE)
+        } else {
+            self.oscillation_amplitude
+        };
+        let render_brightness = if amplitude > 0.0 {
+            (brightness + amplitude * self.oscillation_phase.sin())
+                .clamp(MIN_OSCILLATION_BRIGHTNESS, 1.0)
+        } else {
+            brightness
+        };
+
+        // Create render context (the renderer applies its fades to the brightness)
+        let (left_audio, right_audio) = self.audio_level.get_channel_levels();
+        let mut context = RenderContext {
+            offset_x: 0.0,
+            offset_y: 0.0,
+            time_counter: self.time_counter,
+            brightness: render_brightness,
+            palette,
+            smile_intensity,
+            shimmer_mode: self.shimmer_mode,
+            anti_aliasing: self.anti_aliasing,
+            left_audio,
+            right_audio,
+            user_data: HashMap::new(),
+        };
+
+        // Update all elements
+        self.registry.update_all(&mut self.shared_state, &mut context, self.frame_secs);
+
+        // Apply manual mouth control, blended with the audio-driven opening
+        // (silence leaves only the trigger, no idle breathing under it)
+        if manual_mouth_mode {
+            let audio = if audio_idle { 0.0 } else { self.shared_state.mouth_opening };
+            self.shared_state.mouth_opening = self.mouth_blend.combine(
+                audio, mouth_analog_value * MOUTH_MAX_OPENING, self.mouth_blend_weight);
+        }
+
+        // Stereo mouth: scale each panel's opening by its channel against the louder one
+        // (the trigger and idle breathing stay symmetric)
+        self.shared_state.stereo_mouth = if self.stereo_mouth && !manual_mouth_mode && !audio_idle {
+            let opening = self.shared_state.mouth_opening;
+            stereo_balance(context.left_audio, context.right_audio)
+                .map(|(left, right)| (opening * left, opening * right))
+        } else {
+            None
+        };
+
+        // Recover from a non-finite value (a bad audio sample, a division gone wrong)
+        // before it reaches the drawing math, where it would garble the face for good
+        let mut reset = self.shared_state.reset_non_finite();
+        if !self.pose_offset.0.is_finite() || !self.pose_offset.1.is_finite() {
+            self.pose_offset = (0.0, 0.0);
+            reset.push("pose_offset");
+        }
+        if !reset.is_empty() {
+            warn!("⚠️  Non-finite face state reset to defaults: {}", reset.join(", "));
+        }
+
+        // Adaptive quality follows the renderer's time for its latest frame
+        let render_secs = self.metrics.render_ms.get() / 1000.0;
+        self.update_quality(render_secs);
+        let shed = |effect| self.quality.is_shed(effect);
+        let effects = PostEffects {
+            glitch: glitch_enabled.then_some(self.glitch),
+            bloom: self.bloom.filter(|_| !shed(QualityEffect::Bloom)),
+            eye_glow: self.eye_glow.filter(|_| !shed(QualityEffect::EyeGlow)),
+            invert,
+            night_vision,
+            strobe_hz,
+            fade_speed: self.fade_speed,
+            color_order: self.color_order,
+            debug_overlay: self.debug_overlay,
+        };
+        self.frames.publish(FaceFrame {
+            shared_state: self.shared_state.clone(),
+            context,
+            elements: self.registry.frame_elements(),
+            fading_mouth: self.registry.fading_mouth(),
+            eye_offset: self.registry.eye_offset,
+            pixel_drawer: self.pixel_drawer.clone(),
+            effects,
+        });
+
+        // Update metrics
+        self.metrics.audio_level.set(self.audio_level.get_level());
+        self.metrics.mouth_opening.set(self.shared_state.mouth_opening);
+        self.metrics.brightness.set(brightness);
+    }
+
+    /// Add an element at runtime (e.g. from the HTTP API)
+    pub fn add_element(&mut self, element: Box<dyn FaceElement>) {
+        self.registry.register(element);
+    }
+
+    /// Whether a full-screen effect (matrix rain, waveform) is currently hiding the face
+    pub fn has_exclusive_element(&self) -> bool {
+        self.registry.has_exclusive_element()
+    }
+
+    /// Remove an element by name, returns true if it existed
+    pub fn remove_element(&mut self, name: &str) -> bool {
+        self.registry.unregister(name).is_some()
+    }
+
+    /// Look up an element by name as its concrete type (see `FaceElementRegistry::get_element`)
+    pub fn get_element<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.registry.get_element(name)
+    }
+
+    /// Look up an element by name as its concrete type for calling element-specific methods
+    pub fn get_element_mut<T: 'static>(&mut self, name: &str) -> Option<&mut T> {
+        self.registry.get_element_mut(name)
+    }
+
+    /// Show or hide an element without removing it, returns true if it exists
+    pub fn set_element_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        self.registry.set_enabled(name, enabled)
+    }
+
+    pub fn get_active_eyes_name(&self) -> String {
+        self.registry.get_active_eyes_name()
+    }
+
+    /// Blink the default eyes now, returns false if another eye style is showing
+    pub fn trigger_blink(&mut self) -> bool {
+        if self.registry.get_active_eyes_name() != "Default Eyes" {
+            return false;
+        }
+        match self.registry.get_element_mut::<elements::eyes::DefaultEyes>("Default Eyes") {
+            Some(eyes) => {
+                eyes.trigger_blink();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names of every eye style, in cycling order (any of them can go to `set_active_eyes`)
+    pub fn list_eyes(&self) -> Vec<String> {
+        self.registry.list_eyes()
+    }
+
+    /// Switch to the eye style with the given name, returns false if it doesn't exist
+    pub fn set_active_eyes(&mut self, name: &str) -> bool {
+        let found = self.registry.set_active_eyes(name);
+        if found {
+            info!("👁️  Eyes: {}", name);
+        }
+        found
+    }
+
+    pub fn get_mouth_opening(&self) -> f64 {
+        self.shared_state.mouth_opening
+    }
+
+    pub fn get_active_mouth_name(&self) -> String {
+        self.registry.get_active_mouth_name()
+    }
+
+    /// Top and bottom eyelid positions (9.0 and 1.45 with the eyes open, 0.1 and 7.0 at
+    /// the shut point of a blink or wink)
+    pub fn eyelids(&self) -> (f64, f64) {
+        (self.shared_state.eye_top, self.shared_state.eye_bottom)
+    }
+
+    /// Name, category, state and settings of every registered element
+    pub fn describe_elements(&self) -> Vec<ElementDescription> {
+        self.registry.describe()
+    }
+
+    /// Accessory elements currently shown (sparkles, blep, overlays...)
+    pub fn shown_accessories(&self) -> Vec<String> {
+        self.registry.shown_accessories()
+    }
+
+    /// Names of every mouth style, in cycling order (any of them can go to `set_active_mouth`)
+    pub fn list_mouths(&self) -> Vec<String> {
+        self.registry.list_mouths()
+    }
+
+    /// Switch to the mouth style with the given name, returns false if it doesn't exist
+    pub fn set_active_mouth(&mut self, name: &str) -> bool {
+        let found = self.registry.set_active_mouth(name);
+        if found {
+            info!("👄 Mouth: {}", name);
+        }
+        found
+    }
+
+    /// Snapshot of the current face state for status output
+    pub fn telemetry(&self) -> FaceTelemetry {
+        let state = self.state.lock().unwrap();
+        let seconds_since_audio = self.audio_level.seconds_since_audio();
+        FaceTelemetry {
+            mode: FaceMode::from_inputs(state.manual_mouth_mode, state.mic_muted,
+                                        seconds_since_audio),
+            audio_level: self.audio_level.get_level(),
+            seconds_since_audio,
+            brightness: state.brightness,
+            palette: state.color_palette,
+            mouth_opening: self.shared_state.mouth_opening,
+            active_eyes: self.registry.get_active_eyes_name(),
+            fps: self.metrics.fps.get(),
+            cpu_temp: self.cpu_temperature.as_ref()
+                .map(|temperature| *temperature.lock().unwrap())
+                .filter(|celsius| !celsius.is_nan()),
+            heart_rate: self.heart_rate.as_ref().map(|bpm| *bpm.lock().unwrap()),
+            rng_seed: rng::seed(),
+            exclusive_element: self.registry.exclusive_element().map(|e| e.name().to_string()),
+            reduced_effects: self.quality.shed_effects().iter()
+                .map(|effect| effect.name().to_string())
+                .collect(),
+            profile: self.profiles.active().map(str::to_string),
+        }
+    }
+}
+
+// Implement CycleEyes trait for gamepad controls
+impl CycleEyes for ProtogenFace {
+    fn cycle_eyes_forward(&mut self) {
+        self.registry.cycle_eyes_forward();
+        let eyes_name = self.registry.get_active_eyes_name();
+        info!("👁️  Eyes: {} (→)", eyes_name);
+    }
+
+    fn cycle_eyes_backward(&mut self) {
+        self.registry.cycle_eyes_backward();
+        let eyes_name = self.registry.get_active_eyes_name();
+        info!("👁️  Eyes: {} (←)", eyes_name);
+    }
+
+    fn cycle_mouth(&mut self) {
+        self.registry.cycle_mouth();
+        info!("👄 Mouth: {}", self.registry.get_active_mouth_name());
+    }
+
+    fn handle_element_button(&mut self, button: Button) -> bool {
+        self.registry.handle_button(button, &mut self.shared_state)
+    }
+
+    fn save_snapshot(&mut self, slot: &str) {
+        match self.snapshot(slot) {
+            Ok(()) => info!("📸 Saved snapshot \"{}\"", slot),
+            Err(e) => warn!("⚠️  Could not save snapshot \"{}\": {}", slot, e),
+        }
+    }
+
+    fn recall_snapshot(&mut self, slot: &str) {
+        if self.recall(slot) {
+            info!("📸 Recalled snapshot \"{}\"", slot);
+        } else {
+            warn!("⚠️  No snapshot \"{}\" to recall (saved: {})", slot,
+                  self.snapshots.slots().join(", "));
+        }
+    }
+
+    fn switch_profile_slot(&mut self, slot: usize) {
+        match self.profiles.get(slot).map(|profile| profile.name.clone()) {
+            Some(name) => {
+                self.switch_profile(&name);
+                info!("🗂️  Profile: {}", name);
+            }
+            None => warn!("⚠️  No profile {} in {} (loaded: {})", slot + 1, PROFILES_PATH,
+                          self.profiles.names().join(", ")),
+        }
+    }
+}
+
+// ============================================================================
+// FACE FRAMES AND RENDERER
+// ============================================================================
+
+// Longest step one rendered frame moves the renderer's own effects on by, so a pause in
+// face rendering (video, standby) doesn't jump a fade or strobe
+const MAX_RENDER_STEP_SECS: f64 = 0.1;
+
+/// One update's worth of face, with everything needed to draw it
+///
+/// Built by `ProtogenFace::update`. The elements are copies taken at the end of the
+/// update, so drawing never touches the registry the update thread goes on changing.
+pub struct FaceFrame {
+    shared_state: SharedFaceState,
+    context: RenderContext,
+    elements: Vec<Box<dyn FaceElement>>,  // Active elements in render order
+    fading_mouth: Option<(Box<dyn FaceElement>, f64)>,  // Previous mouth and fade progress
+    eye_offset: (f64, f64),  // Eye slide-in offset after switching variants
+    pixel_drawer: PixelDrawer,
+    effects: PostEffects,
+}
+
+// Full-frame effects the renderer applies over the elements, with their settings
+struct PostEffects {
+    glitch: Option<(f64, f64)>,     // Intensity and bursts per second, None when off
+    bloom: Option<(i32, f64)>,      // Radius and intensity, None when off or dropped for speed
+    eye_glow: Option<(i32, f64)>,   // Same for the halo around the eyes
+    invert: bool,
+    night_vision: bool,
+    strobe_hz: Option<f64>,
+    fade_speed: f64,                // Of the whole-face fade
+    color_order: ColorOrder,
+    debug_overlay: bool,
+}
+
+impl FaceFrame {
+    // Render context for eye elements, shifted by the slide offset while a switch settles
+    // and by the joystick gaze
+    fn eye_context(&self, context: &RenderContext) -> Option<RenderContext> {
+        let gaze = self.shared_state.gaze_offset;
+        let offset = (self.eye_offset.0 + gaze.0, self.eye_offset.1 + gaze.1);
+        if offset == (0.0, 0.0) {
+            return None;
+        }
+        Some(RenderContext {
+            offset_x: context.offset_x + offset.0,
+            offset_y: context.offset_y + offset.1,
+            ..context.clone()
+        })
+    }
+
+    // Draw the active mouth, cross-faded with the previous one while a switch settles
+    // Both render into their own layer so overlapping pixels blend instead of overwriting
+    fn render_mouth(&self, element: &dyn FaceElement, canvas: &mut VirtualLedCanvas,
+                    context: &RenderContext, shared_state: &SharedFaceState,
+                    draw_pixel_fn: &dyn DrawPixelFn) {
+        let Some((from, progress)) = &self.fading_mouth else {
+            element.render(canvas, context, shared_state, draw_pixel_fn);
+            return;
+        };
+
+        let mut from_layer = VirtualLedCanvas::new(canvas.width(), canvas.height());
+        let mut to_layer = VirtualLedCanvas::new(canvas.width(), canvas.height());
+        from.render(&mut from_layer, context, shared_state, draw_pixel_fn);
+        element.render(&mut to_layer, context, shared_state, draw_pixel_fn);
+        canvas.blend_layers(&from_layer, &to_layer, *progress);
+    }
+
+    // Eyes draw through `eye_draw_pixel_fn` (see PixelDrawer::with_gaze), everything else
+    // through `draw_pixel_fn`
+    fn render_all(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+                  shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn,
+                  eye_draw_pixel_fn: &dyn DrawPixelFn) {
+        let eye_context = self.eye_context(context);
+        for element in &self.elements {
+            let (context, draw_pixel_fn) = match element.category() {
+                ElementCategory::Eyes => (eye_context.as_ref().unwrap_or(context), eye_draw_pixel_fn),
+                _ => (context, draw_pixel_fn),
+            };
+            if element.category() == ElementCategory::Mouth {
+                self.render_mouth(element.as_ref(), canvas, context, shared_state, draw_pixel_fn);
+            } else {
+                element.render(canvas, context, shared_state, draw_pixel_fn);
+            }
+        }
+    }
+
+    // Render only the active eyes, for effects that work on the eye pixels alone (eye glow)
+    fn render_eyes(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+                   shared_state: &SharedFaceState, eye_draw_pixel_fn: &dyn DrawPixelFn) {
+        let eye_context = self.eye_context(context);
+        for element in &self.elements {
+            if element.category() == ElementCategory::Eyes {
+                element.render(canvas, eye_context.as_ref().unwrap_or(context), shared_state,
+                               eye_draw_pixel_fn);
+            }
+        }
+    }
+
+    // Render like render_all, recording the drawn bounds of each element
+    fn render_all_with_bounds(&self, canvas: &mut VirtualLedCanvas, context: &RenderContext,
+                              shared_state: &SharedFaceState, draw_pixel_fn: &dyn DrawPixelFn,
+                              eye_draw_pixel_fn: &dyn DrawPixelFn)
+                              -> Vec<(String, Option<ElementBounds>)> {
+        let mut bounds = Vec::new();
+        let eye_context = self.eye_context(context);
+        for element in &self.elements {
+            let (context, draw_pixel_fn) = match element.category() {
+                ElementCategory::Eyes => (eye_context.as_ref().unwrap_or(context), eye_draw_pixel_fn),
+                _ => (context, draw_pixel_fn),
+            };
+            let recorder = BoundsRecorder::new(draw_pixel_fn);
+            if element.category() == ElementCategory::Mouth {
+                self.render_mouth(element.as_ref(), canvas, context, shared_state, &recorder);
+            } else {
+                element.render(canvas, context, shared_state, &recorder);
+            }
+            bounds.push((element.name().to_string(), recorder.bounds()));
+        }
+        bounds
+    }
+}
+
+/// Double buffer between the update thread and the render thread
+///
+/// `ProtogenFace::update` builds each frame on its own (the back buffer) and `publish`
+/// swaps it in as the front one; the renderer takes the front frame with `latest` and
+/// holds on to it while it draws. The lock only covers swapping an `Arc`, never an update
+/// or a draw, so neither thread waits on the other's work. A renderer faster than the
+/// updates draws the same frame again (its fades and effects still move on with real
+/// time); a slower one skips to the newest frame instead of falling behind.
+#[derive(Clone, Default)]
+pub struct FaceFrames {
+    front: Arc<Mutex<Option<Arc<FaceFrame>>>>,
+}
+
+impl FaceFrames {
+    /// Make `frame` the one the renderer draws next
+    pub fn publish(&self, frame: FaceFrame) {
+        // The replaced frame is freed here, after the lock is released (or by the
+        // renderer once it is done drawing it)
+        let _previous = self.front.lock().unwrap().replace(Arc::new(frame));
+    }
+
+    /// The most recently published frame, None before the first update
+    pub fn latest(&self) -> Option<Arc<FaceFrame>> {
+        self.front.lock().unwrap().clone()
+    }
+}
+
+/// Draws published face frames: the elements, then the full-frame effects, render hooks
+/// and debug overlay
+///
+/// Owned by the render thread. It only changes its own effect state (glitch timing, fades,
+/// glow buffers), so it can draw as often as the panels take frames while the face
+/// updates at the target frame rate.
+pub struct FaceRenderer {
+    frames: FaceFrames,
+    metrics: Arc<FaceMetrics>,
+    last_render: Option<Instant>,
+    framebuffer: VirtualLedCanvas,  // Rendered face, copied to the matrix each frame
+    color_order: ColorOrder,        // Of the latest frame, for the copy to the matrix
+    glitch: GlitchEffect,           // Settings come with each frame
+    bloom: Option<BloomEffect>,     // Soft glow pass, kept while the frames ask for it
+    eye_glow: Option<BloomEffect>,  // Halo around the eyes only
+    eye_layer: VirtualLedCanvas,    // The eyes drawn alone, the eye glow's source
+    strobe: StrobeEffect,
+    fade: FadeTransition,           // Whole-face fade for switching to video or standby
+    wake_fade: f64,                 // Brightness multiplier while fading up out of standby
+    render_hooks: Vec<RenderHook>,  // Custom drawing on top of the finished frame
+}
+
+impl FaceRenderer {
+    pub fn new(frames: FaceFrames, metrics: Arc<FaceMetrics>) -> Self {
+        Self {
+            frames,
+            metrics,
+            last_render: None,
+            framebuffer: VirtualLedCanvas::new(PANEL_WIDTH * 2, PANEL_HEIGHT),
+            color_order: ColorOrder::Rgb,
+            glitch: GlitchEffect::new(0.0, 0.0),
+            bloom: None,
+            eye_glow: None,
+            eye_layer: VirtualLedCanvas::new(PANEL_WIDTH * 2, PANEL_HEIGHT),
+            strobe: StrobeEffect::new(),
+            fade: FadeTransition::new(Config::default().fade_speed),
+            wake_fade: 1.0,
+            render_hooks: Vec::new(),
+        }
+    }
+
+    /// Run custom drawing every frame, after the elements and post-processing effects
+    /// (only the debug overlay goes on top). A quick alternative to a full `FaceElement`:
+    ///
+    /// ```ignore
+    /// renderer.add_render_hook(|canvas, context| {
+    ///     let color = get_shimmer_color(context.time_counter, 255.0 * context.brightness,
+    ///                                   context.palette);
+    ///     canvas.set(0, 0, &color);
+    /// });
+    /// ```
+    pub fn add_render_hook<F>(&mut self, hook: F)
+    where
+        F: FnMut(&mut VirtualLedCanvas, &RenderContext) + Send + 'static,
+    {
+        self.render_hooks.push(Box::new(hook));
+    }
+
+    /// Scale the face brightness (0.0 to 1.0) while fading up out of standby
+    pub fn set_wake_fade(&mut self, fade: f64) {
+        self.wake_fade = fade.clamp(0.0, 1.0);
+    }
+
+    /// Fade the whole face to black over the next frames (for video or standby)
+    pub fn fade_out(&mut self) {
+        self.fade.fade_out();
+    }
+
+    /// Fade the face back up to full brightness
+    pub fn fade_in(&mut self) {
+        self.fade.fade_in();
+    }
+
+    /// Whether a fade out has finished (the face renders black)
+    pub fn is_faded_out(&self) -> bool {
+        self.fade.is_faded_out()
+    }
+
+    /// Seconds a full fade out or in takes
+    pub fn fade_secs(&self) -> f64 {
+        self.fade.duration()
+    }
+
+    /// Draw the latest frame and copy it to the LED matrix canvas
+    pub fn render(&mut self, canvas: &mut LedCanvas) {
+        let mut framebuffer = std::mem::replace(&mut self.framebuffer, VirtualLedCanvas::new(0, 0));
+        self.render_to_virtual(&mut framebuffer);
+        framebuffer.blit_to(canvas, self.color_order);
+        self.framebuffer = framebuffer;
+    }
+
+    /// Draw the latest frame into an in-memory canvas (no hardware needed), black until
+    /// the first one is published
+    pub fn render_to_virtual(&mut self, canvas: &mut VirtualLedCanvas) {
+        let render_start = Instant::now();
+        let mut dt = 0.0;
+        if let Some(last) = self.last_render {
+            let frame_secs = render_start.duration_since(last).as_secs_f64();
+            if frame_secs > 0.0 {
+                self.metrics.fps.set(1.0 / frame_secs);
+            }
+            dt = frame_secs.min(MAX_RENDER_STEP_SECS);
+        }
+        self.last_render = Some(render_start);
+
+        canvas.clear();
+        let Some(frame) = self.frames.latest() else {
+            return;
+        };
+        let effects = &frame.effects;
+        self.color_order = effects.color_order;
+        self.fade.speed = effects.fade_speed;
+        let fade = self.fade.update(dt);
+        let context = RenderContext {
+            brightness: frame.context.brightness * self.wake_fade * fade,
+            ..frame.context.clone()
+        };
+
+        // Render all elements, then full-frame post-processing (the debug overlay stays on top)
+        // A wink, smirk or stereo mouth draws each panel on its own with that panel's view
+        // of the state
+        let eye_drawer = frame.pixel_drawer.with_gaze(frame.shared_state.gaze_offset.0);
+        let sides = frame.shared_state.render_sides();
+        // (the overlay outlines what each panel drew, so a split frame keeps both halves)
+        let mut bounds = Vec::new();
+        for &side in &sides {
+            let shared_state = frame.shared_state.for_side(side);
+            let drawer = frame.pixel_drawer.for_side(side);
+            let eye_drawer = eye_drawer.for_side(side);
+            if effects.debug_overlay {
+                bounds.extend(frame.render_all_with_bounds(canvas, &context, &shared_state,
+                                                           &drawer, &eye_drawer));
+            } else {
+                frame.render_all(canvas, &context, &shared_state, &drawer, &eye_drawer);
+            }
+        }
+        sync_glow(&mut self.eye_glow, effects.eye_glow);
+        if let Some(eye_glow) = self.eye_glow.as_mut() {
+            self.eye_layer.clear();
+            for &side in &sides {
+                frame.render_eyes(&mut self.eye_layer, &context, &frame.shared_state.for_side(side),
+                                  &eye_drawer.for_side(side));
+            }
+            eye_glow.apply_from(&self.eye_layer, canvas);
+        }
+        if let Some((intensity, frequency)) = effects.glitch {
+            self.glitch.set_intensity(intensity);
+            self.glitch.set_frequency(frequency);
+            self.glitch.apply(canvas, dt);
+        }
+        if effects.invert {
+            InvertEffect::apply(canvas);
+        }
+        if effects.night_vision {
+            NightVisionEffect::apply(canvas);
+        }
+        sync_glow(&mut self.bloom, effects.bloom);
+        if let Some(bloom) = self.bloom.as_mut() {
+            bloom.apply(canvas);
+        }
+        self.strobe.apply(canvas, effects.strobe_hz, dt);
+        for hook in self.render_hooks.iter_mut() {
+            hook(canvas, &context);
+        }
+        if effects.debug_overlay {
+            DebugOverlay::draw(canvas, &bounds);
+        }
+
+        self.metrics.frame_count.inc();
+        self.metrics.render_ms.set(render_start.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+// Bring a glow pass in line with the frame's settings, keeping its buffer while it stays on
+fn sync_glow(glow: &mut Option<BloomEffect>, settings: Option<(i32, f64)>) {
+    match (glow.as_mut(), settings) {
+        (Some(glow), Some((radius, intensity))) => {
+            glow.set_radius(radius);
+            glow.set_intensity(intensity);
+        }
+        (None, Some((radius, intensity))) => *glow = Some(BloomEffect::new(radius, intensity)),
+        (_, None) => *glow = None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resting_state() -> SharedFaceState {
+        SharedFaceState {
+            mouth_opening: 0.0,
+            eye_top: OPEN_EYE_TOP,
+            eye_bottom: OPEN_EYE_BOTTOM,
+            blink_enabled: true,
+            manual_mouth_active: false,
+            idle_breathing: false,
+            gaze_offset: (0.0, 0.0),
+            mouth_curve: 0.0,
+            wink: None,
+            smirk: None,
+            emotion_intensity: 0.0,
+            stereo_mouth: None,
+        }
+    }
+
+    // Accessory that draws nothing, with a chosen layer and takeover flag
+    #[derive(Clone)]
+    struct Layer {
+        name: &'static str,
+        z_order: i32,
+        exclusive: bool,
+    }
+
+    impl FaceElement for Layer {
+        fn name(&self) -> &str { self.name }
+        fn category(&self) -> ElementCategory { ElementCategory::Accessory }
+        fn update(&mut self, _shared_state: &mut SharedFaceState, _context: &mut RenderContext,
+                  _dt: f64) {}
+        fn render(&self, _canvas: &mut VirtualLedCanvas, _context: &RenderContext,
+                  _shared_state: &SharedFaceState, _draw_pixel_fn: &dyn DrawPixelFn) {}
+        fn z_order(&self) -> i32 { self.z_order }
+        fn wants_exclusive_render(&self) -> bool { self.exclusive }
+        fn as_any(&self) -> &dyn Any { self }
+        fn as_any_mut(&mut self) -> &mut dyn Any { self }
+        fn clone_box(&self) -> Box<dyn FaceElement> { Box::new(self.clone()) }
+    }
+
+    fn layer(name: &'static str, z_order: i32, exclusive: bool) -> Box<dyn FaceElement> {
+        Box::new(Layer { name, z_order, exclusive })
+    }
+
+    fn drawn(registry: &FaceElementRegistry) -> Vec<String> {
+        registry.frame_elements().iter().map(|e| e.name().to_string()).collect()
+    }
+
+    #[test]
+    fn non_finite_state_resets_to_resting_defaults() {
+        let mut state = resting_state();
+        state.mouth_opening = f64::NAN;
+        state.eye_top = f64::INFINITY;
+        state.eye_bottom = f64::NEG_INFINITY;
+        state.gaze_offset = (f64::NAN, f64::NAN);
+        state.mouth_curve = f64::NAN;
+        state.emotion_intensity = f64::INFINITY;
+        state.stereo_mouth = Some((1.0, f64::NAN));
+
+        assert_eq!(state.reset_non_finite(), ["mouth_opening", "eye_top", "eye_bottom",
+                                              "gaze_offset", "mouth_curve", "emotion_intensity",
+                                              "stereo_mouth"]);
+        assert_eq!(state.mouth_opening, 0.0);
+        assert_eq!(state.eye_top, OPEN_EYE_TOP);
+        assert_eq!(state.eye_bottom, OPEN_EYE_BOTTOM);
+        assert_eq!(state.gaze_offset, (0.0, 0.0));
+        assert_eq!(state.mouth_curve, 0.0);
+        assert_eq!(state.emotion_intensity, 0.0);
+        assert_eq!(state.stereo_mouth, None);
+
+        // Finite values are left alone
+        state.mouth_opening = 2.5;
+        state.gaze_offset = (1.0, -1.0);
+        assert!(state.reset_non_finite().is_empty());
+        assert_eq!(state.mouth_opening, 2.5);
+        assert_eq!(state.gaze_offset, (1.0, -1.0));
+    }
+
+    #[test]
+    fn elements_draw_by_layer_unless_one_takes_over() {
+        let mut registry = FaceElementRegistry::new();
+        registry.register(layer("top", 50, false));
+        registry.register(layer("first", 30, false));
+        registry.register(layer("second", 30, false));
+        registry.register(layer("bottom", 0, false));
+        assert!(!registry.has_exclusive_element());
+        assert_eq!(drawn(&registry), ["bottom", "first", "second", "top"]);
+
+        registry.register(layer("takeover", 5, true));
+        assert!(registry.has_exclusive_element());
+        assert_eq!(drawn(&registry), ["takeover"]);
+
+        registry.unregister("takeover");
+        assert!(!registry.has_exclusive_element());
+        assert_eq!(drawn(&registry), ["bottom", "first", "second", "top"]);
     }
 }
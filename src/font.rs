@@ -0,0 +1,171 @@
+// Tiny bitmap font for on-panel text
+// 3x5 pixel glyphs covering printable ASCII (32-126)
+
+pub const GLYPH_WIDTH: i32 = 3;
+pub const GLYPH_HEIGHT: i32 = 5;
+pub const GLYPH_SPACING: i32 = 1;
+
+// One entry per character starting at ' ' (32)
+// Each glyph is 5 rows top to bottom, 3 bits per row (0b100 = left column)
+const GLYPHS: [[u8; 5]; 95] = [
+    [0, 0, 0, 0, 0], // ' '
+    [2, 2, 2, 0, 2], // '!'
+    [5, 5, 0, 0, 0], // '"'
+    [5, 7, 5, 7, 5], // '#'
+    [3, 6, 7, 3, 6], // '$'
+    [5, 1, 2, 4, 5], // '%'
+    [2, 5, 2, 5, 3], // '&'
+    [2, 2, 0, 0, 0], // '\''
+    [1, 2, 2, 2, 1], // '('
+    [4, 2, 2, 2, 4], // ')'
+    [0, 5, 2, 5, 0], // '*'
+    [0, 2, 7, 2, 0], // '+'
+    [0, 0, 0, 2, 4], // ','
+    [0, 0, 7, 0, 0], // '-'
+    [0, 0, 0, 0, 2], // '.'
+    [1, 1, 2, 4, 4], // '/'
+    [7, 5, 5, 5, 7], // '0'
+    [2, 6, 2, 2, 7], // '1'
+    [7, 1, 7, 4, 7], // '2'
+    [7, 1, 7, 1, 7], // '3'
+    [5, 5, 7, 1, 1], // '4'
+    [7, 4, 7, 1, 7], // '5'
+    [7, 4, 7, 5, 7], // '6'
+    [7, 1, 1, 1, 1], // '7'
+    [7, 5, 7, 5, 7], // '8'
+    [7, 5, 7, 1, 7], // '9'
+    [0, 2, 0, 2, 0], // ':'
+    [0, 2, 0, 2, 4], // ';'
+    [1, 2, 4, 2, 1], // '<'
+    [0, 7, 0, 7, 0], // '='
+    [4, 2, 1, 2, 4], // '>'
+    [7, 1, 3, 0, 2], // '?'
+    [2, 5, 7, 4, 3], // '@'
+    [2, 5, 7, 5, 5], // 'A'
+    [6, 5, 6, 5, 6], // 'B'
+    [3, 4, 4, 4, 3], // 'C'
+    [6, 5, 5, 5, 6], // 'D'
+    [7, 4, 6, 4, 7], // 'E'
+    [7, 4, 6, 4, 4], // 'F'
+    [3, 4, 5, 5, 3], // 'G'
+    [5, 5, 7, 5, 5], // 'H'
+    [7, 2, 2, 2, 7], // 'I'
+    [1, 1, 1, 5, 2], // 'J'
+    [5, 5, 6, 5, 5], // 'K'
+    [4, 4, 4, 4, 7], // 'L'
+    [5, 7, 7, 5, 5], // 'M'
+    [6, 5, 5, 5, 5], // 'N'
+    [2, 5, 5, 5, 2], // 'O'
+    [6, 5, 6, 4, 4], // 'P'
+    [2, 5, 5, 6, 3], // 'Q'
+    [6, 5, 6, 5, 5], // 'R'
+    [3, 4, 2, 1, 6], // 'S'
+    [7, 2, 2, 2, 2], // 'T'
+    [5, 5, 5, 5, 7], // 'U'
+    [5, 5, 5, 5, 2], // 'V'
+    [5, 5, 7, 7, 5], // 'W'
+    [5, 5, 2, 5, 5], // 'X'
+    [5, 5, 2, 2, 2], // 'Y'
+    [7, 1, 2, 4, 7], // 'Z'
+    [6, 4, 4, 4, 6], // '['
+    [4, 4, 2, 1, 1], // '\\'
+    [3, 1, 1, 1, 3], // ']'
+    [2, 5, 0, 0, 0], // '^'
+    [0, 0, 0, 0, 7], // '_'
+    [4, 2, 0, 0, 0], // '`'
+    [0, 3, 5, 5, 3], // 'a'
+    [4, 6, 5, 5, 6], // 'b'
+    [0, 3, 4, 4, 3], // 'c'
+    [1, 3, 5, 5, 3], // 'd'
+    [0, 3, 7, 4, 3], // 'e'
+    [1, 2, 7, 2, 2], // 'f'
+    [3, 5, 7, 1, 6], // 'g'
+    [4, 4, 6, 5, 5], // 'h'
+    [2, 0, 2, 2, 2], // 'i'
+    [1, 0, 1, 5, 2], // 'j'
+    [4, 5, 6, 5, 5], // 'k'
+    [6, 2, 2, 2, 7], // 'l'
+    [0, 5, 7, 7, 5], // 'm'
+    [0, 6, 5, 5, 5], // 'n'
+    [0, 2, 5, 5, 2], // 'o'
+    [0, 6, 5, 6, 4], // 'p'
+    [0, 3, 5, 3, 1], // 'q'
+    [0, 3, 4, 4, 4], // 'r'
+    [0, 3, 6, 1, 6], // 's'
+    [2, 7, 2, 2, 1], // 't'
+    [0, 5, 5, 5, 3], // 'u'
+    [0, 5, 5, 5, 2], // 'v'
+    [0, 5, 5, 7, 5], // 'w'
+    [0, 5, 2, 2, 5], // 'x'
+    [0, 5, 3, 1, 6], // 'y'
+    [0, 7, 3, 6, 7], // 'z'
+    [3, 2, 6, 2, 3], // '{'
+    [2, 2, 2, 2, 2], // '|'
+    [6, 2, 3, 2, 6], // '}'
+    [0, 0, 3, 6, 0], // '~'
+];
+
+// Icons drawn in the same 3x5 format as the glyphs
+pub const THERMOMETER_ICON: [u8; 5] = [2, 3, 2, 7, 7];  // Stem with a tick mark over the bulb
+pub const BATTERY_LOW_ICON: [u8; 5] = [2, 7, 5, 5, 7];  // Empty cell with a terminal on top
+
+/// Fixed-size 3x5 bitmap font
+pub struct BitmapFont;
+
+impl BitmapFont {
+    /// Get the glyph rows for a character (unsupported characters render as '?')
+    pub fn glyph(c: char) -> [u8; 5] {
+        let code = c as u32;
+        if (32..=126).contains(&code) {
+            GLYPHS[(code - 32) as usize]
+        } else {
+            GLYPHS[('?' as u32 - 32) as usize]
+        }
+    }
+
+    /// Check whether a glyph pixel is lit (col 0..3, row 0..5 from the top)
+    pub fn is_lit(c: char, col: i32, row: i32) -> bool {
+        if col < 0 || col >= GLYPH_WIDTH || row < 0 || row >= GLYPH_HEIGHT {
+            return false;
+        }
+        let bits = Self::glyph(c)[row as usize];
+        bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0
+    }
+
+    /// Width in pixels of a rendered string (including spacing between glyphs)
+    pub fn text_width(text: &str) -> i32 {
+        let count = text.chars().count() as i32;
+        if count == 0 {
+            0
+        } else {
+            count * (GLYPH_WIDTH + GLYPH_SPACING) - GLYPH_SPACING
+        }
+    }
+
+    /// Call `plot(dx, dy)` for every lit pixel of a 3x5 icon (rows as in `glyph`)
+    /// Offsets are relative to the top-left corner, with dy growing downward
+    pub fn for_each_icon_pixel<F: FnMut(i32, i32)>(icon: &[u8; 5], mut plot: F) {
+        for (row, bits) in icon.iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                    plot(col, row as i32);
+                }
+            }
+        }
+    }
+
+    /// Call `plot(dx, dy)` for every lit pixel of `text`
+    /// Offsets are relative to the top-left corner, with dy growing downward
+    pub fn for_each_pixel<F: FnMut(i32, i32)>(text: &str, mut plot: F) {
+        for (i, c) in text.chars().enumerate() {
+            let origin_x = i as i32 * (GLYPH_WIDTH + GLYPH_SPACING);
+            for row in 0..GLYPH_HEIGHT {
+                for col in 0..GLYPH_WIDTH {
+                    if Self::is_lit(c, col, row) {
+                        plot(origin_x + col, row);
+                    }
+                }
+            }
+        }
+    }
+}
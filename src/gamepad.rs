@@ -1,22 +1,221 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
-use gilrs::{Gilrs, Event, Button, EventType};
+use std::time::{Duration, Instant};
+use gilrs::{Gilrs, Event, Button, EventType, Axis};
+use gilrs::ev::Code;
+use serde::Deserialize;
+use crate::calibration::ButtonMap;
 use crate::color::ColorPalette;
+use crate::macros::{is_recordable, MacroRecorder, MAX_MACRO_LEN};
+use crate::snapshot::{QUICK_SLOT, SNAPSHOT_PATH};
 use crate::MOUTH_MAX_OPENING;
+use tracing::{debug, info, warn};
+
+// Brightness limits (never fully off so the face stays visible)
+pub const MIN_BRIGHTNESS: f64 = 0.1;
+pub const MAX_BRIGHTNESS: f64 = 1.0;
+
+// Number of undoable changes kept
+pub const HISTORY_LEN: usize = 10;
+
+// How long L2 + R2 must be held together to start the matrix rain
+const MATRIX_RAIN_HOLD: Duration = Duration::from_secs(2);
+
+// How long Select must be held alone to start (or cancel) macro recording
+const MACRO_RECORD_HOLD: Duration = Duration::from_secs(3);
+
+// Trigger axis position counted as "held" for button combos
+const TRIGGER_HELD_THRESHOLD: f64 = 0.5;
+
+// Right stick travel ignored for eye gaze, so a worn stick doesn't leave the eyes off-center
+const GAZE_DEADZONE: f64 = 0.15;
+
+// A raw value below this marks a controller's triggers as -1..1 (TriggerRange::Auto)
+const BIPOLAR_DETECT_THRESHOLD: f64 = -0.1;
+
+// Trigger learning: minimum travel that counts as a full pull, and how close to the
+// resting value the axis must come back to count as released
+const LEARN_MIN_TRAVEL: f64 = 0.8;
+const LEARN_RELEASE_TOLERANCE: f64 = 0.2;
+
+/// How a trigger axis reports its travel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerRange {
+    Auto,      // Detect per controller: unipolar until a negative value is seen
+    Unipolar,  // 0.0 released .. 1.0 pressed
+    Bipolar,   // -1.0 released .. 1.0 pressed
+}
+
+/// Turns raw mouth trigger events into 0.0 (released) .. 1.0 (fully pressed)
+/// Handles both axis-style triggers (LeftZ, or an unmapped axis by its evdev code)
+/// and button-style triggers (LeftTrigger2 with an analog value)
+#[derive(Debug, Clone)]
+pub struct TriggerAxis {
+    range: TriggerRange,
+    invert: bool,               // Controller reports pressed as the low end
+    deadzone: f64,              // Travel ignored at the released end (0.0 to 1.0)
+    axis_code: u32,             // evdev ABS code of the trigger when gilrs can't map it
+    bipolar_pads: HashSet<usize>, // Controllers detected as -1..1 (TriggerRange::Auto)
+}
+
+impl TriggerRange {
+    /// Name as written in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            TriggerRange::Auto => "auto",
+            TriggerRange::Unipolar => "unipolar",
+            TriggerRange::Bipolar => "bipolar",
+        }
+    }
+}
+
+impl TriggerAxis {
+    pub fn new(range: TriggerRange, invert: bool, deadzone: f64, axis_code: u32) -> Self {
+        Self {
+            range,
+            invert,
+            deadzone: deadzone.clamp(0.0, 0.99),
+            axis_code,
+            bipolar_pads: HashSet::new(),
+        }
+    }
+
+    /// Whether an axis event comes from the mouth trigger
+    /// The mapped LeftZ axis always counts; the configured (or learned) raw code
+    /// catches triggers that the controller's mapping names differently or not at all
+    pub fn matches(&self, axis: Axis, code: Code) -> bool {
+        axis == Axis::LeftZ || axis_code(code) == self.axis_code
+    }
+
+    /// Normalize a raw axis value from the given controller
+    pub fn axis_value(&mut self, gamepad: usize, raw: f64) -> f64 {
+        let bipolar = match self.range {
+            TriggerRange::Unipolar => false,
+            TriggerRange::Bipolar => true,
+            TriggerRange::Auto => {
+                if raw < BIPOLAR_DETECT_THRESHOLD && self.bipolar_pads.insert(gamepad) {
+                    info!("🎮 Gamepad {} triggers report -1..1", gamepad);
+                }
+                self.bipolar_pads.contains(&gamepad)
+            }
+        };
+        let value = if bipolar { (raw + 1.0) / 2.0 } else { raw };
+        self.apply(value)
+    }
+
+    /// Normalize a button-style trigger value (gilrs already reports these as 0..1)
+    pub fn button_value(&self, raw: f64) -> f64 {
+        self.apply(raw)
+    }
+
+    // Inversion, then deadzone with the remaining travel rescaled to still reach 1.0
+    fn apply(&self, value: f64) -> f64 {
+        let value = value.clamp(0.0, 1.0);
+        let value = if self.invert { 1.0 - value } else { value };
+        if value <= self.deadzone {
+            0.0
+        } else {
+            (value - self.deadzone) / (1.0 - self.deadzone)
+        }
+    }
+}
 
 // Button press tracking for long press detection
 pub struct ButtonTracker {
     start_pressed_at: Option<Instant>,
+    select_held: bool,        // Select is down (Select + Start = undo)
+    select_chord_used: bool,  // Select was part of a chord, skip its own action on release
+    select_pressed_at: Option<Instant>,  // Select held alone (None once the macro hold fired)
+    left_trigger_held: bool,
+    right_trigger_held: bool,
+    triggers_held_since: Option<Instant>,  // Both triggers down (None once the combo fired)
+    macros: MacroRecorder,
 }
 
 impl ButtonTracker {
     pub fn new() -> Self {
         Self {
             start_pressed_at: None,
+            select_held: false,
+            select_chord_used: false,
+            select_pressed_at: None,
+            left_trigger_held: false,
+            right_trigger_held: false,
+            triggers_held_since: None,
+            macros: MacroRecorder::load(SNAPSHOT_PATH),
+        }
+    }
+
+    /// Drop all held buttons (e.g. when a gamepad disconnects mid-press), keeping macros
+    pub fn release_all(&mut self) {
+        self.start_pressed_at = None;
+        self.select_held = false;
+        self.select_chord_used = false;
+        self.select_pressed_at = None;
+        self.left_trigger_held = false;
+        self.right_trigger_held = false;
+        self.triggers_held_since = None;
+    }
+
+    // Triggers report as buttons on some pads and only as axes on others, so both feed this
+    fn set_trigger_held(&mut self, left: bool, held: bool) {
+        let both_were_held = self.left_trigger_held && self.right_trigger_held;
+        if left {
+            self.left_trigger_held = held;
+        } else {
+            self.right_trigger_held = held;
+        }
+        let both_held = self.left_trigger_held && self.right_trigger_held;
+        if both_held && !both_were_held {
+            self.triggers_held_since = Some(Instant::now());
+        } else if !both_held {
+            self.triggers_held_since = None;
         }
     }
 }
 
+// Settings captured before a destructive change so it can be undone
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaskStateSnapshot {
+    pub action: &'static str,  // What was about to change ("palette", "brightness")
+    pub brightness: f64,
+    pub color_palette: ColorPalette,
+}
+
+// Ring buffer of the last HISTORY_LEN snapshots (oldest dropped first)
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistory {
+    snapshots: VecDeque<MaskStateSnapshot>,
+}
+
+impl CommandHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, snapshot: MaskStateSnapshot) {
+        if self.snapshots.len() == HISTORY_LEN {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Remove and return the most recent snapshot
+    pub fn pop(&mut self) -> Option<MaskStateSnapshot> {
+        self.snapshots.pop_back()
+    }
+
+    /// Snapshots from most recent to oldest
+    pub fn iter(&self) -> impl Iterator<Item = &MaskStateSnapshot> {
+        self.snapshots.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+}
+
 // Mask control state
 #[derive(Debug, Clone)]
 pub struct MaskState {
@@ -27,7 +226,62 @@ pub struct MaskState {
     pub manual_mouth_mode: bool,   // Enable manual mouth movement mode
     pub mouth_analog_value: f64,   // Analog trigger value (0.0 to 1.0)
     pub video_mode: bool,          // Video playback active
+    pub video_name: Option<String>, // Video on screen (set by the render loop, for the expression log)
     pub video_action: VideoAction, // What to do with video
+    pub video_palette_mode: bool,  // Render video luminance through the color palette
+    pub slideshow_mode: bool,      // Image slideshow active
+    pub slideshow_action: SlideshowAction, // What to do with the slideshow
+    pub history: CommandHistory,   // Undo history for palette/brightness changes
+    pub glitch_enabled: bool,      // Glitch post-process over the face
+    pub matrix_rain_requested: bool, // Start the matrix rain on the next frame
+    pub calibration_requested: bool, // Start the gamepad calibration wizard
+    pub emote_requested: Option<usize>, // Queue the emote in this slot (index into config emotes)
+    pub wink_requested: bool,           // Queue the wink-and-smirk emote
+    pub wake_requested: bool,           // A button was pressed (wakes the face from standby)
+    pub asleep: bool,              // Panels in quiet standby (set by the render loop; face updates pause)
+    pub night_vision: bool,        // Monochrome green post-process
+    pub invert: bool,              // Negative-image post-process
+    pub smile_intensity: f64,      // Smile Mouth depth, -1.0 (frown) to 1.0 (right stick Y in manual mouth mode)
+    pub mouth_emotion: f64,        // Held mouth override, -1.0 (frown, Select + L1) to 1.0 (smile, Select + R1)
+    pub macro_mode: bool,          // Recording button presses into a macro (hold Select 3s)
+    pub eye_pose: EyePose,         // Discrete look direction (Select + D-pad), added to the gaze
+    pub gaze_x: f64,               // Right stick eye direction, -1.0 (left) to 1.0 (right)
+    pub gaze_y: f64,               // Right stick eye direction, -1.0 (down) to 1.0 (up)
+    pub strobe_hz: Option<f64>,    // Blank every other half cycle at this rate (capped at MAX_STROBE_HZ)
+    pub palette_shift_speed: f64,  // Palette drift over the whole face, color index units per second (0 = off)
+}
+
+/// Fixed look direction for the eyes (screen directions, as seen from the front)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EyePose {
+    Center,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl EyePose {
+    /// Unit direction of the pose (x right, y up)
+    pub fn direction(&self) -> (f64, f64) {
+        match self {
+            EyePose::Center => (0.0, 0.0),
+            EyePose::Left => (-1.0, 0.0),
+            EyePose::Right => (1.0, 0.0),
+            EyePose::Up => (0.0, 1.0),
+            EyePose::Down => (0.0, -1.0),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            EyePose::Center => "Center",
+            EyePose::Left => "Left",
+            EyePose::Right => "Right",
+            EyePose::Up => "Up",
+            EyePose::Down => "Down",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +290,16 @@ pub enum VideoAction {
     PlayFirst,
     NextVideo,
     ExitVideo,
+    Play(String), // Play a video by file name
+    Seek(usize),  // Jump to a frame of the current video
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlideshowAction {
+    None,
+    Start,
+    NextImage,
+    Exit,
 }
 
 impl MaskState {
@@ -48,77 +312,285 @@ impl MaskState {
             manual_mouth_mode: false,
             mouth_analog_value: 0.0,
             video_mode: false,
+            video_name: None,
             video_action: VideoAction::None,
+            video_palette_mode: false,
+            slideshow_mode: false,
+            slideshow_action: SlideshowAction::None,
+            history: CommandHistory::new(),
+            glitch_enabled: false,
+            matrix_rain_requested: false,
+            calibration_requested: false,
+            emote_requested: None,
+            wink_requested: false,
+            wake_requested: false,
+            asleep: false,
+            night_vision: false,
+            invert: false,
+            smile_intensity: 0.0,
+            mouth_emotion: 0.0,
+            macro_mode: false,
+            eye_pose: EyePose::Center,
+            gaze_x: 0.0,
+            gaze_y: 0.0,
+            strobe_hz: None,
+            palette_shift_speed: 0.0,
+        }
+    }
+
+    /// Save the current settings before a destructive change
+    pub fn record(&mut self, action: &'static str) {
+        self.history.push(MaskStateSnapshot {
+            action,
+            brightness: self.brightness,
+            color_palette: self.color_palette,
+        });
+    }
+
+    /// Restore the most recent snapshot, returns it or None if there is nothing to undo
+    pub fn undo(&mut self) -> Option<MaskStateSnapshot> {
+        let snapshot = self.history.pop()?;
+        self.set_brightness(snapshot.brightness);
+        self.color_palette = snapshot.color_palette;
+        Some(snapshot)
+    }
+
+    /// Set brightness, clamped to [MIN_BRIGHTNESS, MAX_BRIGHTNESS]
+    /// All brightness writers should go through this so invalid values never reach rendering
+    pub fn set_brightness(&mut self, brightness: f64) {
+        if !brightness.is_finite() {
+            return;
+        }
+        self.brightness = brightness.clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS);
+    }
+}
+
+/// Raw evdev code of an event (Linux codes carry the event type in the high 16 bits)
+pub fn axis_code(code: Code) -> u32 {
+    code.into_u32() & 0xFFFF
+}
+
+/// Trigger settings recorded by `learn_trigger`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LearnedTrigger {
+    pub axis_code: u32,
+    pub range: TriggerRange,
+    pub invert: bool,
+}
+
+/// Values seen on one axis while learning a trigger
+#[derive(Debug, Clone, Copy)]
+pub struct AxisTravel {
+    rest: f64,     // First value reported, taken as the released position
+    pressed: f64,  // Value farthest from rest
+    min: f64,
+}
+
+impl AxisTravel {
+    pub fn new(value: f64) -> Self {
+        Self { rest: value, pressed: value, min: value }
+    }
+
+    /// Record a value, returns true once the axis was pulled fully and released again
+    pub fn observe(&mut self, value: f64) -> bool {
+        if (value - self.rest).abs() > (self.pressed - self.rest).abs() {
+            self.pressed = value;
+        }
+        self.min = self.min.min(value);
+        (self.pressed - self.rest).abs() >= LEARN_MIN_TRAVEL
+            && (value - self.rest).abs() <= LEARN_RELEASE_TOLERANCE
+    }
+
+    pub fn learned(&self, axis_code: u32) -> LearnedTrigger {
+        LearnedTrigger {
+            axis_code,
+            range: if self.min < -0.5 { TriggerRange::Bipolar } else { TriggerRange::Unipolar },
+            invert: self.pressed < self.rest,
+        }
+    }
+}
+
+/// Calibration step for controllers whose trigger gilrs can't map: wait for the user
+/// to pull the mouth trigger fully and let go, then report which axis moved and how
+/// Returns None if nothing was pulled before the timeout
+pub fn learn_trigger(gilrs: &mut Gilrs, timeout: Duration) -> Option<LearnedTrigger> {
+    info!("🎯 Trigger learning: pull the mouth trigger all the way, then release it");
+    let started = Instant::now();
+    let mut travel: HashMap<u32, AxisTravel> = HashMap::new();
+
+    while started.elapsed() < timeout {
+        while let Some(Event { event, .. }) = gilrs.next_event() {
+            let (code, value) = match event {
+                EventType::AxisChanged(_, value, code) => (axis_code(code), value as f64),
+                _ => continue,
+            };
+            let axis = travel.entry(code).or_insert_with(|| AxisTravel::new(value));
+            if axis.observe(value) {
+                let learned = axis.learned(code);
+                info!("🎯 Learned trigger: axis code {}, {} range{}", learned.axis_code,
+                      learned.range.name(), if learned.invert { ", inverted" } else { "" });
+                return Some(learned);
+            }
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    warn!("⚠️  No trigger pull detected within {}s", timeout.as_secs());
+    None
+}
+
+/// Sets of controls that can be given to different gamepads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlGroup {
+    Face,   // Eyes, mouth, blinking, mute, blep, matrix rain
+    Color,  // Palette, brightness, glitch, calibration
+    Media,  // Video, slideshow, undo
+}
+
+impl ControlGroup {
+    /// Group a (remapped) button belongs to
+    pub fn of(button: Button) -> Self {
+        match button {
+            Button::West | Button::DPadUp | Button::DPadDown | Button::Mode => ControlGroup::Color,
+            Button::Start | Button::Select | Button::LeftTrigger | Button::RightTrigger => {
+                ControlGroup::Media
+            }
+            _ => ControlGroup::Face,
+        }
+    }
+}
+
+/// Which control groups each gamepad may use, for sharing the mask between operators
+/// Gamepads without an entry (including the default, empty setup) control everything
+#[derive(Debug, Clone, Default)]
+pub struct GamepadRoles {
+    roles: HashMap<usize, Vec<ControlGroup>>,
+}
+
+impl GamepadRoles {
+    pub fn new(roles: HashMap<usize, Vec<ControlGroup>>) -> Self {
+        Self { roles }
+    }
+
+    pub fn allows(&self, gamepad: usize, group: ControlGroup) -> bool {
+        self.roles.get(&gamepad).map(|groups| groups.contains(&group)).unwrap_or(true)
+    }
+
+    /// Human-readable list of a gamepad's groups, for logging
+    pub fn describe(&self, gamepad: usize) -> String {
+        match self.roles.get(&gamepad) {
+            Some(groups) => groups.iter().map(|g| format!("{:?}", g).to_lowercase())
+                .collect::<Vec<_>>().join(", "),
+            None => "all controls".to_string(),
         }
     }
 }
 
 // Gamepad input handler
 pub fn handle_gamepad_input<T: CycleEyes>(gilrs: &mut Gilrs, state: &Arc<Mutex<MaskState>>,
-                                          protogen: &mut T, button_tracker: &mut ButtonTracker) {
-    while let Some(Event { id, event, time: _ }) = gilrs.next_event() {
-        println!("🎮 Event from gamepad {}: {:?}", id, event);
-        match event {
-            EventType::ButtonPressed(button, _) => {
-                println!("🎮 Button pressed: {:?}", button);
+                                          protogen: &mut T, button_tracker: &mut ButtonTracker,
+                                          mouth_trigger: &mut TriggerAxis, button_map: &ButtonMap,
+                                          roles: &GamepadRoles) {
+    // L2 + R2 held long enough - fire once per hold
+    if let Some(since) = button_tracker.triggers_held_since {
+        if since.elapsed() >= MATRIX_RAIN_HOLD {
+            button_tracker.triggers_held_since = None;
+            state.lock().unwrap().matrix_rain_requested = true;
+            info!("🟩 L2 + R2 held: Matrix rain");
+        }
+    }
 
-                // Track Start button press time for long press detection
-                if button == Button::Start {
-                    button_tracker.start_pressed_at = Some(Instant::now());
-                }
+    // Select held alone long enough - start recording a macro, or cancel the recording
+    if let Some(since) = button_tracker.select_pressed_at {
+        if since.elapsed() >= MACRO_RECORD_HOLD && !button_tracker.select_chord_used {
+            button_tracker.select_pressed_at = None;
+            button_tracker.select_chord_used = true;
+            let mut s = state.lock().unwrap();
+            s.macro_mode = !s.macro_mode;
+            if s.macro_mode {
+                button_tracker.macros.start_recording();
+                info!("⏺️  Recording macro: press up to {} buttons, then Select + a face button to save",
+                      MAX_MACRO_LEN);
+            } else {
+                info!("⏹️  Macro recording cancelled");
+            }
+        }
+    }
 
-                let mut s = state.lock().unwrap();
-                match button {
-                    // Face buttons
-                    Button::South => {  // A/X button - Toggle mic mute
-                        s.mic_muted = !s.mic_muted;
-                        println!("🎤 Microphone {}", if s.mic_muted { "MUTED" } else { "ACTIVE" });
-                    }
-                    Button::East => {   // B/Circle button - Toggle manual mouth mode
-                        s.manual_mouth_mode = !s.manual_mouth_mode;
-                        println!("👄 Manual mouth mode {}", if s.manual_mouth_mode { "ON" } else { "OFF" });
-                    }
-                    Button::North => {  // Y/Triangle button - Toggle blinking
-                        s.blink_enabled = !s.blink_enabled;
-                        println!("👁️  Blinking {}", if s.blink_enabled { "ON" } else { "OFF" });
-                    }
-                    Button::West => {   // X/Square button - Cycle color palette
-                        s.color_palette = s.color_palette.next();
-                        println!("🎨 Color: {}", s.color_palette.name());
-                    }
+    // Replay one macro press per poll, as a plain press (Select chords don't apply)
+    if let Some(button) = button_tracker.macros.next_playback() {
+        let select_held = std::mem::replace(&mut button_tracker.select_held, false);
+        press_button(button, state, protogen, button_tracker);
+        button_tracker.select_held = select_held;
+    }
 
-                    // D-Pad for brightness and eye cycling
-                    Button::DPadUp => {
-                        s.brightness = (s.brightness + 0.1).min(1.0);
-                        println!("🔆 Brightness: {:.0}%", s.brightness * 100.0);
-                    }
-                    Button::DPadDown => {
-                        s.brightness = (s.brightness - 0.1).max(0.1);
-                        println!("🔅 Brightness: {:.0}%", s.brightness * 100.0);
-                    }
-                    Button::DPadRight => {
-                        drop(s); // Release lock before calling protogen
-                        protogen.cycle_eyes_forward();
-                        return; // Exit early since lock is dropped
-                    }
-                    Button::DPadLeft => {
-                        drop(s); // Release lock before calling protogen
-                        protogen.cycle_eyes_backward();
-                        return; // Exit early since lock is dropped
-                    }
+    while let Some(Event { id, event, time: _ }) = gilrs.next_event() {
+        debug!("🎮 Event from gamepad {}: {:?}", id, event);
+        let gamepad = usize::from(id);
 
-                    // Triggers removed - now using analog axis for smooth control
+        // Drop events for controls this gamepad isn't bound to
+        let group = match event {
+            EventType::ButtonPressed(button, code)
+            | EventType::ButtonReleased(button, code)
+            | EventType::ButtonChanged(button, _, code) => Some(ControlGroup::of(button_map.remap(button, code))),
+            EventType::AxisChanged(..) => Some(ControlGroup::Face),
+            _ => None,
+        };
+        if let Some(group) = group {
+            if !roles.allows(gamepad, group) {
+                debug!("🎮 Gamepad {} not bound to {:?} controls", gamepad, group);
+                continue;
+            }
+        }
 
-                    // Start button is handled on release to detect short vs long press
-                    Button::Start => {
-                        // Do nothing on press, wait for release
-                    }
+        match event {
+            EventType::Connected => {
+                info!("🎮 Gamepad {} connected: {} ({})", gamepad, gilrs.gamepad(id).name(),
+                      roles.describe(gamepad));
+                print_control_mapping();
+            }
+            EventType::Disconnected => {
+                warn!("🎮 Gamepad {} disconnected", gamepad);
+                // Forget anything it was holding so nothing stays stuck down
+                button_tracker.release_all();
+                let mut s = state.lock().unwrap();
+                s.mouth_analog_value = 0.0;
+                s.mouth_emotion = 0.0;
+                s.gaze_x = 0.0;
+                s.gaze_y = 0.0;
+            }
+            EventType::ButtonPressed(button, code) => {
+                let button = button_map.remap(button, code);
+                debug!("🎮 Button pressed: {:?}", button);
+                state.lock().unwrap().wake_requested = true;
 
-                    _ => {}
-                }
+                press_button(button, state, protogen, button_tracker);
             }
-            EventType::ButtonReleased(button, _) => {
+            EventType::ButtonReleased(button, code) => {
+                let button = button_map.remap(button, code);
                 match button {
+                    Button::LeftTrigger2 | Button::RightTrigger2 => {
+                        button_tracker.set_trigger_held(button == Button::LeftTrigger2, false);
+                    }
+                    Button::LeftTrigger | Button::RightTrigger => {
+                        // End a smile/frown override (even if Select was let go first)
+                        let mut s = state.lock().unwrap();
+                        if s.mouth_emotion != 0.0 {
+                            s.mouth_emotion = 0.0;
+                            info!("😐 Mouth: NEUTRAL");
+                        }
+                    }
+                    Button::Select => {  // Toggle full-color / palette-mapped video
+                        button_tracker.select_held = false;
+                        button_tracker.select_pressed_at = None;
+                        if std::mem::take(&mut button_tracker.select_chord_used) {
+                            continue;
+                        }
+                        let mut s = state.lock().unwrap();
+                        s.video_palette_mode = !s.video_palette_mode;
+                        info!("📺 Video colors: {}", if s.video_palette_mode { "PALETTE" } else { "FULL COLOR" });
+                    }
                     Button::Start => {
                         // Check press duration for short vs long press
                         if let Some(pressed_at) = button_tracker.start_pressed_at.take() {
@@ -130,16 +602,16 @@ pub fn handle_gamepad_input<T: CycleEyes>(gilrs: &mut Gilrs, state: &Arc<Mutex<M
                                 // Long press: Exit video mode
                                 if s.video_mode {
                                     s.video_action = VideoAction::ExitVideo;
-                                    println!("📺 ⏹️  Long press: Exiting video mode");
+                                    info!("📺 ⏹️  Long press: Exiting video mode");
                                 }
                             } else {
                                 // Short press: Play first video or skip to next
                                 if s.video_mode {
                                     s.video_action = VideoAction::NextVideo;
-                                    println!("📺 ⏭️  Short press: Next video");
+                                    info!("📺 ⏭️  Short press: Next video");
                                 } else {
                                     s.video_action = VideoAction::PlayFirst;
-                                    println!("📺 ▶️  Short press: Start video playback");
+                                    info!("📺 ▶️  Short press: Start video playback");
                                 }
                             }
                         }
@@ -148,50 +620,455 @@ pub fn handle_gamepad_input<T: CycleEyes>(gilrs: &mut Gilrs, state: &Arc<Mutex<M
                 }
             }
             EventType::AxisChanged(axis, value, code) => {
-                // Handle left trigger - code 10 is left trigger, code 9 is right trigger
-                use gilrs::Axis;
-
-                // Debug: print code to verify which trigger
-                // Check if this is code 10 (left trigger) by examining the debug output
-                // For now, just check axis and filter by code value
-                let code_value = format!("{:?}", code);
-                let is_left_trigger = axis == Axis::LeftZ ||
-                    (axis == Axis::Unknown && code_value.contains("code: 10"));
+                if axis == Axis::RightZ {
+                    button_tracker.set_trigger_held(false, value as f64 > TRIGGER_HELD_THRESHOLD);
+                }
 
-                if is_left_trigger {
+                // Right stick - Eye gaze (Y sets the smile depth instead in manual mouth mode)
+                if axis == Axis::RightStickX || axis == Axis::RightStickY {
+                    let value = if (value as f64).abs() < GAZE_DEADZONE { 0.0 } else { value as f64 };
                     let mut s = state.lock().unwrap();
-                    // Use only positive half: 0.0 (closed) to 1.0 (fully open)
-                    // Triggers typically go from -1.0 (not pressed) to 1.0 (fully pressed)
-                    let analog_value = (value.max(0.0).clamp(0.0, 1.0)) as f64;
-                    s.mouth_analog_value = analog_value;
-                    // Only print when in manual mouth mode
-                    if s.manual_mouth_mode {
-                        println!("👄 Mouth analog: {:.2}", analog_value);
+                    if axis == Axis::RightStickX {
+                        s.gaze_x = value;
+                    } else if s.manual_mouth_mode {
+                        s.smile_intensity = value;
+                    } else {
+                        s.gaze_y = value;
                     }
                 }
+
+                if mouth_trigger.matches(axis, code) {
+                    let analog_value = mouth_trigger.axis_value(usize::from(id), value as f64);
+                    set_mouth_analog(state, button_tracker, analog_value);
+                }
+            }
+            EventType::ButtonChanged(Button::LeftTrigger2, value, _) => {
+                let analog_value = mouth_trigger.button_value(value as f64);
+                set_mouth_analog(state, button_tracker, analog_value);
             }
             _ => {}
         }
     }
 }
 
-// Trait for objects that can cycle eyes
+// Eye pose for a D-pad direction pressed together with Select
+fn eye_pose_for(button: Button) -> Option<EyePose> {
+    match button {
+        Button::DPadLeft => Some(EyePose::Left),
+        Button::DPadRight => Some(EyePose::Right),
+        Button::DPadUp => Some(EyePose::Up),
+        Button::DPadDown => Some(EyePose::Down),
+        _ => None,
+    }
+}
+
+// Profile slot for a D-pad direction pressed while Start is held (clockwise from up)
+fn profile_slot(button: Button) -> Option<usize> {
+    match button {
+        Button::DPadUp => Some(0),
+        Button::DPadRight => Some(1),
+        Button::DPadDown => Some(2),
+        Button::DPadLeft => Some(3),
+        _ => None,
+    }
+}
+
+// Macro slot for a face button pressed together with Select
+fn macro_slot(button: Button) -> Option<usize> {
+    match button {
+        Button::South => Some(0),
+        Button::East => Some(1),
+        Button::North => Some(2),
+        Button::West => Some(3),
+        _ => None,
+    }
+}
+
+// Emote slot for a face button pressed together with Select
+fn emote_slot(button: Button) -> Option<usize> {
+    match button {
+        Button::South => Some(0),
+        Button::East => Some(1),
+        Button::North => Some(2),
+        _ => None,
+    }
+}
+
+// Act on one button press (from the gamepad, or replayed from a macro)
+fn press_button<T: CycleEyes>(button: Button, state: &Arc<Mutex<MaskState>>, protogen: &mut T,
+                              button_tracker: &mut ButtonTracker) {
+    // Select + face button - Save the macro being recorded, or play the one saved there
+    if button_tracker.select_held {
+        if let Some(slot) = macro_slot(button) {
+            let mut s = state.lock().unwrap();
+            if s.macro_mode {
+                button_tracker.select_chord_used = true;
+                s.macro_mode = false;
+                match button_tracker.macros.finish_recording(slot) {
+                    Ok(0) => info!("⏹️  Macro {} cleared", slot + 1),
+                    Ok(len) => info!("💾 Macro {} saved ({} presses)", slot + 1, len),
+                    Err(e) => warn!("⚠️  Could not save macro {}: {}", slot + 1, e),
+                }
+                return;
+            }
+            if button_tracker.macros.has_macro(slot) {
+                button_tracker.select_chord_used = true;
+                button_tracker.macros.play(slot);
+                info!("▶️  Macro {}", slot + 1);
+                return;
+            }
+        }
+    }
+
+    // Select + X/Square - Toggle inverted colors
+    if button_tracker.select_held && button == Button::West {
+        button_tracker.select_chord_used = true;
+        let mut s = state.lock().unwrap();
+        s.invert = !s.invert;
+        info!("🔳 Invert: {}", if s.invert { "ON" } else { "OFF" });
+        return;
+    }
+
+    // Select + D-pad - Look that way (again to look straight ahead)
+    if button_tracker.select_held {
+        if let Some(pose) = eye_pose_for(button) {
+            button_tracker.select_chord_used = true;
+            let mut s = state.lock().unwrap();
+            s.eye_pose = if s.eye_pose == pose { EyePose::Center } else { pose };
+            info!("👀 Look: {}", s.eye_pose.name());
+            return;
+        }
+    }
+
+    // Select + R1/L1 - Smile/frown while held
+    if button_tracker.select_held
+        && (button == Button::RightTrigger || button == Button::LeftTrigger) {
+        button_tracker.select_chord_used = true;
+        let mut s = state.lock().unwrap();
+        s.mouth_emotion = if button == Button::RightTrigger { 1.0 } else { -1.0 };
+        info!("🙂 Mouth: {}", if s.mouth_emotion > 0.0 { "SMILE" } else { "FROWN" });
+        return;
+    }
+
+    // Start + D-pad - Switch profile (Start's own video action is skipped on release)
+    if let Some(slot) = profile_slot(button) {
+        if button_tracker.start_pressed_at.take().is_some() {
+            protogen.switch_profile_slot(slot);
+            return;
+        }
+    }
+
+    // Select + R2 - Wink and smirk
+    if button_tracker.select_held && button == Button::RightTrigger2 {
+        button_tracker.select_chord_used = true;
+        state.lock().unwrap().wink_requested = true;
+        return;
+    }
+
+    // Select + L3/R3 - Save/recall the quick snapshot
+    if button_tracker.select_held
+        && (button == Button::LeftThumb || button == Button::RightThumb) {
+        button_tracker.select_chord_used = true;
+        if button == Button::LeftThumb {
+            protogen.save_snapshot(QUICK_SLOT);
+        } else {
+            protogen.recall_snapshot(QUICK_SLOT);
+        }
+        return;
+    }
+
+    // Select + face button - Play one of the first three emotes
+    if button_tracker.select_held {
+        if let Some(slot) = emote_slot(button) {
+            button_tracker.select_chord_used = true;
+            state.lock().unwrap().emote_requested = Some(slot);
+            return;
+        }
+    }
+
+    // Track Start button press time for long press detection
+    // (not when it's part of the Select + Start undo chord)
+    if button == Button::Start && !button_tracker.select_held {
+        button_tracker.start_pressed_at = Some(Instant::now());
+    }
+
+    if button == Button::LeftTrigger2 || button == Button::RightTrigger2 {
+        button_tracker.set_trigger_held(button == Button::LeftTrigger2, true);
+    }
+
+    let mut s = state.lock().unwrap();
+    if s.macro_mode && is_recordable(button) && !button_tracker.macros.record(button) {
+        info!("⏺️  Macro is full ({} presses), Select + a face button to save it", MAX_MACRO_LEN);
+    }
+    match button {
+        // Face buttons
+        Button::South => {  // A/X button - Toggle mic mute
+            s.mic_muted = !s.mic_muted;
+            info!("🎤 Microphone {}", if s.mic_muted { "MUTED" } else { "ACTIVE" });
+        }
+        Button::East => {   // B/Circle button - Toggle manual mouth mode
+            s.manual_mouth_mode = !s.manual_mouth_mode;
+            info!("👄 Manual mouth mode {}", if s.manual_mouth_mode { "ON" } else { "OFF" });
+        }
+        Button::North => {  // Y/Triangle button - Toggle blinking
+            s.blink_enabled = !s.blink_enabled;
+            info!("👁️  Blinking {}", if s.blink_enabled { "ON" } else { "OFF" });
+        }
+        Button::West => {   // X/Square button - Cycle color palette
+            s.record("palette");
+            s.color_palette = s.color_palette.next();
+            info!("🎨 Color: {}", s.color_palette.name());
+        }
+
+        // D-Pad for brightness and eye cycling
+        Button::DPadUp => {
+            s.record("brightness");
+            let brightness = s.brightness + 0.1;
+            s.set_brightness(brightness);
+            info!("🔆 Brightness: {:.0}%", s.brightness * 100.0);
+        }
+        Button::DPadDown => {
+            s.record("brightness");
+            let brightness = s.brightness - 0.1;
+            s.set_brightness(brightness);
+            info!("🔅 Brightness: {:.0}%", s.brightness * 100.0);
+        }
+        Button::DPadRight => {
+            drop(s); // Release lock before calling protogen
+            protogen.cycle_eyes_forward();
+        }
+        Button::DPadLeft => {
+            drop(s); // Release lock before calling protogen
+            protogen.cycle_eyes_backward();
+        }
+
+        // Triggers removed - now using analog axis for smooth control
+
+        Button::Select => {
+            // Handled on release so Select + Start can be used for undo
+            button_tracker.select_held = true;
+            button_tracker.select_pressed_at = Some(Instant::now());
+        }
+
+        Button::RightThumb => {  // Right stick click - Cycle mouth style
+            drop(s); // Release lock before calling protogen
+            protogen.cycle_mouth();
+        }
+
+        Button::LeftThumb => {   // Left stick click - Toggle blep (tongue out)
+            drop(s); // Release lock before calling protogen
+            protogen.handle_element_button(button);
+        }
+
+        Button::Mode => {
+            if button_tracker.select_held {
+                // Select + Home/Guide - Start the calibration wizard
+                // (back to the face first, the prompts are drawn on it)
+                button_tracker.select_chord_used = true;
+                s.calibration_requested = true;
+                if s.video_mode {
+                    s.video_action = VideoAction::ExitVideo;
+                }
+                if s.slideshow_mode {
+                    s.slideshow_action = SlideshowAction::Exit;
+                }
+            } else {
+                // Home/Guide button - Toggle glitch effect
+                s.glitch_enabled = !s.glitch_enabled;
+                info!("📟 Glitch effect {}", if s.glitch_enabled { "ON" } else { "OFF" });
+            }
+        }
+
+        // Bumpers for the image slideshow
+        Button::RightTrigger => {  // R1 - Start slideshow / next image
+            if s.slideshow_mode {
+                s.slideshow_action = SlideshowAction::NextImage;
+                info!("🖼️  Next image");
+            } else {
+                s.slideshow_action = SlideshowAction::Start;
+                info!("🖼️  Starting slideshow");
+            }
+        }
+        Button::LeftTrigger => {   // L1 - Exit slideshow
+            if s.slideshow_mode {
+                s.slideshow_action = SlideshowAction::Exit;
+                info!("🖼️  Exiting slideshow");
+            }
+        }
+
+        // Start button is handled on release to detect short vs long press
+        Button::Start => {
+            if button_tracker.select_held {
+                // Select + Start - Undo the last palette/brightness change
+                button_tracker.select_chord_used = true;
+                match s.undo() {
+                    Some(snapshot) => info!("↩️  Undo {}: {} at {:.0}%",
+                                            snapshot.action, s.color_palette.name(),
+                                            s.brightness * 100.0),
+                    None => info!("↩️  Nothing to undo"),
+                }
+            }
+            // Otherwise do nothing on press, wait for release
+        }
+
+        _ => {}
+    }
+}
+
+// Left trigger position (0.0 closed to 1.0 fully open) drives the manual mouth
+fn set_mouth_analog(state: &Arc<Mutex<MaskState>>, button_tracker: &mut ButtonTracker,
+                    analog_value: f64) {
+    button_tracker.set_trigger_held(true, analog_value > TRIGGER_HELD_THRESHOLD);
+    let mut s = state.lock().unwrap();
+    s.mouth_analog_value = analog_value;
+    // Only print when in manual mouth mode
+    if s.manual_mouth_mode {
+        debug!("👄 Mouth analog: {:.2}", analog_value);
+    }
+}
+
+// Trait for objects that can cycle eyes (and mouth styles) and pass buttons to elements
 pub trait CycleEyes {
     fn cycle_eyes_forward(&mut self);
     fn cycle_eyes_backward(&mut self);
+    fn cycle_mouth(&mut self);
+    fn handle_element_button(&mut self, button: Button) -> bool;
+    fn save_snapshot(&mut self, slot: &str);
+    fn recall_snapshot(&mut self, slot: &str);
+    fn switch_profile_slot(&mut self, slot: usize);
 }
 
 /// Print the gamepad control mapping to console
 pub fn print_control_mapping() {
-    println!("\n📋 Gamepad Controls:");
-    println!("  A/X       - Toggle microphone mute");
-    println!("  B/Circle  - Toggle manual breathing");
-    println!("  Y/Triangle- Toggle blinking");
-    println!("  X/Square  - Cycle color palette");
-    println!("  D-Pad ↑↓  - Adjust brightness");
-    println!("  D-Pad ←→  - Cycle eye styles");
-    println!("  L Trigger - Open mouth (hold)");
-    println!("  R Trigger - Close mouth (hold)");
-    println!("  Start (short) - Play video / Next video");
-    println!("  Start (long)  - Exit video mode\n");
+    info!("📋 Gamepad Controls:");
+    info!("  A/X       - Toggle microphone mute");
+    info!("  B/Circle  - Toggle manual breathing");
+    info!("  Y/Triangle- Toggle blinking");
+    info!("  X/Square  - Cycle color palette");
+    info!("  D-Pad ↑↓  - Adjust brightness");
+    info!("  D-Pad ←→  - Cycle eye styles");
+    info!("  R Stick   - Move the eyes (gaze); up/down sets smile depth in manual mouth mode");
+    info!("  R Stick (click) - Cycle mouth styles");
+    info!("  L Stick (click) - Blep (tongue out)");
+    info!("  L Trigger - Open mouth (hold)");
+    info!("  R Trigger - Close mouth (hold)");
+    info!("  Start (short) - Play video / Next video");
+    info!("  Start (long)  - Exit video mode");
+    info!("  Select    - Toggle full-color / palette video");
+    info!("  Select + Start - Undo last palette/brightness change");
+    info!("  Home/Guide - Toggle glitch effect");
+    info!("  L2 + R2 (hold 2s) - Matrix rain");
+    info!("  Select + Home - Calibrate gamepad");
+    info!("  Select + A/B/Y - Emotes 1-3");
+    info!("  Select + X - Invert colors");
+    info!("  Select + D-Pad - Look left/right/up/down (again to center)");
+    info!("  Select + R1/L1 - Smile/frown (hold)");
+    info!("  Select + R2 - Wink and smirk");
+    info!("  Select + L3/R3 - Save/recall quick snapshot");
+    info!("  Start + D-Pad ↑→↓← - Switch to profile 1-4");
+    info!("  Select (hold 3s) - Record a macro, Select + A/B/Y/X saves it there");
+    info!("  Select + A/B/Y/X - Play the macro saved there (instead of the emote/invert)");
+    info!("  R1        - Start slideshow / Next image");
+    info!("  L1        - Exit slideshow");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_brightness_clamps_out_of_range_values() {
+        let mut state = MaskState::new();
+
+        state.set_brightness(2.0);
+        assert_eq!(state.brightness, MAX_BRIGHTNESS);
+
+        state.set_brightness(-1.0);
+        assert_eq!(state.brightness, MIN_BRIGHTNESS);
+
+        state.set_brightness(0.5);
+        assert_eq!(state.brightness, 0.5);
+
+        state.set_brightness(f64::NAN);
+        assert_eq!(state.brightness, 0.5);
+    }
+
+    #[test]
+    fn undo_restores_recent_changes_and_keeps_only_the_last_ten() {
+        let mut state = MaskState::new();
+
+        state.record("palette");
+        state.color_palette = state.color_palette.next();
+        state.record("brightness");
+        state.set_brightness(0.3);
+
+        assert_eq!(state.undo().map(|s| s.action), Some("brightness"));
+        assert_eq!(state.brightness, 1.0);
+        assert_eq!(state.undo().map(|s| s.action), Some("palette"));
+        assert_eq!(state.color_palette, ColorPalette::Forest);
+        assert!(state.undo().is_none());
+
+        for _ in 0..HISTORY_LEN + 5 {
+            state.record("brightness");
+        }
+        assert_eq!(state.history.len(), HISTORY_LEN);
+    }
+
+    #[test]
+    fn trigger_combo_times_only_while_both_triggers_are_held() {
+        let mut tracker = ButtonTracker::new();
+
+        tracker.set_trigger_held(true, true);
+        assert!(tracker.triggers_held_since.is_none());
+
+        tracker.set_trigger_held(false, true);
+        let since = tracker.triggers_held_since;
+        assert!(since.is_some());
+
+        // Repeated axis events while held must not restart the timer
+        tracker.set_trigger_held(false, true);
+        assert_eq!(tracker.triggers_held_since, since);
+
+        tracker.set_trigger_held(true, false);
+        assert!(tracker.triggers_held_since.is_none());
+    }
+
+    #[test]
+    fn trigger_axis_detects_bipolar_pads_and_applies_deadzone_and_inversion() {
+        let mut trigger = TriggerAxis::new(TriggerRange::Auto, false, 0.0, 10);
+        assert_eq!(trigger.axis_value(0, 0.5), 0.5);
+        // Resting at -1 marks pad 0 as bipolar, pad 1 stays unipolar
+        assert_eq!(trigger.axis_value(0, -1.0), 0.0);
+        assert_eq!(trigger.axis_value(0, 0.0), 0.5);
+        assert_eq!(trigger.axis_value(1, 0.0), 0.0);
+
+        let mut trigger = TriggerAxis::new(TriggerRange::Unipolar, true, 0.2, 10);
+        assert_eq!(trigger.axis_value(0, 1.0), 0.0);
+        assert_eq!(trigger.axis_value(0, 0.9), 0.0);
+        assert_eq!(trigger.axis_value(0, 0.0), 1.0);
+        assert!((trigger.button_value(0.4) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gamepad_roles_limit_bound_pads_and_leave_others_unrestricted() {
+        let roles = GamepadRoles::new(HashMap::from([(0, vec![ControlGroup::Face])]));
+
+        assert!(roles.allows(0, ControlGroup::of(Button::DPadRight)));
+        assert!(!roles.allows(0, ControlGroup::of(Button::West)));
+        assert!(roles.allows(1, ControlGroup::of(Button::West)));
+    }
+
+    #[test]
+    fn learning_records_a_full_pull_and_release() {
+        // Inverted -1..1 trigger: rests at 1.0, pulled to -1.0
+        let mut travel = AxisTravel::new(1.0);
+        assert!(!travel.observe(0.0));
+        assert!(!travel.observe(-1.0));
+        assert!(travel.observe(0.95));
+        assert_eq!(travel.learned(10), LearnedTrigger {
+            axis_code: 10,
+            range: TriggerRange::Bipolar,
+            invert: true,
+        });
+    }
 }
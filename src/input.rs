@@ -0,0 +1,243 @@
+// Input thread
+// The mask runs three threads: this one polls the gamepads, the face update thread
+// (main.rs) advances the face at the target rate, and the render thread draws the
+// latest update to the panels as fast as they refresh. A slow render (video decode,
+// bloom on a hot Pi) never delays the controls or the face's timing. Synchronization:
+// - MaskState (Arc<Mutex>) carries everything the controls set directly: brightness,
+//   palette, mouth trigger, gaze, mode flags. Each thread takes the lock a few times
+//   per event or frame and none holds it while doing anything slow, so each sees the
+//   others' changes within one poll or frame. The render thread also reports back
+//   through it (standby, the video on screen).
+// - The face itself (element registry, SharedFaceState, snapshots, profiles) is owned
+//   by the update thread alone. Gamepad actions that change it (cycle eyes, blep,
+//   snapshots, profiles) are queued as FaceCommands and applied in order at the start
+//   of the next update, the same way HTTP API commands are.
+// - Each update ends by publishing a FaceFrame: copies of SharedFaceState, the render
+//   context and the active elements. FaceFrames is the double buffer between the two
+//   face threads: the update thread swaps a finished frame in, the render thread takes
+//   the newest one and draws it. The lock only covers swapping an Arc, so neither side
+//   waits on the other's work, and a frame is never changed once published.
+// - Settings reloaded from the config (mouth trigger, button map) travel back to this
+//   thread on a second channel and are picked up on the next poll.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use gilrs::{Button, Gilrs};
+
+use crate::calibration::{ButtonMap, CalibrationWizard};
+use crate::config::save_learned_trigger;
+use crate::elements::TextElement;
+use crate::face::ProtogenFace;
+use crate::gamepad::{handle_gamepad_input, ButtonTracker, CycleEyes, GamepadRoles, MaskState,
+                     TriggerAxis};
+use tracing::{info, warn};
+
+// Pause between gamepad polls (200 Hz, well under one 30 FPS frame)
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Face changes requested by the input thread, applied on the render thread
+#[derive(Debug, Clone, PartialEq)]
+pub enum FaceCommand {
+    CycleEyesForward,
+    CycleEyesBackward,
+    CycleMouth,
+    ElementButton(Button),
+    SaveSnapshot(String),
+    RecallSnapshot(String),
+    SwitchProfile(usize),
+    CalibrationPrompt(Option<String>),  // Show (or with None, remove) the wizard's prompt
+}
+
+/// Config settings the input thread uses, sent again when the config file changes
+pub enum InputSetting {
+    MouthTrigger(TriggerAxis),
+    ButtonMap(ButtonMap),
+}
+
+/// What the input thread starts with
+pub struct InputOptions {
+    pub mouth_trigger: TriggerAxis,
+    pub button_map: ButtonMap,
+    pub roles: GamepadRoles,
+    pub trigger_deadzone: f64,     // Applied to a trigger learned by the calibration wizard
+    pub config_path: String,       // Where a learned trigger is saved
+    pub gamepad_map_path: String,  // Where the calibration wizard saves the button map
+    pub calibrate: bool,           // Start in the calibration wizard
+}
+
+/// Handle to the running input thread, owned by the render loop
+pub struct InputThread {
+    commands: Receiver<FaceCommand>,
+    settings: Sender<InputSetting>,
+    handle: JoinHandle<()>,
+    prompt: Option<String>,  // Calibration prompt kept on the face while the wizard runs
+}
+
+impl InputThread {
+    /// Move the gamepads to a new thread that polls them until `shutdown` is set
+    pub fn spawn(mut gilrs: Gilrs, state: Arc<Mutex<MaskState>>, options: InputOptions,
+                 shutdown: Arc<AtomicBool>) -> Self {
+        let (command_tx, commands) = mpsc::channel();
+        let (settings, settings_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let mut face = FaceCommandQueue { tx: command_tx };
+            let mut button_tracker = ButtonTracker::new();
+            let mut mouth_trigger = options.mouth_trigger;
+            let mut button_map = options.button_map;
+            let mut calibration = None;
+            if options.calibrate {
+                calibration = Some(start_calibration(&face));
+            }
+
+            while !shutdown.load(Ordering::Relaxed) {
+                for setting in settings_rx.try_iter() {
+                    match setting {
+                        InputSetting::MouthTrigger(trigger) => mouth_trigger = trigger,
+                        InputSetting::ButtonMap(map) => button_map = map,
+                    }
+                }
+
+                // Handle gamepad input, or feed it to the calibration wizard
+                if std::mem::take(&mut state.lock().unwrap().calibration_requested) {
+                    calibration = Some(start_calibration(&face));
+                }
+                if let Some(wizard) = calibration.as_mut() {
+                    if update_calibration(wizard, &mut gilrs, &face) {
+                        if let Some(learned) = wizard.learned_trigger() {
+                            mouth_trigger = TriggerAxis::new(learned.range, learned.invert,
+                                                             options.trigger_deadzone,
+                                                             learned.axis_code);
+                            if let Err(e) = save_learned_trigger(&options.config_path, &learned) {
+                                warn!("⚠️  Could not save trigger mapping to {}: {}",
+                                      options.config_path, e);
+                            }
+                        }
+                        match wizard.save(&options.gamepad_map_path) {
+                            Ok(map) => button_map = map,
+                            Err(e) => warn!("⚠️  Could not save gamepad map {}: {}",
+                                            options.gamepad_map_path, e),
+                        }
+                        calibration = None;
+                    }
+                } else {
+                    handle_gamepad_input(&mut gilrs, &state, &mut face, &mut button_tracker,
+                                         &mut mouth_trigger, &button_map, &options.roles);
+                }
+
+                thread::sleep(INPUT_POLL_INTERVAL);
+            }
+        });
+        Self { commands, settings, handle, prompt: None }
+    }
+
+    /// Pass a reloaded setting to the input thread
+    pub fn update(&self, setting: InputSetting) {
+        let _ = self.settings.send(setting);
+    }
+
+    /// Wait for the thread to notice the shutdown flag and stop
+    pub fn join(self) {
+        if self.handle.join().is_err() {
+            warn!("⚠️  Input thread panicked");
+        }
+    }
+}
+
+/// Apply queued face commands in the order they were made (called once per frame from
+/// the render loop)
+pub fn handle_face_commands(input: &mut InputThread, protogen: &mut ProtogenFace) {
+    let commands: Vec<FaceCommand> = input.commands.try_iter().collect();
+    for command in commands {
+        match command {
+            FaceCommand::CycleEyesForward => protogen.cycle_eyes_forward(),
+            FaceCommand::CycleEyesBackward => protogen.cycle_eyes_backward(),
+            FaceCommand::CycleMouth => protogen.cycle_mouth(),
+            FaceCommand::ElementButton(button) => {
+                protogen.handle_element_button(button);
+            }
+            FaceCommand::SaveSnapshot(slot) => protogen.save_snapshot(&slot),
+            FaceCommand::RecallSnapshot(slot) => protogen.recall_snapshot(&slot),
+            FaceCommand::SwitchProfile(slot) => protogen.switch_profile_slot(slot),
+            FaceCommand::CalibrationPrompt(prompt) => {
+                protogen.remove_element("Text");
+                input.prompt = prompt;
+            }
+        }
+    }
+    // Put the prompt back if something else (e.g. POST /text) replaced it
+    if let Some(prompt) = &input.prompt {
+        if protogen.get_element::<TextElement>("Text").is_none() {
+            protogen.add_element(Box::new(TextElement::scrolling(prompt)));
+        }
+    }
+}
+
+// Stands in for the face on the input thread: every call becomes a FaceCommand
+struct FaceCommandQueue {
+    tx: Sender<FaceCommand>,
+}
+
+impl FaceCommandQueue {
+    fn send(&self, command: FaceCommand) {
+        // Only fails once the render loop is gone, during shutdown
+        let _ = self.tx.send(command);
+    }
+}
+
+impl CycleEyes for FaceCommandQueue {
+    fn cycle_eyes_forward(&mut self) {
+        self.send(FaceCommand::CycleEyesForward);
+    }
+
+    fn cycle_eyes_backward(&mut self) {
+        self.send(FaceCommand::CycleEyesBackward);
+    }
+
+    fn cycle_mouth(&mut self) {
+        self.send(FaceCommand::CycleMouth);
+    }
+
+    // Whether an element used the button is only known on the render thread
+    fn handle_element_button(&mut self, button: Button) -> bool {
+        self.send(FaceCommand::ElementButton(button));
+        true
+    }
+
+    fn save_snapshot(&mut self, slot: &str) {
+        self.send(FaceCommand::SaveSnapshot(slot.to_string()));
+    }
+
+    fn recall_snapshot(&mut self, slot: &str) {
+        self.send(FaceCommand::RecallSnapshot(slot.to_string()));
+    }
+
+    fn switch_profile_slot(&mut self, slot: usize) {
+        self.send(FaceCommand::SwitchProfile(slot));
+    }
+}
+
+// New wizard with its first prompt on the face
+fn start_calibration(face: &FaceCommandQueue) -> CalibrationWizard {
+    let wizard = CalibrationWizard::new();
+    face.send(FaceCommand::CalibrationPrompt(Some(wizard.prompt())));
+    wizard
+}
+
+/// Advance the calibration wizard and keep its prompt on the panel
+/// Returns true once every step is done (the prompt is removed)
+fn update_calibration(wizard: &mut CalibrationWizard, gilrs: &mut Gilrs,
+                      face: &FaceCommandQueue) -> bool {
+    let changed = wizard.poll(gilrs);
+    if wizard.is_finished() {
+        face.send(FaceCommand::CalibrationPrompt(None));
+        info!("🎯 Gamepad calibration finished");
+        return true;
+    }
+    if changed {
+        face.send(FaceCommand::CalibrationPrompt(Some(wizard.prompt())));
+    }
+    false
+}
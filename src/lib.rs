@@ -0,0 +1,53 @@
+// Protogen face library
+// Everything that draws and drives the face, shared by the mask binary (main.rs) and the
+// examples: elements, effects, audio, gamepad input, config and the HTTP API. The binary
+// adds the LED matrix loop on top; examples can render to a VirtualLedCanvas instead.
+
+pub mod animation;
+pub mod api;
+pub mod audio;
+pub mod battery;
+pub mod ble;
+pub mod calibration;
+pub mod canvas;
+pub mod cli;
+pub mod color;
+pub mod config;
+pub mod config_watch;
+pub mod debug_overlay;
+pub mod effects;
+pub mod elements;
+pub mod emote;
+pub mod expression;
+pub mod face;
+pub mod font;
+pub mod gamepad;
+pub mod input;
+pub mod logging;
+pub mod macros;
+pub mod metrics;
+pub mod osc;
+pub mod pacing;
+pub mod profile;
+pub mod quality;
+pub mod panel_test;
+pub mod panic_guard;
+pub mod rng;
+pub mod schedule;
+pub mod slideshow;
+pub mod standby;
+pub mod snapshot;
+pub mod stream;
+pub mod systemd;
+pub mod telemetry;
+pub mod thermal;
+pub mod video;
+pub mod wifi;
+
+// Hardware constants
+pub const PANEL_WIDTH: i32 = 64;
+pub const PANEL_HEIGHT: i32 = 32;
+
+// Microphone constants (matching Arduino code)
+pub const MOUTH_MAX_OPENING: f64 = 6.0;
+pub const IDLE_TIMEOUT_SECS: u64 = 30; // Switch to breathing after 30 seconds of silence
@@ -0,0 +1,242 @@
+// Expression log
+// With `expression_logging = true` every eye switch, mouth or mouth mode change, palette
+// change, video start and accessory activation is appended to a CSV file as
+// `timestamp_ms,event_type,old_value,new_value`, for looking back at how the mask got used
+// at an event (which eyes people liked, how long a palette stayed on). Changes come from
+// the gamepad, the API, OSC, sequences and the schedule, so instead of hooking each of
+// them the render loop hands over what the face shows once per frame and the logger writes
+// a row for everything that differs from the frame before.
+//
+// Frame log
+// `--frame-log <PATH>` writes one JSON line per rendered frame (mouth opening, eyelids,
+// eyes, audio level, mode) plus a line for every expression change, to answer "why did
+// the mouth do that" without a panel attached. Lines are buffered and flushed about once
+// a second so logging doesn't cost frame rate.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde_json::json;
+
+use crate::telemetry::FaceTelemetry;
+
+/// Directory the expression log is written to
+pub const EXPRESSION_LOG_DIR: &str = "/var/log/protogen";
+
+const EXPRESSION_LOG_FILE: &str = "expressions.csv";
+const CSV_HEADER: &str = "timestamp_ms,event_type,old_value,new_value";
+
+// How often buffered frame log lines are written out
+const FRAME_LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the face shows in one frame, as far as the log cares
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpressionState {
+    pub eyes: String,
+    pub mouth: String,
+    pub mouth_mode: String,        // MIC, MANUAL or BREATHING
+    pub palette: String,
+    pub video: Option<String>,     // File name while a video plays
+    pub accessories: Vec<String>,  // Accessory elements currently shown
+}
+
+/// One row of the log (without the timestamp)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionEvent {
+    pub event_type: &'static str,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl ExpressionEvent {
+    fn new(event_type: &'static str, old_value: &str, new_value: &str) -> Self {
+        Self { event_type, old_value: old_value.to_string(), new_value: new_value.to_string() }
+    }
+}
+
+/// Appends expression changes to the CSV log
+pub struct ExpressionLogger {
+    file: File,
+    last: Option<ExpressionState>,
+}
+
+impl ExpressionLogger {
+    /// Open the log in EXPRESSION_LOG_DIR
+    pub fn open() -> io::Result<Self> {
+        Self::open_in(Path::new(EXPRESSION_LOG_DIR))
+    }
+
+    /// Open (or start) the log in a directory, creating it if needed; a new file gets the
+    /// CSV header first, an existing one is appended to
+    pub fn open_in(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let mut file = OpenOptions::new().create(true).append(true)
+            .open(dir.join(EXPRESSION_LOG_FILE))?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+        Ok(Self { file, last: None })
+    }
+
+    /// Compare a frame with the one before and append a row per change (the first frame
+    /// only sets the starting point)
+    pub fn observe(&mut self, state: ExpressionState) -> io::Result<()> {
+        let events = match &self.last {
+            Some(last) if *last == state => return Ok(()),
+            Some(last) => changes(last, &state),
+            None => Vec::new(),
+        };
+        self.last = Some(state);
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let timestamp_ms = unix_ms();
+        let rows: String = events.iter()
+            .map(|event| format!("{},{},{},{}\n", timestamp_ms, event.event_type,
+                                 csv_field(&event.old_value), csv_field(&event.new_value)))
+            .collect();
+        self.file.write_all(rows.as_bytes())
+    }
+}
+
+/// Writes the per-frame JSONL debug log
+pub struct FrameLogger {
+    writer: BufWriter<File>,
+    start: Instant,
+    last_flush: Instant,
+    frame: u64,
+    last: Option<ExpressionState>,
+}
+
+impl FrameLogger {
+    /// Create (or truncate) the log file and write a start line with the wall-clock time
+    pub fn create(path: &str) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "{}", json!({ "event": "start", "unix_ms": unix_ms() }))?;
+        let now = Instant::now();
+        Ok(Self { writer, start: now, last_flush: now, frame: 0, last: None })
+    }
+
+    /// Log one rendered frame: a line for each expression change since the last frame,
+    /// then the frame itself. `eyelids` are the top and bottom eyelid positions (9.0 and
+    /// 1.45 with the eyes open, 0.1 and 7.0 with them shut)
+    pub fn log(&mut self, telemetry: &FaceTelemetry, eyelids: (f64, f64),
+               state: ExpressionState) -> io::Result<()> {
+        let t = (self.start.elapsed().as_secs_f64() * 1000.0).round() / 1000.0;
+        if let Some(last) = &self.last {
+            for event in changes(last, &state) {
+                writeln!(self.writer, "{}", json!({
+                    "frame": self.frame,
+                    "t": t,
+                    "event": event.event_type,
+                    "old": event.old_value,
+                    "new": event.new_value,
+                }))?;
+            }
+        }
+        self.last = Some(state);
+
+        writeln!(self.writer, "{}", json!({
+            "frame": self.frame,
+            "t": t,
+            "mode": telemetry.mode.name(),
+            "audio_level": telemetry.audio_level,
+            "mouth_opening": telemetry.mouth_opening,
+            "eye_top": eyelids.0,
+            "eye_bottom": eyelids.1,
+            "eyes": telemetry.active_eyes,
+        }))?;
+        self.frame += 1;
+
+        if self.last_flush.elapsed() >= FRAME_LOG_FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+/// Events between two frames, in a fixed order (eyes, mouth, mouth mode, palette, video,
+/// accessories); stopping a video or hiding an accessory isn't logged
+pub fn changes(old: &ExpressionState, new: &ExpressionState) -> Vec<ExpressionEvent> {
+    let mut events = Vec::new();
+    if old.eyes != new.eyes {
+        events.push(ExpressionEvent::new("eyes", &old.eyes, &new.eyes));
+    }
+    if old.mouth != new.mouth {
+        events.push(ExpressionEvent::new("mouth", &old.mouth, &new.mouth));
+    }
+    if old.mouth_mode != new.mouth_mode {
+        events.push(ExpressionEvent::new("mouth_mode", &old.mouth_mode, &new.mouth_mode));
+    }
+    if old.palette != new.palette {
+        events.push(ExpressionEvent::new("palette", &old.palette, &new.palette));
+    }
+    if let Some(video) = new.video.as_deref().filter(|video| old.video.as_deref() != Some(*video)) {
+        events.push(ExpressionEvent::new("video", old.video.as_deref().unwrap_or(""), video));
+    }
+    for accessory in new.accessories.iter().filter(|name| !old.accessories.contains(name)) {
+        events.push(ExpressionEvent::new("accessory", "", accessory));
+    }
+    events
+}
+
+// Milliseconds since the Unix epoch
+fn unix_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// Quote a value that contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face(eyes: &str, palette: &str, video: Option<&str>, accessories: &[&str]) -> ExpressionState {
+        ExpressionState {
+            eyes: eyes.to_string(),
+            mouth: "Default Mouth".to_string(),
+            mouth_mode: "MIC".to_string(),
+            palette: palette.to_string(),
+            video: video.map(str::to_string),
+            accessories: accessories.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn changes_are_appended_as_csv_rows() {
+        let dir = std::env::temp_dir().join(format!("protogen_expressions_{}", std::process::id()));
+        let mut logger = ExpressionLogger::open_in(&dir).unwrap();
+
+        logger.observe(face("Default Eyes", "Forest (Green)", None, &[])).unwrap();
+        logger.observe(face("Default Eyes", "Forest (Green)", None, &[])).unwrap();
+        logger.observe(face("Heart Eyes", "Fire (Red/Orange)", None, &["Sparkle"])).unwrap();
+        logger.observe(face("Heart Eyes", "Fire (Red/Orange)", Some("intro.mp4"), &[])).unwrap();
+
+        let contents = fs::read_to_string(dir.join(EXPRESSION_LOG_FILE)).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        let rows: Vec<Vec<&str>> = contents.lines()
+            .map(|line| line.splitn(2, ',').collect())
+            .collect();
+        assert_eq!(rows[0], vec!["timestamp_ms", "event_type,old_value,new_value"]);
+        let events: Vec<&str> = rows[1..].iter().map(|row| row[1]).collect();
+        assert_eq!(events, vec![
+            "eyes,Default Eyes,Heart Eyes",
+            "palette,Forest (Green),Fire (Red/Orange)",
+            "accessory,,Sparkle",
+            "video,,intro.mp4",
+        ]);
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
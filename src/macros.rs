@@ -0,0 +1,132 @@
+// Button macros
+// Hold Select for 3 seconds to start recording, press up to MAX_MACRO_LEN buttons (they
+// still do their normal thing while recording), then Select + a face button to keep the
+// sequence in that slot. Select + the same face button replays it later, one button per
+// input poll. Saving an empty recording clears the slot, which gives the face button its
+// usual Select chord back. Macros are kept in the state file next to the snapshots.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io;
+use gilrs::Button;
+
+use crate::calibration::{button_by_name, button_name};
+use crate::snapshot::{load_state_key, save_state_key};
+use tracing::warn;
+
+/// Number of macro slots (one per face button)
+pub const MACRO_SLOTS: usize = 4;
+
+/// Most button presses one macro keeps
+pub const MAX_MACRO_LEN: usize = 10;
+
+/// Recorded macros, the recording in progress and the playback queue
+#[derive(Debug)]
+pub struct MacroRecorder {
+    path: String,
+    slots: [Vec<Button>; MACRO_SLOTS],
+    recording: Vec<Button>,
+    playback: VecDeque<Button>,
+}
+
+impl MacroRecorder {
+    /// Load macros from the state file (stored as slot number -> button names)
+    pub fn load(path: &str) -> Self {
+        let mut slots: [Vec<Button>; MACRO_SLOTS] = Default::default();
+        let stored = load_state_key(path, "macros")
+            .and_then(|macros| {
+                serde_json::from_value::<BTreeMap<usize, Vec<String>>>(macros)
+                    .map_err(|e| warn!("⚠️  Could not read macros from {}: {}", path, e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        for (slot, names) in stored {
+            if let Some(buttons) = slots.get_mut(slot) {
+                *buttons = names.iter().filter_map(|name| button_by_name(name)).collect();
+            }
+        }
+        Self {
+            path: path.to_string(),
+            slots,
+            recording: Vec::new(),
+            playback: VecDeque::new(),
+        }
+    }
+
+    /// Forget the presses recorded so far
+    pub fn start_recording(&mut self) {
+        self.recording.clear();
+    }
+
+    /// Add a press to the recording, returns false once the macro is full
+    pub fn record(&mut self, button: Button) -> bool {
+        if self.recording.len() >= MAX_MACRO_LEN {
+            return false;
+        }
+        self.recording.push(button);
+        true
+    }
+
+    /// Keep the recording in a slot and write the state file, returns its length
+    pub fn finish_recording(&mut self, slot: usize) -> io::Result<usize> {
+        self.slots[slot] = std::mem::take(&mut self.recording);
+        let stored: BTreeMap<usize, Vec<&str>> = self.slots.iter().enumerate()
+            .filter(|(_, buttons)| !buttons.is_empty())
+            .map(|(slot, buttons)| (slot, buttons.iter().filter_map(|b| button_name(*b)).collect()))
+            .collect();
+        save_state_key(&self.path, "macros", serde_json::to_value(stored)?)?;
+        Ok(self.slots[slot].len())
+    }
+
+    pub fn has_macro(&self, slot: usize) -> bool {
+        self.slots.get(slot).is_some_and(|buttons| !buttons.is_empty())
+    }
+
+    /// Queue a slot's presses for playback (after anything still playing)
+    pub fn play(&mut self, slot: usize) {
+        if let Some(buttons) = self.slots.get(slot) {
+            self.playback.extend(buttons.iter().copied());
+        }
+    }
+
+    /// Next press to replay, one per input poll
+    pub fn next_playback(&mut self) -> Option<Button> {
+        self.playback.pop_front()
+    }
+}
+
+/// Whether a button can go into a macro (Select and Start drive recording and video,
+/// the analog triggers have no single press to replay)
+pub fn is_recordable(button: Button) -> bool {
+    button != Button::Select && button != Button::Start && button_name(button).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_macros_are_capped_saved_and_replayed() {
+        let path = std::env::temp_dir().join(format!("protogen_macros_{}.json",
+                                                     std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut macros = MacroRecorder::load(path);
+        macros.start_recording();
+        for _ in 0..MAX_MACRO_LEN {
+            assert!(macros.record(Button::DPadUp));
+        }
+        assert!(!macros.record(Button::West));
+        assert_eq!(macros.finish_recording(2).unwrap(), MAX_MACRO_LEN);
+
+        let mut loaded = MacroRecorder::load(path);
+        let _ = std::fs::remove_file(path);
+        assert!(loaded.has_macro(2));
+        assert!(!loaded.has_macro(0));
+        loaded.play(2);
+        let replayed: Vec<Button> = std::iter::from_fn(|| loaded.next_playback()).collect();
+        assert_eq!(replayed, vec![Button::DPadUp; MAX_MACRO_LEN]);
+
+        assert!(!is_recordable(Button::Select));
+        assert!(is_recordable(Button::West));
+    }
+}
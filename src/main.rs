@@ -1,164 +1,925 @@
-// Module declarations
-mod audio;
-mod color;
-mod elements;
-mod face;
-mod gamepad;
-mod video;
-
 use rpi_led_matrix::{LedMatrix, LedMatrixOptions, LedCanvas, LedColor};
-use std::thread;
 use std::time::{Duration, Instant};
 use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::any::Any;
-use gilrs::{Gilrs, Button};
+use std::path::Path;
+use gilrs::{GilrsBuilder, Button};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use tracing::{error, info, info_span, warn};
+use tracing_subscriber::EnvFilter;
+
+use pi_mask_test::{animation, api, audio, battery, ble, calibration, canvas, cli, color, config,
+                   config_watch, effects, elements, face, gamepad, input, logging, metrics, osc, pacing,
+                   panel_test, panic_guard, profile, rng, schedule, slideshow, standby, stream,
+                   systemd, telemetry, thermal, video, wifi};
+use pi_mask_test::{PANEL_WIDTH, PANEL_HEIGHT, IDLE_TIMEOUT_SECS};
 
 // Re-export from modules
-use audio::{AudioLevel, start_audio_capture, SILENT_LIMIT};
-use color::ColorPalette;
+use animation::TimedSequencer;
+use api::{ApiServer, handle_api_commands};
+use audio::{AudioLevel, AudioSynthesizer, SynthMode, start_audio_capture, MIC_CALIBRATION_TIME,
+            SILENT_LIMIT};
+use calibration::ButtonMap;
+use canvas::VirtualLedCanvas;
+use cli::CliArgs;
+use color::{ColorOrder, ColorPalette, get_palette_color_for_luminance, get_shimmer_color,
+            luminance};
+use config::{Config, save_learned_trigger};
+use config_watch::{ConfigReload, ConfigWatcher, RESTART_KEYS, apply_face_setting};
+use effects::FadeTransition;
+use elements::{StatusIndicators, StatusIndicatorElement, TemperatureWarningElement,
+               BatteryWarningElement, WiFiSignalIndicator};
+use elements::indicator::{NO_AUDIO_DEVICE, API_UNAVAILABLE};
 use face::ProtogenFace;
-use gamepad::{MaskState, handle_gamepad_input, ButtonTracker, VideoAction, print_control_mapping};
-use video::VideoPlayer;
+use gamepad::{MaskState, VideoAction, SlideshowAction, print_control_mapping, learn_trigger,
+              TriggerAxis};
+use input::{InputOptions, InputSetting, InputThread, handle_face_commands};
+use logging::{ExpressionLogger, ExpressionState, FrameLogger, EXPRESSION_LOG_DIR};
+use metrics::FaceMetrics;
+use pacing::FramePacer;
+use profile::PROFILES_PATH;
+use schedule::{DaySchedule, local_minute_of_day, parse_time_of_day};
+use slideshow::Slideshow;
+use standby::{Standby, STANDBY_POLL};
+use stream::FrameFeed;
+use telemetry::FaceTelemetry;
+use video::{VideoPlayer, VideoFrame, EndOfVideoPolicy};
 
-// Hardware constants
-const PANEL_WIDTH: i32 = 64;
-const PANEL_HEIGHT: i32 = 32;
+// Console status line interval
+const STATUS_INTERVAL_SECS: u64 = 5;
 
-// Microphone constants (matching Arduino code)
-const MOUTH_MAX_OPENING: f64 = 6.0;
-const IDLE_TIMEOUT_SECS: u64 = 30; // Switch to breathing after 30 seconds of silence
+// Frames rendered by --dry-run
+const DRY_RUN_FRAMES: usize = 3;
+
+// How long --learn-trigger waits for the trigger to be pulled
+const TRIGGER_LEARN_TIMEOUT: Duration = Duration::from_secs(15);
 
 // ============================================================================
 // MAIN ENTRY POINT
 // ============================================================================
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Logging: level filter from RUST_LOG (e.g. RUST_LOG=debug), info by default
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let args = CliArgs::parse();
+    if args.dry_run {
+        return dry_run(&args);
+    }
+    let mut config = Config::load_or_default(&args.config_path);
+    if args.test_panel {
+        return panel_test::run(&LedMatrix::new(Some(matrix_options()), None)?, config.color_order);
+    }
+    rng::init(args.seed.or(config.rng_seed));
+
+    // Problems raised here are shown on the face by the status indicator
+    let indicators = StatusIndicators::new();
+
     // Initialize audio capture
     let audio_level = Arc::new(AudioLevel::new());
 
-    println!("Initializing microphone...");
-    let _stream = match start_audio_capture(audio_level.clone()) {
+    info!("Initializing microphone...");
+    let _stream = match start_audio_capture(audio_level.clone(), indicators.clone()) {
         Ok(stream) => {
-            println!("✅ Microphone initialized successfully!");
+            info!("✅ Microphone initialized successfully!");
+            if args.skip_calibration {
+                info!("🎚️  Microphone calibration skipped");
+            } else {
+                audio_level.start_calibration(MIC_CALIBRATION_TIME);
+            }
             Some(stream)
         }
         Err(e) => {
-            eprintln!("⚠️  Warning: Could not initialize microphone: {}", e);
-            eprintln!("Will use breathing animation only.");
+            warn!("⚠️  Could not initialize microphone: {}", e);
+            warn!("Will use breathing animation only.");
+            indicators.show(NO_AUDIO_DEVICE);
             None
         }
     };
 
-    // Initialize gamepad
-    let mut gilrs = Gilrs::new().unwrap();
-    let mask_state = Arc::new(Mutex::new(MaskState::new()));
-    let mut button_tracker = ButtonTracker::new();
+    // Initialize gamepad (config mappings are added on top of gilrs's built-in SDL database)
+    let mut gilrs = GilrsBuilder::new()
+        .add_mappings(&config.gamepad_mappings.join("\n"))
+        .build()
+        .unwrap();
+    if !config.gamepad_mappings.is_empty() {
+        info!("🎮 Added {} gamepad mapping(s) from config", config.gamepad_mappings.len());
+    }
+    let mask_state = Arc::new(Mutex::new(initial_mask_state(&config)));
+    let mut mouth_trigger = config.mouth_trigger();
+    let gamepad_roles = config.gamepad_roles();
 
     // Check for connected gamepads
-    println!("\n🎮 Gamepad Status:");
+    info!("🎮 Gamepad Status:");
     let mut gamepad_found = false;
     let mut gamepad_id = None;
     for (id, gamepad) in gilrs.gamepads() {
-        println!("  Connected: {} (ID: {:?}, Power: {:?})", gamepad.name(), id, gamepad.power_info());
-        println!("  Mapping: {:?}", gamepad.mapping_source());
+        info!("  Connected: {} (ID: {:?}, Power: {:?}, {})", gamepad.name(), id, gamepad.power_info(),
+              gamepad_roles.describe(usize::from(id)));
+        info!("  Mapping: {:?}", gamepad.mapping_source());
         gamepad_found = true;
         gamepad_id = Some(id);
     }
     if !gamepad_found {
-        println!("  ⚠️  No gamepad detected. Controls disabled.");
-        println!("  Tip: Connect a Bluetooth gamepad and pair it before starting.");
-        println!("  Debug: Check 'ls /dev/input/' and permissions");
+        warn!("  ⚠️  No gamepad detected. Controls disabled.");
+        info!("  Tip: Connect a Bluetooth gamepad and pair it before starting.");
+        info!("  Debug: Check 'ls /dev/input/' and permissions");
     } else {
-        println!("  ✅ Gamepad ready! Press any button to test...");
+        info!("  ✅ Gamepad ready! Press any button to test...");
+    }
+
+    // Optional calibration for triggers the mapping doesn't recognize
+    if args.learn_trigger {
+        if let Some(learned) = learn_trigger(&mut gilrs, TRIGGER_LEARN_TIMEOUT) {
+            mouth_trigger = TriggerAxis::new(learned.range, learned.invert,
+                                             config.mouth_trigger_deadzone, learned.axis_code);
+            match save_learned_trigger(&args.config_path, &learned) {
+                Ok(()) => info!("💾 Saved trigger mapping to {}", args.config_path),
+                Err(e) => warn!("⚠️  Could not save trigger mapping to {}: {}", args.config_path, e),
+            }
+        }
+    }
+
+    // Start HTTP control API
+    let metrics = Arc::new(FaceMetrics::new());
+    let frame_feed = Arc::new(FrameFeed::new());
+    let api_server = match ApiServer::start(args.api_port, metrics.clone(), mask_state.clone(),
+                                                frame_feed.clone()) {
+        Ok(server) => {
+            info!("🌐 HTTP API listening on port {}", args.api_port);
+            Some(server)
+        }
+        Err(e) => {
+            warn!("⚠️  Could not start HTTP API: {}", e);
+            indicators.show(API_UNAVAILABLE);
+            None
+        }
+    };
+
+    // Watch the CPU temperature for the status line and the overheating warning
+    let cpu_temperature = thermal::start_temperature_monitor();
+
+    // Follow a BLE heart rate monitor for the expression, if one is set
+    let heart_rate = config.heart_rate_device.as_deref().and_then(|address| {
+        match ble::start_heart_rate_monitor(address) {
+            Ok(heart_rate) => {
+                info!("❤️  Looking for heart rate monitor {}", address);
+                Some(heart_rate)
+            }
+            Err(e) => {
+                warn!("⚠️  Bad heart_rate_device {}: {}", address, e);
+                None
+            }
+        }
+    });
+
+    // Watch the Wi-Fi signal for the signal bars
+    let wifi_signal = wifi::start_wifi_monitor();
+
+    // Set by SIGTERM/SIGINT/SIGHUP (registered below) or a flat battery to stop cleanly
+    let shutdown_requested = Arc::new(AtomicBool::new(false));
+
+    // Watch the UPS HAT battery for the low-battery icon and shutdown
+    let battery_level = if config.show_battery {
+        match battery::start_battery_monitor(config.battery_gauge, shutdown_requested.clone()) {
+            Ok(level) => Some(level),
+            Err(e) => {
+                warn!("⚠️  Could not read the {} battery gauge on {}: {}",
+                      config.battery_gauge.name(), battery::I2C_BUS_PATH, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Start OSC receiver for VJ / music software
+    match osc::start_osc_receiver(args.osc_port, mask_state.clone()) {
+        Ok(()) => info!("🎛️  OSC listening on UDP port {}", args.osc_port),
+        Err(e) => warn!("⚠️  Could not start OSC receiver on port {}: {}", args.osc_port, e),
     }
 
     // Initialize video player
     let mut video_player = VideoPlayer::new("./videos");
+    video_player.set_end_policy(config.video_end_policy);
+    info!("🔁 Video end policy: {}", config.video_end_policy.name());
+
+    // Initialize image slideshow
+    let mut slideshow = Slideshow::new("./images", config.slideshow_dwell_secs,
+                                       config.slideshow_crossfade_secs,
+                                       config.slideshow_auto_advance);
 
     // Initialize LED matrix
-    let mut options = LedMatrixOptions::new();
-    options.set_rows(32);
-    options.set_cols(64);
-    options.set_chain_length(2);
-    options.set_hardware_mapping("adafruit-hat");
+    let matrix = LedMatrix::new(Some(matrix_options()), None)?;
+    let _panic_guard = panic_guard::install(&matrix);
+    let mut protogen = ProtogenFace::new(audio_level.clone(), mask_state.clone(), metrics.clone());
+    configure_face(&mut protogen, &config);
+    protogen.add_element(Box::new(StatusIndicatorElement::new(
+        indicators.clone(), config.indicator_style, config.indicator_x, config.indicator_y)));
+    protogen.add_element(Box::new(TemperatureWarningElement::new(cpu_temperature.clone())));
+    protogen.set_element_enabled("Temperature Warning", config.show_temp_warning);
+    protogen.set_cpu_temperature(cpu_temperature);
+    if let Some(heart_rate) = heart_rate {
+        protogen.set_heart_rate(heart_rate);
+    }
+    protogen.add_element(Box::new(WiFiSignalIndicator::new(wifi_signal)));
+    protogen.set_element_enabled("WiFi Signal", config.show_wifi);
+    if let Some(level) = battery_level {
+        protogen.add_element(Box::new(BatteryWarningElement::new(level)));
+    }
+    if args.debug_overlay {
+        protogen.set_debug_overlay(true);
+        info!("🔲 Debug overlay enabled");
+    }
+    // Start with the requested profile on top of the config
+    if let Some(name) = &args.profile {
+        if protogen.switch_profile(name) {
+            info!("🗂️  Profile: {}", name);
+        } else {
+            warn!("⚠️  No profile \"{}\" in {} (loaded: {})", name, PROFILES_PATH,
+                  protogen.list_profiles().join(", "));
+        }
+    }
 
-    let matrix = LedMatrix::new(Some(options), None)?;
-    let mut protogen = ProtogenFace::new(audio_level.clone(), mask_state.clone());
+    // Load the timed expression script, if one was given
+    let sequencer = args.sequence_path.as_deref().and_then(|path| {
+        match TimedSequencer::load(path) {
+            Ok(sequencer) => {
+                info!("📜 Loaded sequence {} ({} events)", path, sequencer.remaining());
+                Some(sequencer)
+            }
+            Err(e) => {
+                warn!("⚠️  Could not load sequence {}: {}", path, e);
+                None
+            }
+        }
+    });
 
-    println!("\n🚀 Starting animation loop...");
-    println!("Microphone threshold: {}", SILENT_LIMIT);
-    println!("Idle timeout: {} seconds", IDLE_TIMEOUT_SECS);
+    info!("🚀 Starting animation loop...");
+    info!("Microphone threshold: {}", SILENT_LIMIT);
+    info!("Idle timeout: {} seconds", IDLE_TIMEOUT_SECS);
     print_control_mapping();
 
-    // Animation loop (run indefinitely - press Ctrl+C to stop)
-    loop {
-        // Handle gamepad input (non-blocking)
-        handle_gamepad_input(&mut gilrs, &mask_state, &mut protogen, &mut button_tracker);
+    // Stop cleanly on Ctrl+C, systemd stop (SIGTERM) or terminal hangup
+    for signal in [SIGTERM, SIGINT, SIGHUP] {
+        signal_hook::flag::register(signal, shutdown_requested.clone())?;
+    }
+
+    // Gamepads are polled on their own thread from here on (see input.rs)
+    let input = InputThread::spawn(gilrs, mask_state.clone(), InputOptions {
+        mouth_trigger,
+        button_map: ButtonMap::load_or_default(&config.gamepad_map_path),
+        roles: gamepad_roles,
+        trigger_deadzone: config.mouth_trigger_deadzone,
+        config_path: args.config_path.clone(),
+        gamepad_map_path: config.gamepad_map_path.clone(),
+        calibrate: args.calibrate,
+    }, shutdown_requested.clone());
+
+    // This thread draws the face frames; hand each finished one to MJPEG stream viewers
+    // (GET /stream.mjpg)
+    let mut renderer = protogen.renderer();
+    let stream_feed = frame_feed.clone();
+    renderer.add_render_hook(move |canvas, _| stream_feed.publish(canvas));
+
+    // The face updates on its own thread from here on (see input.rs for how the threads
+    // share state)
+    let (config_reloads_tx, config_reloads) = mpsc::channel();
+    let update_thread = thread::Builder::new()
+        .name("face-update".to_string())
+        .spawn({
+            let updates = FaceUpdates {
+                protogen,
+                mask_state: mask_state.clone(),
+                input,
+                api_server,
+                sequencer,
+                target_fps: config.target_fps,
+                schedule: DaySchedule::new(&config.schedule, config.schedule_fade_minutes),
+                expression_log: open_expression_log(config.expression_logging),
+                frame_log: args.frame_log.as_deref().and_then(open_frame_log),
+                config_watcher: ConfigWatcher::new(&args.config_path),
+                config_reloads: config_reloads_tx,
+                shutdown: shutdown_requested.clone(),
+            };
+            move || run_face_updates(updates)
+        })?;
+    info!("Target frame rate: {} FPS", config.target_fps);
+    if !config.schedule.is_empty() {
+        info!("🕒 Following a schedule of {} entries", config.schedule.len());
+    }
+
+    // Face render target for semi-transparent video overlay
+    let mut overlay_buffer = VirtualLedCanvas::new(PANEL_WIDTH * 2, PANEL_HEIGHT);
+
+    // Render loop (runs until a shutdown signal is received). It isn't paced: each swap
+    // waits for the panels' next refresh, so it draws as fast as they show frames
+    let render_span = info_span!("render");
+    let mut last_frame = Instant::now();
+    let mut standby = new_standby(&config);
+    let mut video_fade = FadeTransition { target_brightness: 0.0, speed: config.fade_speed,
+                                          current: 0.0 };
+    systemd::notify_ready();
+    while !shutdown_requested.load(Ordering::Relaxed) {
+        let _enter = render_span.enter();
+        systemd::notify_watchdog();
+        if update_thread.is_finished() {
+            error!("❌ Face update thread stopped, shutting down");
+            shutdown_requested.store(true, Ordering::Relaxed);
+            break;
+        }
+        let now = Instant::now();
+        let dt = now.duration_since(last_frame).as_secs_f64();
+        last_frame = now;
+
+        // Config edits the update thread passed on (it applies the face's own settings)
+        for reload in config_reloads.try_iter() {
+            for key in &reload.changed {
+                match key.as_str() {
+                    "video_end_policy" => {
+                        video_player.set_end_policy(reload.config.video_end_policy);
+                    }
+                    "standby_after_secs" | "standby_wake_level" | "standby_fade_in_secs"
+                    | "standby_dot" => standby = new_standby(&reload.config),
+                    "fade_speed" => video_fade.speed = reload.config.fade_speed.max(0.0),
+                    _ => {}  // Read every frame (video_overlay_alpha, color_order) or not ours
+                }
+            }
+            config = reload.config;
+        }
 
-        // Handle video actions from gamepad
+        // Handle slideshow and video actions from gamepad, API and sequencer
         {
             let mut state = mask_state.lock().unwrap();
-            match state.video_action {
+            match std::mem::replace(&mut state.slideshow_action, SlideshowAction::None) {
+                SlideshowAction::Start => {
+                    if slideshow.start() {
+                        state.slideshow_mode = true;
+                    }
+                }
+                SlideshowAction::NextImage => {
+                    slideshow.next_image();
+                }
+                SlideshowAction::Exit => {
+                    slideshow.stop();
+                    state.slideshow_mode = false;
+                }
+                SlideshowAction::None => {}
+            }
+
+            match std::mem::replace(&mut state.video_action, VideoAction::None) {
                 VideoAction::PlayFirst => {
                     if video_player.play_first() {
                         state.video_mode = true;
+                        video_fade.set(0.0);
+                        video_fade.fade_in();
                     }
-                    state.video_action = VideoAction::None;
                 }
                 VideoAction::NextVideo => {
                     video_player.next_video();
-                    state.video_action = VideoAction::None;
                 }
                 VideoAction::ExitVideo => {
-                    video_player.stop();
-                    state.video_mode = false;
-                    state.video_action = VideoAction::None;
+                    // Stopped below once the video has faded out
+                    video_fade.fade_out();
+                }
+                VideoAction::Play(name) => {
+                    if video_player.play_named(&name) {
+                        state.video_mode = true;
+                        video_fade.set(0.0);
+                        video_fade.fade_in();
+                    }
+                }
+                VideoAction::Seek(frame) => {
+                    video_player.seek_to_frame(frame);
                 }
                 VideoAction::None => {}
             }
+            if state.video_mode && video_fade.target_brightness <= 0.0
+                && (video_fade.is_faded_out() || !video_player.is_playing()) {
+                video_player.stop();
+                state.video_mode = false;
+            }
+            state.video_name = video_player.current_video_name().filter(|_| state.video_mode);
         }
 
+        // Quiet standby: dark panels (or a breathing dot) and a slow audio poll until
+        // sound or a button wakes the face (face updates pause meanwhile)
+        let (woken, palette, brightness) = {
+            let mut state = mask_state.lock().unwrap();
+            (std::mem::take(&mut state.wake_requested) || state.video_mode || state.slideshow_mode,
+             state.color_palette, state.brightness)
+        };
+        let asleep = standby.update(audio_level.get_level(), woken, dt);
+        mask_state.lock().unwrap().asleep = asleep;
+        if asleep {
+            let mut canvas = matrix.offscreen_canvas();
+            draw_standby_dot(&mut canvas, standby.dot_brightness() * brightness, palette,
+                             config.color_order);
+            let _ = matrix.swap(canvas);
+            thread::sleep(STANDBY_POLL);
+            continue;
+        }
+        renderer.set_wake_fade(standby.fade());
+
         let mut canvas = matrix.offscreen_canvas();
 
-        // Render based on mode
+        // Full-screen video waits for the face to fade out, then fades in itself (and the
+        // other way round on exit); the face also fades out just before standby
         let state = mask_state.lock().unwrap();
-        if state.video_mode && video_player.is_playing() {
+        let show_video = state.video_mode && video_player.is_playing();
+        let face_hidden = show_video && config.video_overlay_alpha >= 1.0;
+        if face_hidden || standby.secs_until_sleep() < renderer.fade_secs() {
+            renderer.fade_out();
+        } else {
+            renderer.fade_in();
+        }
+
+        // Render based on mode
+        if show_video && (!face_hidden || renderer.is_faded_out()) {
             // Video mode - render video frame (mirrored on both 64x32 panels)
+            let fade = video_fade.update(dt);
             if let Some(frame) = video_player.next_frame(64, 32) {
-                // Apply brightness
-                let brightness = (state.brightness * 255.0) as u8;
-
-                // Draw video frame mirrored on both panels
-                for y in 0..32 {
-                    for x in 0..64 {
-                        let (r, g, b) = frame.get_pixel(x, y);
-                        let r = ((r as u16 * brightness as u16) / 255) as u8;
-                        let g = ((g as u16 * brightness as u16) / 255) as u8;
-                        let b = ((b as u16 * brightness as u16) / 255) as u8;
-                        let color = LedColor { red: r, green: g, blue: b };
-
-                        // Draw on left panel
-                        canvas.set(x as i32, y as i32, &color);
-                        // Mirror on right panel
-                        canvas.set((x + 64) as i32, y as i32, &color);
-                    }
+                if config.video_overlay_alpha < 1.0 {
+                    // Semi-transparent video over the animated face
+                    let brightness = state.brightness;
+                    drop(state);
+                    renderer.render_to_virtual(&mut overlay_buffer);
+                    frame.overlay_on_canvas(&mut overlay_buffer, config.video_overlay_alpha * fade,
+                                            brightness);
+                    overlay_buffer.blit_to(&mut canvas, config.color_order);
+                } else {
+                    draw_frame(&mut canvas, &frame, &state, fade, config.color_order);
                 }
             } else if video_player.has_ended() {
-                // Video ended, return to face
+                // Video ended, let the end-of-video policy decide what plays next
                 drop(state);
-                let mut state = mask_state.lock().unwrap();
-                state.video_mode = false;
-                video_player.stop();
-                println!("📺 Video ended, returning to protogen face");
+                let continued = match video_player.end_policy() {
+                    EndOfVideoPolicy::Loop => video_player.restart(),
+                    EndOfVideoPolicy::Advance => video_player.next_video(),
+                    EndOfVideoPolicy::Stop => false,
+                };
+                if !continued {
+                    let mut state = mask_state.lock().unwrap();
+                    state.video_mode = false;
+                    video_player.stop();
+                    info!("📺 Video ended, returning to protogen face");
+                }
+            }
+        } else if state.slideshow_mode && slideshow.is_active() {
+            // Slideshow mode - same blit path as video
+            if let Some(frame) = slideshow.next_frame() {
+                draw_frame(&mut canvas, &frame, &state, 1.0, config.color_order);
             }
         } else {
             // Protogen face mode
             drop(state);
-            protogen.render(&mut canvas);
+            renderer.render(&mut canvas);
         }
 
         let _ = matrix.swap(canvas);
+    }
+
+    // ========================================================================
+    // SHUTDOWN
+    // ========================================================================
+
+    info!("🛑 Shutdown requested, cleaning up...");
+    systemd::notify_stopping();
+
+    // Leave the panels dark instead of frozen on the last frame
+    let mut canvas = matrix.offscreen_canvas();
+    canvas.clear();
+    let _ = matrix.swap(canvas);
+
+    // (the update thread stops the input thread and the HTTP API on its way out)
+    if update_thread.join().is_err() {
+        warn!("⚠️  Face update thread panicked");
+    }
+    video_player.stop();
+    drop(_stream);
+
+    info!("shutdown complete");
+    Ok(())
+}
 
-        thread::sleep(Duration::from_millis(33)); // ~30 FPS
+/// What the face update thread owns (see `run_face_updates`)
+struct FaceUpdates {
+    protogen: ProtogenFace,
+    mask_state: Arc<Mutex<MaskState>>,
+    input: InputThread,
+    api_server: Option<ApiServer>,
+    sequencer: Option<TimedSequencer>,
+    target_fps: f64,
+    schedule: DaySchedule,
+    expression_log: Option<ExpressionLogger>,
+    frame_log: Option<FrameLogger>,
+    config_watcher: ConfigWatcher,
+    config_reloads: Sender<ConfigReload>,  // Passed on to the render loop once applied here
+    shutdown: Arc<AtomicBool>,
+}
+
+/// Update loop of the face thread: controls, API, config, scripts and schedule, then one
+/// `ProtogenFace::update` per frame at the target rate (the render loop draws the frames)
+fn run_face_updates(updates: FaceUpdates) {
+    let FaceUpdates { mut protogen, mask_state, mut input, mut api_server, mut sequencer,
+                      target_fps, mut schedule, mut expression_log, mut frame_log,
+                      mut config_watcher, config_reloads, shutdown } = updates;
+    let sequence_start = Instant::now();
+    let update_span = info_span!("update");
+    let mut last_status = Instant::now();
+    let mut pacer = FramePacer::new(target_fps);
+    while !shutdown.load(Ordering::Relaxed) {
+        pacer.begin_frame();
+        let _enter = update_span.enter();
+
+        // Apply face changes queued by the input thread since the last frame
+        handle_face_commands(&mut input, &mut protogen);
+
+        // Handle HTTP API commands (non-blocking)
+        if let Some(api) = &mut api_server {
+            handle_api_commands(api, &mask_state, &mut protogen);
+            let palette = mask_state.lock().unwrap().color_palette;
+            api.advertise_status(&protogen.get_active_eyes_name(), palette.name());
+        }
+
+        // Apply edits to the config file; settings only read at startup wait for a restart
+        if let Some(reload) = config_watcher.poll() {
+            let new_config = &reload.config;
+            for key in &reload.changed {
+                let applied = match key.as_str() {
+                    "target_fps" => {
+                        pacer = FramePacer::new(new_config.target_fps);
+                        protogen.set_frame_secs(pacer.frame_secs());
+                        true
+                    }
+                    "mouth_trigger_range" | "mouth_trigger_invert" | "mouth_trigger_deadzone"
+                    | "mouth_trigger_axis_code" => {
+                        input.update(InputSetting::MouthTrigger(new_config.mouth_trigger()));
+                        true
+                    }
+                    "gamepad_map_path" => {
+                        let map = ButtonMap::load_or_default(&new_config.gamepad_map_path);
+                        input.update(InputSetting::ButtonMap(map));
+                        true
+                    }
+                    "palette_image" => {
+                        if let Some(palette) = new_config.palette_image.as_deref()
+                            .and_then(load_palette_image) {
+                            mask_state.lock().unwrap().color_palette = palette;
+                        }
+                        true
+                    }
+                    // Applied by the render loop
+                    "video_end_policy" | "video_overlay_alpha" | "standby_after_secs"
+                    | "standby_wake_level" | "standby_fade_in_secs" | "standby_dot" => true,
+                    "schedule" | "schedule_fade_minutes" => {
+                        schedule = DaySchedule::new(&new_config.schedule,
+                                                    new_config.schedule_fade_minutes);
+                        true
+                    }
+                    "fade_speed" => {
+                        // (the video fade's speed is set by the render loop)
+                        protogen.set_fade_speed(new_config.fade_speed);
+                        true
+                    }
+                    "expression_logging" => {
+                        expression_log = open_expression_log(new_config.expression_logging);
+                        true
+                    }
+                    key => apply_face_setting(&mut protogen, &mask_state, new_config, key),
+                };
+                if applied {
+                    info!("⚙️  Config: applied {}", key);
+                } else if RESTART_KEYS.contains(&key.as_str()) {
+                    warn!("⚠️  Config: {} changes on the next restart", key);
+                } else {
+                    warn!("⚠️  Config: unknown setting {}", key);
+                }
+            }
+            // Only fails once the render loop is gone, during shutdown
+            let _ = config_reloads.send(reload);
+        }
+
+        // Fire any scripted sequence events that are due
+        if let Some(seq) = sequencer.as_mut() {
+            seq.update(sequence_start.elapsed().as_secs_f64(), &mut protogen, &mask_state);
+            if seq.is_finished() {
+                info!("📜 Sequence finished");
+                sequencer = None;
+            }
+        }
+
+        // Follow the time-of-day schedule, if the config has one
+        schedule.update(local_minute_of_day(), &mask_state);
+
+        // Append eye, mouth, palette, video and accessory changes to the expression log
+        if let Some(logger) = expression_log.as_mut() {
+            let logged = logger.observe(expression_state(&protogen, &protogen.telemetry(),
+                                                         &mask_state));
+            if let Err(e) = logged {
+                warn!("⚠️  Could not write the expression log, stopping it: {}", e);
+                expression_log = None;
+            }
+        }
+
+        // The face holds still while the render loop has the panels in standby
+        if mask_state.lock().unwrap().asleep {
+            thread::sleep(STANDBY_POLL);
+            continue;
+        }
+
+        // Advance the face and hand the frame to the render loop
+        protogen.update();
+
+        // Per-frame debug log (--frame-log)
+        if let Some(logger) = frame_log.as_mut() {
+            let telemetry = protogen.telemetry();
+            let state = expression_state(&protogen, &telemetry, &mask_state);
+            if let Err(e) = logger.log(&telemetry, protogen.eyelids(), state) {
+                warn!("⚠️  Could not write the frame log, stopping it: {}", e);
+                frame_log = None;
+            }
+        }
+
+        // Periodic status line
+        if last_status.elapsed().as_secs() >= STATUS_INTERVAL_SECS {
+            info!("📊 {}", protogen.telemetry());
+            last_status = Instant::now();
+        }
+
+        pacer.end_frame();
+    }
+
+    input.join();
+    if let Some(api) = api_server {
+        api.shutdown();
+    }
+}
+
+/// Sample the Custom palette from the config's palette_image, None if it can't be read
+fn load_palette_image(path: &str) -> Option<ColorPalette> {
+    match ColorPalette::from_image(path) {
+        Ok(palette) => {
+            info!("🎨 Custom palette sampled from {}", path);
+            Some(palette)
+        }
+        Err(e) => {
+            warn!("⚠️  Could not load palette image {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Mask state the controls start from
+fn initial_mask_state(config: &Config) -> MaskState {
+    let mut state = MaskState::new();
+    state.night_vision = config.night_vision;
+    state.palette_shift_speed = config.palette_shift_speed;
+    if let Some(palette) = config.palette_image.as_deref().and_then(load_palette_image) {
+        state.color_palette = palette;
+    }
+    state
+}
+
+/// Apply the config's face settings (everything that doesn't need hardware), shared by
+/// the real start and `--dry-run` so both check the same face
+fn configure_face(face: &mut ProtogenFace, config: &Config) {
+    face.set_frame_secs(FramePacer::new(config.target_fps).frame_secs());
+    face.set_idle_animation_interval(config.idle_animation_interval_secs);
+    face.set_blep_auto_retract(config.blep_auto_retract_secs);
+    face.set_mirror_offset(config.mirror_offset);
+    face.set_color_order(config.color_order);
+    face.set_shimmer_mode(config.shimmer_mode);
+    face.set_anti_aliasing(config.anti_aliasing);
+    face.set_fade_speed(config.fade_speed);
+    face.set_brightness_oscillation(config.brightness_oscillation_amplitude,
+                                    config.brightness_oscillation_hz);
+    face.set_eye_pose_distance(config.eye_pose_distance);
+    face.set_glitch(config.glitch_intensity, config.glitch_frequency);
+    face.set_bloom(config.bloom_enabled, config.bloom_radius, config.bloom_intensity);
+    face.set_eye_glow(config.eye_glow_enabled, config.eye_glow_radius,
+                      config.eye_glow_intensity);
+    face.set_adaptive_quality(config.adaptive_quality, config.quality_shed_order.clone());
+    face.set_matrix_rain_duration(config.matrix_rain_duration_secs);
+    face.set_element_enabled("Fire", config.fire_enabled);
+    face.set_element_enabled("Waveform", config.waveform_enabled);
+    face.set_waveform_smoothed(config.waveform_smoothed);
+    face.set_element_enabled("Color Burst", config.burst_enabled);
+    face.set_burst_rings(config.burst_ring_speed, config.burst_ring_width);
+    face.set_element_enabled("Spectrum Bars", config.spectrum_enabled);
+    face.set_element_enabled("Clock", config.show_clock);
+    face.set_spectrum(config.spectrum_bands, config.spectrum_y);
+    face.set_element_enabled("Pulse Breathing", config.pulse_breathing_enabled);
+    face.set_nose_breathing(config.nose_breathing);
+    face.set_mouth_emotion_curve(config.mouth_emotion_curve);
+    face.set_mouth_blend(config.mouth_blend, config.mouth_blend_weight);
+    face.set_stereo_mouth(config.stereo_mouth);
+    face.set_heart_rate_range(config.heart_rate_rest_bpm, config.heart_rate_excited_bpm);
+    face.set_emotes(config.emotes.clone());
+    face.set_background_brightness(config.background_brightness);
+    face.set_right_panel_bias(config.right_panel_brightness, config.right_panel_color_shift);
+    face.set_min_pixel_brightness(config.min_pixel_brightness);
+}
+
+// Two chained 64x32 panels on the Adafruit HAT
+fn matrix_options() -> LedMatrixOptions {
+    let mut options = LedMatrixOptions::new();
+    options.set_rows(32);
+    options.set_cols(64);
+    options.set_chain_length(2);
+    options.set_hardware_mapping("adafruit-hat");
+    options
+}
+
+/// Validate the config, sequence script and face elements without opening the
+/// LED matrix, microphone or HTTP API
+fn dry_run(args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    info!("🧪 Dry run: validating config and face elements (no hardware)");
+    let mut errors = Vec::new();
+
+    // A missing config file is fine (defaults), an unreadable or invalid one is not
+    let config = if Path::new(&args.config_path).exists() {
+        match Config::load(&args.config_path) {
+            Ok(config) => {
+                info!("⚙️  Loaded config from {}", args.config_path);
+                config
+            }
+            Err(e) => {
+                errors.push(format!("config {}: {}", args.config_path, e));
+                Config::default()
+            }
+        }
+    } else {
+        info!("⚙️  No config at {}, using defaults", args.config_path);
+        Config::default()
+    };
+    rng::init(args.seed.or(config.rng_seed));
+
+    if let Some(path) = &args.sequence_path {
+        match TimedSequencer::load(path) {
+            Ok(sequencer) => info!("📜 Sequence {} OK ({} events)", path, sequencer.remaining()),
+            Err(e) => errors.push(format!("sequence {}: {}", path, e)),
+        }
+    }
+
+    // Build the face with synthetic audio and render a few frames in memory
+    let audio_level = Arc::new(AudioLevel::new());
+    let mut synth = AudioSynthesizer::new(audio_level.clone(), SynthMode::Sine);
+    let mask_state = Arc::new(Mutex::new(initial_mask_state(&config)));
+    let mut protogen = ProtogenFace::new(audio_level, mask_state, Arc::new(FaceMetrics::new()));
+    configure_face(&mut protogen, &config);
+    protogen.add_element(Box::new(StatusIndicatorElement::new(
+        StatusIndicators::new(), config.indicator_style, config.indicator_x, config.indicator_y)));
+    protogen.add_element(Box::new(TemperatureWarningElement::new(Arc::new(Mutex::new(f64::NAN)))));
+    protogen.set_element_enabled("Temperature Warning", config.show_temp_warning);
+    protogen.add_element(Box::new(WiFiSignalIndicator::new(Arc::new(Mutex::new(None)))));
+    protogen.set_element_enabled("WiFi Signal", config.show_wifi);
+    if config.show_battery {
+        protogen.add_element(Box::new(BatteryWarningElement::new(Arc::new(Mutex::new(100)))));
+    }
+    protogen.set_debug_overlay(args.debug_overlay);
+
+    for entry in &config.schedule {
+        if parse_time_of_day(&entry.time).is_none() {
+            errors.push(format!("schedule: bad time \"{}\" (use \"HH:MM\")", entry.time));
+        }
+    }
+
+    // Emotes name their eyes by string, so catch typos before they fail on stage
+    let eyes = protogen.list_eyes();
+    for emote in &config.emotes {
+        if let Some(name) = emote.eyes.as_ref().filter(|name| !eyes.contains(name)) {
+            errors.push(format!("emote {}: unknown eyes \"{}\" (available: {})",
+                                emote.name, name, eyes.join(", ")));
+        }
+    }
+
+    if let Some(name) = &args.profile {
+        if !protogen.switch_profile(name) {
+            errors.push(format!("profile \"{}\" not in {} (loaded: {})", name, PROFILES_PATH,
+                                protogen.list_profiles().join(", ")));
+        }
+    }
+
+    let mut canvas = VirtualLedCanvas::new(PANEL_WIDTH * 2, PANEL_HEIGHT);
+    let mut renderer = protogen.renderer();
+    for _ in 0..DRY_RUN_FRAMES {
+        synth.update(0.033);
+        protogen.update();
+        renderer.render_to_virtual(&mut canvas);
+    }
+    if canvas.lit_pixel_count() == 0 {
+        errors.push("face rendered no pixels".to_string());
+    }
+
+    if errors.is_empty() {
+        info!("dry run OK");
+        return Ok(());
+    }
+    for e in &errors {
+        error!("❌ {}", e);
+    }
+    Err(format!("dry run failed with {} error(s)", errors.len()).into())
+}
+
+// What the expression and frame logs compare from frame to frame
+fn expression_state(protogen: &ProtogenFace, telemetry: &FaceTelemetry,
+                    mask_state: &Mutex<MaskState>) -> ExpressionState {
+    let video = mask_state.lock().unwrap().video_name.clone();
+    ExpressionState {
+        eyes: telemetry.active_eyes.clone(),
+        mouth: protogen.get_active_mouth_name(),
+        mouth_mode: telemetry.mode.name().to_string(),
+        palette: telemetry.palette.name().to_string(),
+        video,
+        accessories: protogen.shown_accessories(),
+    }
+}
+
+// Expression log when `expression_logging` is on (a log that can't be opened is skipped)
+fn open_expression_log(enabled: bool) -> Option<ExpressionLogger> {
+    if !enabled {
+        return None;
+    }
+    match ExpressionLogger::open() {
+        Ok(logger) => {
+            info!("📝 Logging expression changes to {}", EXPRESSION_LOG_DIR);
+            Some(logger)
+        }
+        Err(e) => {
+            warn!("⚠️  Could not open the expression log in {}: {}", EXPRESSION_LOG_DIR, e);
+            None
+        }
+    }
+}
+
+// Per-frame debug log for --frame-log (a log that can't be created is skipped)
+fn open_frame_log(path: &str) -> Option<FrameLogger> {
+    match FrameLogger::create(path) {
+        Ok(logger) => {
+            info!("📝 Logging every frame to {}", path);
+            Some(logger)
+        }
+        Err(e) => {
+            warn!("⚠️  Could not create frame log {}: {}", path, e);
+            None
+        }
+    }
+}
+
+// Standby tracker with the config's settings
+fn new_standby(config: &Config) -> Standby {
+    Standby::new(config.standby_after_secs, config.standby_wake_level,
+                 config.standby_fade_in_secs, config.standby_dot)
+}
+
+/// Draw the standby screen: dark, with a small dot at each nose when `brightness` is above 0
+fn draw_standby_dot(canvas: &mut LedCanvas, brightness: f64, palette: ColorPalette,
+                    order: ColorOrder) {
+    canvas.clear();
+    if brightness <= 0.0 {
+        return;
+    }
+    let color = get_shimmer_color(0.0, 255.0 * brightness, palette);
+    let color = order.apply(color.red, color.green, color.blue);
+    // 2x2 pixels just above the nose tip (panel coordinates, y down)
+    for (x, y) in [(55, 7), (56, 7), (55, 8), (56, 8)] {
+        canvas.set(x, y, &color);
+        canvas.set(PANEL_WIDTH * 2 - 1 - x, y, &color);
+    }
+}
+
+/// Draw a 64x32 video/image frame mirrored on both panels, applying brightness (times `fade`),
+/// the optional palette mapping and the panels' channel order
+fn draw_frame(canvas: &mut LedCanvas, frame: &VideoFrame, state: &MaskState, fade: f64,
+              order: ColorOrder) {
+    let brightness = (state.brightness * fade * 255.0) as u8;
+
+    for y in 0..32 {
+        for x in 0..64 {
+            let (r, g, b) = frame.get_pixel(x, y);
+            let color = if state.video_palette_mode {
+                // Monochrome in the face's palette
+                get_palette_color_for_luminance(luminance(r, g, b), state.brightness * fade,
+                                                state.color_palette)
+            } else {
+                let r = ((r as u16 * brightness as u16) / 255) as u8;
+                let g = ((g as u16 * brightness as u16) / 255) as u8;
+                let b = ((b as u16 * brightness as u16) / 255) as u8;
+                LedColor { red: r, green: g, blue: b }
+            };
+            let color = order.apply(color.red, color.green, color.blue);
+
+            // Draw on left panel
+            canvas.set(x as i32, y as i32, &color);
+            // Mirror on right panel
+            canvas.set((x + 64) as i32, y as i32, &color);
+        }
     }
-}
\ No newline at end of file
+}
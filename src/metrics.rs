@@ -0,0 +1,58 @@
+// Prometheus metrics
+// Render statistics updated by ProtogenFace and served as text by the HTTP API
+// at GET /metrics, so the mask can be watched from Grafana over the local network.
+
+use prometheus::{Encoder, Gauge, IntCounter, Registry, TextEncoder};
+use tracing::warn;
+
+/// Render metrics shared between the render loop and the HTTP API
+pub struct FaceMetrics {
+    registry: Registry,
+    pub fps: Gauge,
+    pub audio_level: Gauge,
+    pub mouth_opening: Gauge,
+    pub brightness: Gauge,
+    pub frame_count: IntCounter,
+    pub render_ms: Gauge,
+}
+
+impl FaceMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let fps = Gauge::new("protogen_fps", "Measured face render rate in frames per second").unwrap();
+        let audio_level = Gauge::new("protogen_audio_level", "Current normalized microphone level (0.0 to 1.0)").unwrap();
+        let mouth_opening = Gauge::new("protogen_mouth_opening", "Current mouth opening").unwrap();
+        let brightness = Gauge::new("protogen_brightness", "Display brightness (0.0 to 1.0)").unwrap();
+        let frame_count = IntCounter::new("protogen_frame_count", "Total face frames rendered").unwrap();
+        let render_ms = Gauge::new("protogen_render_ms", "Time spent rendering the last face frame in milliseconds").unwrap();
+
+        // Names are fixed and unique, so registration can't fail
+        registry.register(Box::new(fps.clone())).unwrap();
+        registry.register(Box::new(audio_level.clone())).unwrap();
+        registry.register(Box::new(mouth_opening.clone())).unwrap();
+        registry.register(Box::new(brightness.clone())).unwrap();
+        registry.register(Box::new(frame_count.clone())).unwrap();
+        registry.register(Box::new(render_ms.clone())).unwrap();
+
+        Self {
+            registry,
+            fps,
+            audio_level,
+            mouth_opening,
+            brightness,
+            frame_count,
+            render_ms,
+        }
+    }
+
+    /// Encode all metrics in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        let encoder = TextEncoder::new();
+        if let Err(e) = encoder.encode(&self.registry.gather(), &mut buffer) {
+            warn!("⚠️  Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
@@ -0,0 +1,144 @@
+// OSC (Open Sound Control) receiver
+// Lets VJ and music software (TouchDesigner, Max for Live, ...) drive the mask over UDP.
+// Packets are decoded with rosc; bundles are flattened and applied in order on arrival
+// (their time tags are ignored). Messages go straight into MaskState from the receiver
+// thread, like gamepad input.
+
+use std::io;
+use std::net::UdpSocket;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use rosc::{OscMessage, OscPacket, OscType};
+
+use crate::color::ColorPalette;
+use crate::gamepad::MaskState;
+use tracing::{debug, info, warn};
+
+pub const DEFAULT_OSC_PORT: u16 = 9000;
+
+/// Bind the UDP port and handle incoming messages on a background thread
+pub fn start_osc_receiver(port: u16, state: Arc<Mutex<MaskState>>) -> io::Result<()> {
+    let socket = UdpSocket::bind(("0.0.0.0", port))?;
+    thread::spawn(move || {
+        let mut buf = [0u8; rosc::decoder::MTU];
+        loop {
+            match socket.recv_from(&mut buf) {
+                Ok((len, from)) => match rosc::decoder::decode_udp(&buf[..len]) {
+                    Ok((_, packet)) => {
+                        for message in flatten(packet) {
+                            apply_message(&message, &state);
+                        }
+                    }
+                    Err(e) => debug!("🎛️  Unreadable OSC packet from {}: {:?}", from, e),
+                },
+                Err(e) => {
+                    warn!("⚠️  OSC receive failed, stopping the receiver: {}", e);
+                    return;
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Messages in a packet, nested bundles included, in the order they were sent
+fn flatten(packet: OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(message) => vec![message],
+        OscPacket::Bundle(bundle) => bundle.content.into_iter().flat_map(flatten).collect(),
+    }
+}
+
+// Numeric argument as f64; NaN and infinities from the network are dropped
+fn number(arg: &OscType) -> Option<f64> {
+    let value = match arg {
+        OscType::Int(value) => *value as f64,
+        OscType::Long(value) => *value as f64,
+        OscType::Float(value) => *value as f64,
+        OscType::Double(value) => *value,
+        OscType::Bool(value) => if *value { 1.0 } else { 0.0 },
+        _ => return None,
+    };
+    value.is_finite().then_some(value)
+}
+
+/// Apply one message to the mask state; unknown addresses and bad arguments are ignored
+fn apply_message(message: &OscMessage, state: &Mutex<MaskState>) {
+    let arg = message.args.first();
+    let mut s = state.lock().unwrap();
+    match (message.addr.as_str(), arg) {
+        ("/protogen/mouth", Some(arg)) => {
+            if let Some(value) = number(arg) {
+                // Used while manual mouth mode is on, like the trigger
+                s.mouth_analog_value = value.clamp(0.0, 1.0);
+            }
+        }
+        ("/protogen/brightness", Some(arg)) => {
+            // Not recorded for undo: faders send a stream of values
+            if let Some(value) = number(arg) {
+                s.set_brightness(value);
+            }
+        }
+        ("/protogen/palette", Some(OscType::String(name))) => {
+            match ColorPalette::from_name(name) {
+                Some(palette) if palette != s.color_palette => {
+                    s.record("palette");
+                    s.color_palette = palette;
+                    info!("🎨 Color: {} (OSC)", palette.name());
+                }
+                Some(_) => {}
+                None => warn!("⚠️  OSC: unknown palette \"{}\"", name),
+            }
+        }
+        ("/protogen/blink", Some(arg)) => {
+            if let Some(value) = number(arg) {
+                s.blink_enabled = value != 0.0;
+                info!("👁️  Blinking {} (OSC)", if s.blink_enabled { "ON" } else { "OFF" });
+            }
+        }
+        (address, _) => debug!("🎛️  Ignoring OSC message {} {:?}", address, message.args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosc::{OscBundle, OscTime};
+
+    fn message(addr: &str, arg: OscType) -> OscMessage {
+        OscMessage { addr: addr.to_string(), args: vec![arg] }
+    }
+
+    #[test]
+    fn messages_map_onto_the_mask_state_and_non_finite_values_are_dropped() {
+        let state = Mutex::new(MaskState::new());
+        let bundle = OscPacket::Bundle(OscBundle {
+            timetag: OscTime { seconds: 0, fractional: 1 },
+            content: vec![
+                OscPacket::Message(message("/protogen/mouth", OscType::Float(0.5))),
+                OscPacket::Bundle(OscBundle {
+                    timetag: OscTime { seconds: 0, fractional: 1 },
+                    content: vec![OscPacket::Message(message("/protogen/palette",
+                                                             OscType::String("ocean".into())))],
+                }),
+            ],
+        });
+        for message in flatten(bundle) {
+            apply_message(&message, &state);
+        }
+        assert_eq!(state.lock().unwrap().mouth_analog_value, 0.5);
+        assert_eq!(state.lock().unwrap().color_palette, ColorPalette::Ocean);
+
+        apply_message(&message("/protogen/blink", OscType::Int(0)), &state);
+        assert!(!state.lock().unwrap().blink_enabled);
+
+        let brightness = state.lock().unwrap().brightness;
+        apply_message(&message("/protogen/mouth", OscType::Float(f32::NAN)), &state);
+        apply_message(&message("/protogen/brightness", OscType::Double(f64::INFINITY)), &state);
+        apply_message(&message("/protogen/blink", OscType::Float(f32::NAN)), &state);
+        let state = state.lock().unwrap();
+        assert_eq!(state.mouth_analog_value, 0.5);
+        assert_eq!(state.brightness, brightness);
+        assert!(!state.blink_enabled);
+    }
+}
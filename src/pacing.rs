@@ -0,0 +1,89 @@
+// Frame pacing
+// The main loop renders a frame, then sleeps only for what is left of the frame
+// budget at the target FPS (none at all when the frame ran long), instead of a
+// fixed sleep that made every frame slower by however long rendering took.
+
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+// How often a missed target is reported, so slow frames don't flood the log
+const SLOW_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Keeps the main loop at a target frame rate
+pub struct FramePacer {
+    frame_budget: Duration,     // Time per frame at the target FPS
+    frame_start: Instant,
+    slow_frames: u32,           // Frames over budget since the last report
+    slowest: Duration,          // Longest frame since the last report
+    last_report: Instant,
+}
+
+impl FramePacer {
+    pub fn new(target_fps: f64) -> Self {
+        let now = Instant::now();
+        Self {
+            frame_budget: Self::budget(target_fps),
+            frame_start: now,
+            slow_frames: 0,
+            slowest: Duration::ZERO,
+            last_report: now,
+        }
+    }
+
+    /// Seconds per frame at the target rate, the `dt` animations should advance by
+    pub fn frame_secs(&self) -> f64 {
+        self.frame_budget.as_secs_f64()
+    }
+
+    /// Mark the start of a frame's work
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Instant::now();
+    }
+
+    /// Sleep for whatever is left of the frame budget, and report if the target isn't met
+    pub fn end_frame(&mut self) {
+        let work = self.frame_start.elapsed();
+        match self.remaining(work) {
+            Some(rest) => thread::sleep(rest),
+            None => {
+                self.slow_frames += 1;
+                self.slowest = self.slowest.max(work);
+            }
+        }
+
+        if self.slow_frames > 0 && self.last_report.elapsed() >= SLOW_REPORT_INTERVAL {
+            warn!("🐢 Can't keep up with {:.0} FPS: {} slow frame(s) in the last {}s (slowest {:.1}ms, budget {:.1}ms)",
+                  1.0 / self.frame_secs(), self.slow_frames, SLOW_REPORT_INTERVAL.as_secs(),
+                  self.slowest.as_secs_f64() * 1000.0, self.frame_secs() * 1000.0);
+            self.slow_frames = 0;
+            self.slowest = Duration::ZERO;
+            self.last_report = Instant::now();
+        }
+    }
+
+    // Time left to sleep after `work`, None when the frame used its whole budget
+    fn remaining(&self, work: Duration) -> Option<Duration> {
+        self.frame_budget.checked_sub(work).filter(|rest| !rest.is_zero())
+    }
+
+    // Frame budget for a target FPS (non-positive or non-finite targets fall back to 30)
+    fn budget(target_fps: f64) -> Duration {
+        let fps = if target_fps.is_finite() && target_fps > 0.0 { target_fps } else { 30.0 };
+        Duration::from_secs_f64(1.0 / fps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sleeps_only_the_rest_of_the_budget() {
+        let pacer = FramePacer::new(50.0);
+        assert_eq!(pacer.remaining(Duration::from_millis(5)), Some(Duration::from_millis(15)));
+        assert_eq!(pacer.remaining(Duration::from_millis(20)), None);
+        assert_eq!(pacer.remaining(Duration::from_millis(35)), None);
+        assert_eq!(FramePacer::new(0.0).frame_secs(), FramePacer::new(30.0).frame_secs());
+    }
+}
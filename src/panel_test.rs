@@ -0,0 +1,92 @@
+// Panel diagnostic (--test-panel)
+// Steps through solid colors, panel order and brightness ramps on the raw matrix
+// canvas, bypassing the face entirely, so wiring, color order, dead pixels and
+// chain order can be checked during hardware bring-up. Each step describes on the
+// console what should be visible.
+
+use std::thread;
+use std::time::Duration;
+use rpi_led_matrix::LedMatrix;
+use tracing::info;
+
+use crate::color::ColorOrder;
+use crate::{PANEL_HEIGHT, PANEL_WIDTH};
+
+// How long each pattern stays up
+const STEP_TIME: Duration = Duration::from_secs(4);
+
+// Number of chained panels (the face mirrors one panel onto the other)
+const PANEL_COUNT: i32 = 2;
+
+/// One pattern: a name, what the builder should see, and the color of each pixel
+struct TestStep {
+    name: &'static str,
+    expect: &'static str,
+    pixel: fn(x: i32, y: i32) -> (u8, u8, u8),
+}
+
+const STEPS: &[TestStep] = &[
+    TestStep {
+        name: "Solid red",
+        expect: "Both panels fully red. Green or blue means the channel order is wrong: try another color_order",
+        pixel: |_, _| (255, 0, 0),
+    },
+    TestStep {
+        name: "Solid green",
+        expect: "Both panels fully green",
+        pixel: |_, _| (0, 255, 0),
+    },
+    TestStep {
+        name: "Solid blue",
+        expect: "Both panels fully blue",
+        pixel: |_, _| (0, 0, 255),
+    },
+    TestStep {
+        name: "Solid white",
+        expect: "Even white everywhere. Look for dark (dead) pixels; a tint towards one side often means weak power",
+        pixel: |_, _| (255, 255, 255),
+    },
+    TestStep {
+        name: "Panel order",
+        expect: "First panel in the chain (nearest the HAT) lit white, second panel dark",
+        pixel: |x, _| if x < PANEL_WIDTH { (255, 255, 255) } else { (0, 0, 0) },
+    },
+    TestStep {
+        name: "Ramp",
+        expect: "Each panel fades from dark on the left to bright on the right, in red (top), green (middle) and blue (bottom) bands. Steps or gaps in the fade point to a bad data line",
+        pixel: |x, y| {
+            let level = ((x % PANEL_WIDTH) * 255 / (PANEL_WIDTH - 1)) as u8;
+            match y * 3 / PANEL_HEIGHT {
+                0 => (level, 0, 0),
+                1 => (0, level, 0),
+                _ => (0, 0, level),
+            }
+        },
+    },
+];
+
+/// Show every test pattern once in the configured channel order, then blank the panels
+pub fn run(matrix: &LedMatrix, order: ColorOrder) -> Result<(), Box<dyn std::error::Error>> {
+    info!("🧪 Panel test: {} patterns, {}s each, color_order = \"{}\"",
+          STEPS.len(), STEP_TIME.as_secs(), order.name());
+
+    for (i, step) in STEPS.iter().enumerate() {
+        info!("🧪 [{}/{}] {}: {}", i + 1, STEPS.len(), step.name, step.expect);
+
+        let mut canvas = matrix.offscreen_canvas();
+        for y in 0..PANEL_HEIGHT {
+            for x in 0..PANEL_WIDTH * PANEL_COUNT {
+                let (red, green, blue) = (step.pixel)(x, y);
+                canvas.set(x, y, &order.apply(red, green, blue));
+            }
+        }
+        let _ = matrix.swap(canvas);
+        thread::sleep(STEP_TIME);
+    }
+
+    let mut canvas = matrix.offscreen_canvas();
+    canvas.clear();
+    let _ = matrix.swap(canvas);
+    info!("🧪 Panel test done");
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// Panic guard
+// A panic would otherwise leave the panels frozen on the last (possibly bright) frame.
+// The hook installed here blanks the matrix and logs the panic before the default
+// hook prints the backtrace.
+
+use std::panic;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::thread;
+use rpi_led_matrix::LedMatrix;
+use tracing::error;
+
+// Matrix owned by main(), registered for the lifetime of the render loop
+static MATRIX: AtomicPtr<LedMatrix> = AtomicPtr::new(ptr::null_mut());
+
+/// Unregisters the matrix when dropped (declare it after the matrix so it drops first)
+pub struct PanicGuard;
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        MATRIX.store(ptr::null_mut(), Ordering::SeqCst);
+    }
+}
+
+/// Install the panic hook and register the matrix it should clear
+pub fn install(matrix: &LedMatrix) -> PanicGuard {
+    MATRIX.store(matrix as *const LedMatrix as *mut LedMatrix, Ordering::SeqCst);
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        error!("💥 Panic: {}", info);
+
+        // Only the main thread drives the matrix - a panic on a helper thread
+        // (e.g. the HTTP API) leaves rendering running, so don't blank it
+        if thread::current().name() == Some("main") {
+            clear_matrix();
+        }
+
+        default_hook(info);
+    }));
+
+    PanicGuard
+}
+
+fn clear_matrix() {
+    let matrix = MATRIX.swap(ptr::null_mut(), Ordering::SeqCst);
+    if matrix.is_null() {
+        return;
+    }
+
+    // SAFETY: the pointer is set from a live reference in main() and cleared when the
+    // PanicGuard drops, before the matrix; we're on the main thread that owns it
+    let matrix = unsafe { &*matrix };
+    let mut canvas = matrix.offscreen_canvas();
+    canvas.clear();
+    let _ = matrix.swap(canvas);
+}
@@ -0,0 +1,158 @@
+// Profiles
+// Up to four named looks kept in profiles.toml, e.g. "stage", "con floor", "photo" and
+// "low power". Each one is a full face snapshot: the mask state (brightness, blinking,
+// post effects), the registry state (eye and mouth style, hidden elements) and the color
+// theme. Pick one at startup with --profile <name> or live with Start + D-pad. Changes made
+// while a profile is active are not written back; POST /profile/save does that on purpose.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use serde::{Deserialize, Serialize};
+
+use crate::snapshot::FaceSnapshot;
+use tracing::warn;
+
+/// Profile file (working directory, next to the config)
+pub const PROFILES_PATH: &str = "profiles.toml";
+
+/// Number of profiles (one per D-pad direction)
+pub const PROFILE_SLOTS: usize = 4;
+
+/// One named look; the snapshot fields sit next to the name in the file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(flatten)]
+    pub look: FaceSnapshot,
+}
+
+// Layout of profiles.toml: a [[profile]] table per profile, in slot order
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profile: Vec<Profile>,
+}
+
+/// The profiles from the file and which one was picked last
+#[derive(Debug)]
+pub struct ProfileManager {
+    path: String,
+    profiles: Vec<Profile>,
+    active: Option<usize>,
+}
+
+impl ProfileManager {
+    /// Load profiles from a file; a missing or unreadable file starts empty
+    /// (profiles past PROFILE_SLOTS are ignored)
+    pub fn load(path: &str) -> Self {
+        let mut profiles = match fs::read_to_string(path) {
+            Ok(contents) => match toml::from_str::<ProfileFile>(&contents) {
+                Ok(file) => file.profile,
+                Err(e) => {
+                    warn!("⚠️  Could not parse profiles {}: {}", path, e);
+                    Vec::new()
+                }
+            },
+            Err(e) => {
+                if e.kind() != ErrorKind::NotFound {
+                    warn!("⚠️  Could not read profiles {}: {}", path, e);
+                }
+                Vec::new()
+            }
+        };
+        if profiles.len() > PROFILE_SLOTS {
+            warn!("⚠️  {} has {} profiles, only the first {} are used", path, profiles.len(),
+                  PROFILE_SLOTS);
+            profiles.truncate(PROFILE_SLOTS);
+        }
+        Self { path: path.to_string(), profiles, active: None }
+    }
+
+    /// Slot of the profile with the given name
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.profiles.iter().position(|profile| profile.name == name)
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&Profile> {
+        self.profiles.get(slot)
+    }
+
+    /// Profile names, in slot order
+    pub fn names(&self) -> Vec<String> {
+        self.profiles.iter().map(|profile| profile.name.clone()).collect()
+    }
+
+    /// Name of the profile picked last, if any
+    pub fn active(&self) -> Option<&str> {
+        self.active.and_then(|slot| self.profiles.get(slot)).map(|profile| profile.name.as_str())
+    }
+
+    pub fn set_active(&mut self, slot: usize) {
+        if slot < self.profiles.len() {
+            self.active = Some(slot);
+        }
+    }
+
+    /// Store a look under a name (replacing that profile, or taking the next free slot)
+    /// and write the file; fails if all slots hold other profiles
+    pub fn save(&mut self, name: &str, look: FaceSnapshot) -> io::Result<usize> {
+        let slot = match self.find(name) {
+            Some(slot) => {
+                self.profiles[slot].look = look;
+                slot
+            }
+            None if self.profiles.len() < PROFILE_SLOTS => {
+                self.profiles.push(Profile { name: name.to_string(), look });
+                self.profiles.len() - 1
+            }
+            None => {
+                return Err(io::Error::other(format!("all {} profile slots are taken ({})",
+                                                    PROFILE_SLOTS, self.names().join(", "))));
+            }
+        };
+        let file = ProfileFile { profile: self.profiles.clone() };
+        fs::write(&self.path, toml::to_string_pretty(&file).map_err(io::Error::other)?)?;
+        Ok(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::ColorPalette;
+
+    fn look(palette: ColorPalette) -> FaceSnapshot {
+        FaceSnapshot {
+            eyes: "Heart Eyes".to_string(),
+            mouth: "Default Mouth".to_string(),
+            hidden: vec!["Fire".to_string()],
+            palette,
+            brightness: 0.4,
+            blink_enabled: true,
+            glitch_enabled: false,
+            night_vision: false,
+            invert: false,
+        }
+    }
+
+    #[test]
+    fn profiles_fill_four_slots_and_load_back() {
+        let path = std::env::temp_dir().join(format!("protogen_profiles_{}.toml",
+                                                     std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut profiles = ProfileManager::load(path);
+        for (slot, name) in ["stage", "photo", "con", "night"].iter().enumerate() {
+            assert_eq!(profiles.save(name, look(ColorPalette::Purple)).unwrap(), slot);
+        }
+        assert!(profiles.save("extra", look(ColorPalette::Purple)).is_err());
+        // Saving an existing name replaces it in place
+        assert_eq!(profiles.save("photo", look(ColorPalette::Forest)).unwrap(), 1);
+
+        let loaded = ProfileManager::load(path);
+        let _ = fs::remove_file(path);
+        assert_eq!(loaded.names(), vec!["stage", "photo", "con", "night"]);
+        assert_eq!(loaded.get(1).unwrap().look, look(ColorPalette::Forest));
+        assert_eq!(loaded.active(), None);
+    }
+}
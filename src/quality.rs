@@ -0,0 +1,165 @@
+// Adaptive quality
+// When rendering gets too slow for the target frame rate (e.g. a thermally throttled
+// Pi), optional effects are switched off one at a time, in a configurable order, and
+// come back one at a time once there is headroom again. The face stays smooth instead
+// of stuttering with everything on.
+
+use serde::Deserialize;
+
+// Render time (fraction of the frame budget) above which an effect is dropped
+const SHED_LOAD: f64 = 0.85;
+// Render time below which a dropped effect is brought back
+const RESTORE_LOAD: f64 = 0.5;
+// How long the load has to stay past a threshold before acting
+const SHED_AFTER_SECS: f64 = 2.0;
+const RESTORE_AFTER_SECS: f64 = 10.0;
+// Smoothing of the measured render time (fraction of the new sample per frame)
+const LOAD_SMOOTHING: f64 = 0.1;
+
+/// Optional effects that can be dropped under load ("bloom", "eye_glow", ... in config files)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityEffect {
+    Bloom,     // Whole-face bloom
+    EyeGlow,   // Halo around the eyes
+    Spectrum,  // Audio spectrum bars (a DFT every frame)
+    Fire,      // Fire simulation
+}
+
+impl QualityEffect {
+    pub fn name(&self) -> &str {
+        match self {
+            QualityEffect::Bloom => "bloom",
+            QualityEffect::EyeGlow => "eye_glow",
+            QualityEffect::Spectrum => "spectrum",
+            QualityEffect::Fire => "fire",
+        }
+    }
+
+    /// Default order effects are dropped in, most expensive for the least loss first
+    pub fn default_order() -> Vec<Self> {
+        vec![QualityEffect::Bloom, QualityEffect::EyeGlow, QualityEffect::Spectrum,
+             QualityEffect::Fire]
+    }
+}
+
+/// What the quality manager wants done this frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityAction {
+    Shed,     // Drop the next effect in the order that is running
+    Restore,  // Bring back the most recently dropped effect
+}
+
+/// Watches the render load and decides when to drop or restore an effect
+#[derive(Debug)]
+pub struct QualityManager {
+    enabled: bool,
+    order: Vec<QualityEffect>,
+    shed: Vec<QualityEffect>,  // Dropped effects, most recent last
+    load: f64,                 // Smoothed render time as a fraction of the frame budget
+    over_secs: f64,            // How long the load has been above SHED_LOAD
+    under_secs: f64,           // How long it has been below RESTORE_LOAD
+}
+
+impl QualityManager {
+    pub fn new(enabled: bool, order: Vec<QualityEffect>) -> Self {
+        Self {
+            enabled,
+            order,
+            shed: Vec::new(),
+            load: 0.0,
+            over_secs: 0.0,
+            under_secs: 0.0,
+        }
+    }
+
+    /// Feed one frame's render time, returns an action once the load has stayed
+    /// past a threshold long enough
+    pub fn update(&mut self, render_secs: f64, budget_secs: f64) -> Option<QualityAction> {
+        if !self.enabled || budget_secs <= 0.0 {
+            return None;
+        }
+        let sample = render_secs / budget_secs;
+        self.load += (sample - self.load) * LOAD_SMOOTHING;
+
+        if self.load > SHED_LOAD {
+            self.over_secs += budget_secs;
+            self.under_secs = 0.0;
+        } else if self.load < RESTORE_LOAD {
+            self.under_secs += budget_secs;
+            self.over_secs = 0.0;
+        } else {
+            self.over_secs = 0.0;
+            self.under_secs = 0.0;
+        }
+
+        if self.over_secs >= SHED_AFTER_SECS && self.shed.len() < self.order.len() {
+            self.over_secs = 0.0;
+            return Some(QualityAction::Shed);
+        }
+        if self.under_secs >= RESTORE_AFTER_SECS && !self.shed.is_empty() {
+            self.under_secs = 0.0;
+            return Some(QualityAction::Restore);
+        }
+        None
+    }
+
+    /// Next effect in the order not dropped yet that passes `running`
+    pub fn next_to_shed(&self, running: impl Fn(QualityEffect) -> bool) -> Option<QualityEffect> {
+        self.order.iter().copied().find(|&effect| !self.shed.contains(&effect) && running(effect))
+    }
+
+    pub fn mark_shed(&mut self, effect: QualityEffect) {
+        self.shed.push(effect);
+    }
+
+    /// Take the most recently dropped effect back off the list
+    pub fn pop_shed(&mut self) -> Option<QualityEffect> {
+        self.shed.pop()
+    }
+
+    pub fn is_shed(&self, effect: QualityEffect) -> bool {
+        self.shed.contains(&effect)
+    }
+
+    /// Effects currently dropped, in the order they went
+    pub fn shed_effects(&self) -> &[QualityEffect] {
+        &self.shed
+    }
+
+    /// Change the settings; with adaptive quality off, dropped effects should be restored
+    pub fn configure(&mut self, enabled: bool, order: Vec<QualityEffect>) {
+        self.enabled = enabled;
+        self.order = order;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheds_under_sustained_load_and_restores_with_headroom() {
+        let mut quality = QualityManager::new(true, QualityEffect::default_order());
+        let budget = 0.02;
+
+        // A brief spike is ignored
+        assert_eq!(quality.update(0.05, budget), None);
+
+        let mut actions = Vec::new();
+        for _ in 0..(SHED_AFTER_SECS / budget) as usize * 2 {
+            actions.extend(quality.update(0.03, budget));
+        }
+        assert_eq!(actions.first(), Some(&QualityAction::Shed));
+        assert_eq!(quality.next_to_shed(|effect| effect != QualityEffect::Bloom),
+                   Some(QualityEffect::EyeGlow));
+        quality.mark_shed(QualityEffect::EyeGlow);
+
+        let mut restored = false;
+        for _ in 0..(RESTORE_AFTER_SECS / budget) as usize * 2 {
+            restored |= quality.update(0.005, budget) == Some(QualityAction::Restore);
+        }
+        assert!(restored);
+        assert_eq!(quality.pop_shed(), Some(QualityEffect::EyeGlow));
+    }
+}
@@ -0,0 +1,269 @@
+// Time-of-day schedule
+// For an always-on display (a desk ornament, say) the config can list times of day
+// and the palette, brightness and mouth expression to switch to at each one, e.g.
+// bright and colorful from 08:00, dim and warm from 22:00. The schedule wraps around
+// midnight: before the first entry of the day the last entry (from yesterday) holds.
+// Brightness and expression fade in over `schedule_fade_minutes` after each entry;
+// settings an entry leaves out keep the value from the entry before that set them.
+// Changes made by hand (gamepad, API) stay until the next entry that sets that setting
+// starts, even in the middle of a fade.
+
+use std::sync::Mutex;
+use chrono::{Local, Timelike};
+use serde::Deserialize;
+
+use crate::color::ColorPalette;
+use crate::gamepad::{MaskState, MAX_BRIGHTNESS, MIN_BRIGHTNESS};
+use tracing::{info, warn};
+
+const MINUTES_PER_DAY: f64 = 24.0 * 60.0;
+
+/// One schedule entry as written in the config file ([[schedule]] tables)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScheduleEntry {
+    pub time: String,                   // "HH:MM", 24-hour local time
+    pub palette: Option<ColorPalette>,
+    pub brightness: Option<f64>,        // 0.1 to 1.0 (MIN_BRIGHTNESS to MAX_BRIGHTNESS)
+    pub mouth_emotion: Option<f64>,     // -1.0 (frown) to 1.0 (smile)
+}
+
+/// Settings the schedule asks for at one moment (None where no entry sets them)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScheduledSettings {
+    pub palette: Option<ColorPalette>,
+    pub brightness: Option<f64>,
+    pub mouth_emotion: Option<f64>,
+}
+
+// A faded setting the schedule writes, and whether a hand change is holding it off
+#[derive(Debug, Default)]
+struct DrivenSetting {
+    written: Option<f64>,    // Value the schedule last left in the mask state
+    held_by: Option<usize>,  // Entry in effect when it was changed by hand
+}
+
+impl DrivenSetting {
+    // Whether the schedule may write the setting now (`value` is what the mask state holds,
+    // `entry` the entry setting it); a hand change holds until another entry takes over
+    fn may_write(&mut self, value: f64, entry: Option<usize>) -> bool {
+        if self.written.is_some_and(|written| written != value) {
+            self.written = None;
+            self.held_by = entry;
+        }
+        if self.held_by.is_some() && self.held_by == entry {
+            return false;
+        }
+        self.held_by = None;
+        true
+    }
+}
+
+/// Applies the schedule entry for the current time of day to the mask state
+pub struct DaySchedule {
+    entries: Vec<(f64, ScheduleEntry)>,  // Minute of the day, sorted by it
+    fade_minutes: f64,
+    applied: ScheduledSettings,          // What was last written to the mask state
+    brightness: DrivenSetting,
+    mouth_emotion: DrivenSetting,
+    current: Option<usize>,              // Entry in effect, for logging
+}
+
+impl DaySchedule {
+    /// Build a schedule from config entries (entries with a bad time are reported and skipped)
+    pub fn new(entries: &[ScheduleEntry], fade_minutes: f64) -> Self {
+        let mut entries: Vec<(f64, ScheduleEntry)> = entries.iter()
+            .filter_map(|entry| match parse_time_of_day(&entry.time) {
+                Some(minute) => Some((minute, entry.clone())),
+                None => {
+                    warn!("⚠️  Schedule: ignoring entry with bad time {:?} (use \"HH:MM\")",
+                          entry.time);
+                    None
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            entries,
+            fade_minutes: fade_minutes.max(0.0),
+            applied: ScheduledSettings::default(),
+            brightness: DrivenSetting::default(),
+            mouth_emotion: DrivenSetting::default(),
+            current: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Settings for a minute of the day (0.0 to 1440.0, fractions for seconds)
+    pub fn settings_at(&self, minute: f64) -> ScheduledSettings {
+        ScheduledSettings {
+            palette: self.held_at(minute, |entry| entry.palette).map(|(_, palette)| palette),
+            brightness: self.faded_at(minute, |entry| entry.brightness.map(|b| b.clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS))),
+            mouth_emotion: self.faded_at(minute, |entry| entry.mouth_emotion.map(|e| e.clamp(-1.0, 1.0))),
+        }
+    }
+
+    /// Write whatever the schedule changed since the last call into the mask state
+    pub fn update(&mut self, minute: f64, state: &Mutex<MaskState>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let current = self.latest_index(minute, |_| true);
+        if current != self.current {
+            if let Some(index) = current {
+                info!("🕒 Schedule: {} settings", self.entries[index].1.time);
+            }
+            self.current = current;
+        }
+
+        let settings = self.settings_at(minute);
+        let mut state = state.lock().unwrap();
+        if settings.palette != self.applied.palette {
+            if let Some(palette) = settings.palette {
+                state.color_palette = palette;
+            }
+        }
+        let brightness_entry = self.latest_index(minute, |entry| entry.brightness.is_some());
+        if self.brightness.may_write(state.brightness, brightness_entry) {
+            if let Some(brightness) = settings.brightness {
+                if Some(brightness) != self.applied.brightness || self.brightness.written.is_none() {
+                    state.set_brightness(brightness);
+                    self.brightness.written = Some(state.brightness);
+                }
+            }
+        }
+        let emotion_entry = self.latest_index(minute, |entry| entry.mouth_emotion.is_some());
+        if self.mouth_emotion.may_write(state.mouth_emotion, emotion_entry) {
+            if let Some(emotion) = settings.mouth_emotion {
+                if Some(emotion) != self.applied.mouth_emotion || self.mouth_emotion.written.is_none() {
+                    state.mouth_emotion = emotion;
+                    self.mouth_emotion.written = Some(emotion);
+                }
+            }
+        }
+        self.applied = settings;
+    }
+
+    // Index of the latest entry at or before `minute` that sets a value, wrapping back to
+    // the day's last such entry before the first one
+    fn latest_index<F: Fn(&ScheduleEntry) -> bool>(&self, minute: f64, sets: F) -> Option<usize> {
+        let candidates = self.entries.iter().enumerate().filter(|(_, (_, entry))| sets(entry));
+        let wrapped = candidates.clone().last().map(|(index, _)| index);
+        candidates.filter(|(_, (at, _))| *at <= minute).last().map(|(index, _)| index)
+            .or(wrapped)
+    }
+
+    // Value from the latest entry that sets it, with the minutes since that entry
+    fn held_at<T, F: Fn(&ScheduleEntry) -> Option<T>>(&self, minute: f64, value: F)
+                                                      -> Option<(f64, T)> {
+        let index = self.latest_index(minute, |entry| value(entry).is_some())?;
+        let (at, entry) = &self.entries[index];
+        Some(((minute - at).rem_euclid(MINUTES_PER_DAY), value(entry)?))
+    }
+
+    // Like held_at, but blending from the previous entry's value during the fade
+    fn faded_at<F: Fn(&ScheduleEntry) -> Option<f64>>(&self, minute: f64, value: F) -> Option<f64> {
+        let (since, target) = self.held_at(minute, &value)?;
+        if since >= self.fade_minutes {
+            return Some(target);
+        }
+        // The value just before this entry took over
+        let before = (minute - since - 1e-6).rem_euclid(MINUTES_PER_DAY);
+        let (_, from) = self.held_at(before, &value)?;
+        Some(from + (target - from) * since / self.fade_minutes)
+    }
+}
+
+/// Minute of the day for "HH:MM" (or "H:MM"), None if it isn't a valid time
+pub fn parse_time_of_day(time: &str) -> Option<f64> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    if hours >= 24 || minutes >= 60 {
+        return None;
+    }
+    Some((hours * 60 + minutes) as f64)
+}
+
+/// Local wall-clock time as (hours, minutes, seconds)
+pub fn local_time() -> (u32, u32, u32) {
+    let now = Local::now();
+    (now.hour(), now.minute(), now.second())
+}
+
+/// Current local minute of the day, with seconds as the fraction
+pub fn local_minute_of_day() -> f64 {
+    let (hours, minutes, seconds) = local_time();
+    (hours * 60 + minutes) as f64 + seconds as f64 / 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: &str, palette: Option<ColorPalette>, brightness: Option<f64>) -> ScheduleEntry {
+        ScheduleEntry { time: time.to_string(), palette, brightness, mouth_emotion: None }
+    }
+
+    #[test]
+    fn schedule_wraps_midnight_holds_and_fades() {
+        let schedule = DaySchedule::new(&[
+            entry("22:00", Some(ColorPalette::Fire), Some(0.2)),
+            entry("08:00", Some(ColorPalette::Rainbow), Some(1.0)),
+            entry("12:00", Some(ColorPalette::Ocean), None),
+            entry("25:00", Some(ColorPalette::Purple), None),  // Skipped
+        ], 30.0);
+
+        // Just after midnight yesterday's 22:00 entry still holds
+        let night = schedule.settings_at(30.0);
+        assert_eq!(night.palette, Some(ColorPalette::Fire));
+        assert_eq!(night.brightness, Some(0.2));
+
+        // Halfway through the 08:00 fade
+        let morning = schedule.settings_at(8.0 * 60.0 + 15.0);
+        assert_eq!(morning.palette, Some(ColorPalette::Rainbow));
+        assert!((morning.brightness.unwrap() - 0.6).abs() < 1e-9);
+
+        // 12:00 changes only the palette, brightness holds from 08:00
+        let noon = schedule.settings_at(13.0 * 60.0);
+        assert_eq!(noon.palette, Some(ColorPalette::Ocean));
+        assert_eq!(noon.brightness, Some(1.0));
+        assert_eq!(noon.mouth_emotion, None);
+
+        // A brightness below the floor (0.0 would blank the face) is held at MIN_BRIGHTNESS
+        let dark = DaySchedule::new(&[entry("20:00", None, Some(0.0))], 0.0);
+        assert_eq!(dark.settings_at(21.0 * 60.0).brightness, Some(MIN_BRIGHTNESS));
+
+        assert_eq!(parse_time_of_day("7:05"), Some(425.0));
+        assert_eq!(parse_time_of_day("07:5"), None);
+        assert_eq!(parse_time_of_day("24:00"), None);
+    }
+
+    #[test]
+    fn hand_changes_hold_through_a_fade_until_the_next_entry() {
+        let mut schedule = DaySchedule::new(&[
+            entry("08:00", None, Some(1.0)),
+            entry("22:00", None, Some(0.2)),
+        ], 30.0);
+        let state = Mutex::new(MaskState::new());
+
+        schedule.update(22.0 * 60.0 + 5.0, &state);
+        let fading = state.lock().unwrap().brightness;
+        assert!(fading < 1.0 && fading > 0.2);
+
+        // Turned up by hand mid-fade: the fade leaves it alone from then on
+        state.lock().unwrap().set_brightness(0.9);
+        schedule.update(22.0 * 60.0 + 10.0, &state);
+        schedule.update(23.0 * 60.0, &state);
+        assert_eq!(state.lock().unwrap().brightness, 0.9);
+
+        // The next entry takes over again
+        schedule.update(8.0 * 60.0 + 30.0, &state);
+        assert_eq!(state.lock().unwrap().brightness, 1.0);
+    }
+}
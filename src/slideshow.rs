@@ -0,0 +1,179 @@
+// Image slideshow
+// Cycles through static images (PNG/JPG) from a directory as a lower-power alternative
+// to video. Each image is letterboxed into a 64x32 VideoFrame so it goes through the
+// same canvas blit path as video playback.
+
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::time::Instant;
+use image::imageops::FilterType;
+use tracing::{info, warn, error};
+
+use crate::video::VideoFrame;
+use crate::{PANEL_WIDTH, PANEL_HEIGHT};
+
+/// Plays a directory of still images with optional auto-advance and crossfade
+pub struct Slideshow {
+    image_files: Vec<PathBuf>,
+    current_index: usize,
+    current_frame: Option<VideoFrame>,
+    previous_frame: Option<VideoFrame>, // Faded out during the crossfade
+    shown_at: Instant,
+    dwell_secs: f64,       // How long each image stays up before auto-advancing
+    crossfade_secs: f64,   // 0.0 = hard cut
+    auto_advance: bool,
+}
+
+impl Slideshow {
+    /// Create a slideshow and scan the images directory
+    pub fn new(images_dir: &str, dwell_secs: f64, crossfade_secs: f64, auto_advance: bool) -> Self {
+        let image_files = Self::scan_image_directory(images_dir);
+
+        if image_files.is_empty() {
+            warn!("⚠️  No image files found in {}", images_dir);
+        } else {
+            info!("🖼️  Found {} image file(s) in {}", image_files.len(), images_dir);
+        }
+
+        Self {
+            image_files,
+            current_index: 0,
+            current_frame: None,
+            previous_frame: None,
+            shown_at: Instant::now(),
+            dwell_secs,
+            crossfade_secs,
+            auto_advance,
+        }
+    }
+
+    /// Scan directory for image files
+    fn scan_image_directory(dir: &str) -> Vec<PathBuf> {
+        let path = Path::new(dir);
+
+        if !path.exists() {
+            info!("📁 Creating images directory: {}", dir);
+            if let Err(e) = fs::create_dir_all(path) {
+                error!("❌ Failed to create directory: {}", e);
+                return Vec::new();
+            }
+        }
+
+        let mut files = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if let Some(ext) = path.extension() {
+                    let ext = ext.to_string_lossy().to_lowercase();
+                    if matches!(ext.as_str(), "png" | "jpg" | "jpeg") {
+                        files.push(path);
+                    }
+                }
+            }
+        }
+
+        // Sort alphabetically for consistent ordering
+        files.sort();
+        files
+    }
+
+    /// Start from the first image
+    pub fn start(&mut self) -> bool {
+        if self.image_files.is_empty() {
+            warn!("⚠️  No images available to show");
+            return false;
+        }
+
+        self.previous_frame = None;
+        self.current_index = 0;
+        self.show(0)
+    }
+
+    /// Crossfade to the next image (wraps around)
+    pub fn next_image(&mut self) -> bool {
+        if self.image_files.is_empty() {
+            return false;
+        }
+
+        self.previous_frame = self.current_frame.take();
+        self.current_index = (self.current_index + 1) % self.image_files.len();
+        self.show(self.current_index)
+    }
+
+    /// Stop the slideshow and free the loaded images
+    pub fn stop(&mut self) {
+        self.current_frame = None;
+        self.previous_frame = None;
+        info!("⏹️  Slideshow stopped");
+    }
+
+    /// Check if an image is currently loaded
+    pub fn is_active(&self) -> bool {
+        self.current_frame.is_some()
+    }
+
+    fn show(&mut self, index: usize) -> bool {
+        let path = &self.image_files[index];
+        match Self::load_image(path) {
+            Ok(frame) => {
+                info!("🖼️  Showing image: {}", path.display());
+                self.current_frame = Some(frame);
+                self.shown_at = Instant::now();
+                true
+            }
+            Err(e) => {
+                error!("❌ Failed to load image {}: {}", path.display(), e);
+                self.current_frame = None;
+                false
+            }
+        }
+    }
+
+    /// Load an image and letterbox it into a panel-sized frame
+    fn load_image(path: &Path) -> Result<VideoFrame, image::ImageError> {
+        let image = image::open(path)?.to_rgb8();
+        let (width, height) = (PANEL_WIDTH as u32, PANEL_HEIGHT as u32);
+
+        // Scale to fit while keeping the aspect ratio, then center on black
+        let scale = (width as f64 / image.width() as f64)
+            .min(height as f64 / image.height() as f64);
+        let scaled_w = ((image.width() as f64 * scale).round() as u32).clamp(1, width);
+        let scaled_h = ((image.height() as f64 * scale).round() as u32).clamp(1, height);
+        let scaled = image::imageops::resize(&image, scaled_w, scaled_h, FilterType::Triangle);
+
+        let offset_x = (width - scaled_w) / 2;
+        let offset_y = (height - scaled_h) / 2;
+        let mut data = vec![0u8; (width * height * 3) as usize];
+        for (x, y, pixel) in scaled.enumerate_pixels() {
+            let idx = (((y + offset_y) * width + (x + offset_x)) * 3) as usize;
+            data[idx..idx + 3].copy_from_slice(&pixel.0);
+        }
+
+        Ok(VideoFrame::from_rgb(width as usize, height as usize, data))
+    }
+
+    /// Get the frame to display, advancing and crossfading as needed
+    pub fn next_frame(&mut self) -> Option<VideoFrame> {
+        if self.current_frame.is_none() {
+            return None;
+        }
+
+        let elapsed = self.shown_at.elapsed().as_secs_f64();
+        if self.auto_advance && self.image_files.len() > 1 && elapsed >= self.dwell_secs {
+            self.next_image();
+            return self.next_frame();
+        }
+
+        let current = self.current_frame.as_ref()?;
+        match &self.previous_frame {
+            Some(previous) if self.crossfade_secs > 0.0 && elapsed < self.crossfade_secs => {
+                Some(previous.blend(current, elapsed / self.crossfade_secs))
+            }
+            _ => {
+                self.previous_frame = None;
+                Some(current.clone())
+            }
+        }
+    }
+}
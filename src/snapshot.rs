@@ -0,0 +1,163 @@
+// Face snapshots
+// Named captures of the live look (eye and mouth style, which elements are shown,
+// palette, brightness, blinking and the post effects) that can be recalled on the fly,
+// like presets made from whatever the face is doing right now. Slots are kept in a
+// JSON state file so they survive a restart (button macros are stored there too).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::color::ColorPalette;
+use tracing::warn;
+
+/// State file the snapshot slots are kept in (working directory, next to the config)
+pub const SNAPSHOT_PATH: &str = "protogen_state.json";
+
+/// Slot saved and recalled from the gamepad (Select + stick clicks)
+pub const QUICK_SLOT: &str = "quick";
+
+// Longest slot name accepted over the HTTP API
+pub const MAX_SLOT_NAME_LEN: usize = 32;
+
+// Held for every read-modify-write of a state file: snapshots are saved on the render
+// thread and macros on the input thread, and neither may drop the other's entry
+static STATE_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Everything needed to put the face back the way it looked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FaceSnapshot {
+    pub eyes: String,
+    pub mouth: String,
+    pub hidden: Vec<String>,  // Elements switched off, every other element is shown
+    pub palette: ColorPalette,
+    pub brightness: f64,
+    pub blink_enabled: bool,
+    pub glitch_enabled: bool,
+    pub night_vision: bool,
+    pub invert: bool,
+}
+
+/// Named snapshot slots, written back to the state file on every save
+#[derive(Debug)]
+pub struct SnapshotStore {
+    path: String,
+    slots: BTreeMap<String, FaceSnapshot>,
+}
+
+impl SnapshotStore {
+    /// Load the slots from a state file; a missing or unreadable file starts empty
+    pub fn load(path: &str) -> Self {
+        let slots = load_state_key(path, "slots")
+            .map(|slots| serde_json::from_value(slots).unwrap_or_else(|e| {
+                warn!("⚠️  Could not read snapshots from {}: {}", path, e);
+                BTreeMap::new()
+            }))
+            .unwrap_or_default();
+        Self { path: path.to_string(), slots }
+    }
+
+    pub fn get(&self, slot: &str) -> Option<&FaceSnapshot> {
+        self.slots.get(slot)
+    }
+
+    /// Slot names, sorted
+    pub fn slots(&self) -> Vec<String> {
+        self.slots.keys().cloned().collect()
+    }
+
+    /// Store a snapshot (replacing any in that slot) and write the state file
+    pub fn save(&mut self, slot: &str, snapshot: FaceSnapshot) -> io::Result<()> {
+        self.slots.insert(slot.to_string(), snapshot);
+        save_state_key(&self.path, "slots", serde_json::to_value(&self.slots)?)
+    }
+}
+
+/// One top-level entry of the state file, None if the file or entry is missing
+/// (or the file can't be read, which is reported)
+pub fn load_state_key(path: &str, key: &str) -> Option<serde_json::Value> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            if e.kind() != ErrorKind::NotFound {
+                warn!("⚠️  Could not read state file {}: {}", path, e);
+            }
+            return None;
+        }
+    };
+    match serde_json::from_str::<serde_json::Value>(&contents) {
+        Ok(mut state) => state.get_mut(key).map(serde_json::Value::take),
+        Err(e) => {
+            warn!("⚠️  Could not parse state file {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Replace one top-level entry of the state file, keeping the others
+/// A file that can't be parsed is an error rather than a fresh start, so one corrupt
+/// write never takes every other entry with it. The new file is written next to the old
+/// one and renamed over it, so a power cut mid-save leaves the previous version intact
+pub fn save_state_key(path: &str, key: &str, value: serde_json::Value) -> io::Result<()> {
+    let _lock = STATE_FILE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut state: serde_json::Map<String, serde_json::Value> = match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+            io::Error::new(ErrorKind::InvalidData,
+                           format!("state file {} is unreadable, not overwriting it: {}", path, e))
+        })?,
+        Err(e) if e.kind() == ErrorKind::NotFound => serde_json::Map::new(),
+        Err(e) => return Err(e),
+    };
+    state.insert(key.to_string(), value);
+    let temp_path = format!("{}.tmp", path);
+    fs::write(&temp_path, serde_json::to_string_pretty(&state)?)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_slots_load_back_from_the_state_file() {
+        let path = std::env::temp_dir().join(format!("protogen_snapshots_{}.json",
+                                                     std::process::id()));
+        let path = path.to_str().unwrap();
+        let snapshot = FaceSnapshot {
+            eyes: "Heart Eyes".to_string(),
+            mouth: "Smile Mouth".to_string(),
+            hidden: vec!["Default Nose".to_string()],
+            palette: ColorPalette::Purple,
+            brightness: 0.6,
+            blink_enabled: false,
+            glitch_enabled: true,
+            night_vision: false,
+            invert: false,
+        };
+
+        let mut store = SnapshotStore::load(path);
+        assert!(store.get("stage").is_none());
+        store.save("stage", snapshot.clone()).unwrap();
+
+        let loaded = SnapshotStore::load(path);
+        let _ = fs::remove_file(path);
+        assert_eq!(loaded.get("stage"), Some(&snapshot));
+        assert_eq!(loaded.slots(), vec!["stage"]);
+    }
+
+    #[test]
+    fn corrupt_state_file_is_not_overwritten() {
+        let path = std::env::temp_dir().join(format!("protogen_state_corrupt_{}.json",
+                                                     std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "{\"slots\": {\"stage\": ").unwrap();
+
+        let result = save_state_key(path, "macros", serde_json::json!({}));
+        let contents = fs::read_to_string(path).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(contents, "{\"slots\": {\"stage\": ");
+    }
+}
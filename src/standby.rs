@@ -0,0 +1,130 @@
+// Quiet standby
+// For a reactive room installation: after `standby_after_secs` without sound or gamepad
+// input the face goes dark (or shows a dim breathing dot) and the render loop drops to
+// a slow poll of the audio level, leaving the CPU mostly idle. Sound above
+// `standby_wake_level` or any button press brings the face straight back, fading up
+// from black over `standby_fade_in_secs`.
+
+use std::time::Duration;
+
+use crate::audio::SILENT_LIMIT;
+use tracing::info;
+
+/// Pause between audio checks while in standby (instead of rendering frames)
+pub const STANDBY_POLL: Duration = Duration::from_millis(100);
+
+// Breathing dot: seconds per breath and peak brightness (0.0 to 1.0)
+const DOT_BREATH_SECS: f64 = 4.0;
+const DOT_MAX_BRIGHTNESS: f64 = 0.15;
+
+/// Tracks quiet time, standby and the fade back up after waking
+pub struct Standby {
+    after_secs: f64,    // Quiet time before standby, 0 = never
+    wake_level: f64,    // Audio level that wakes the face
+    fade_in_secs: f64,
+    dot: bool,          // Breathing dot while in standby, otherwise dark
+    asleep: bool,
+    quiet_secs: f64,    // Time without sound over SILENT_LIMIT or input
+    fade: f64,          // Face brightness multiplier while waking, 0.0 to 1.0
+    dot_phase: f64,     // Seconds into the current breath
+}
+
+impl Standby {
+    pub fn new(after_secs: f64, wake_level: f64, fade_in_secs: f64, dot: bool) -> Self {
+        Self {
+            after_secs: after_secs.max(0.0),
+            wake_level,
+            fade_in_secs: fade_in_secs.max(0.0),
+            dot,
+            asleep: false,
+            quiet_secs: 0.0,
+            fade: 1.0,
+            dot_phase: 0.0,
+        }
+    }
+
+    /// Advance by `dt` with the latest audio level; `woken` is set for gamepad input
+    /// (or anything else that needs the panels). Returns true while in standby
+    pub fn update(&mut self, level: f64, woken: bool, dt: f64) -> bool {
+        if self.after_secs <= 0.0 {
+            return false;
+        }
+        if self.asleep {
+            if !woken && level < self.wake_level {
+                self.dot_phase = (self.dot_phase + dt) % DOT_BREATH_SECS;
+                return true;
+            }
+            info!("☀️  Waking from standby");
+            self.asleep = false;
+            self.quiet_secs = 0.0;
+            self.fade = 0.0;
+        }
+
+        self.fade = if self.fade_in_secs > 0.0 {
+            (self.fade + dt / self.fade_in_secs).min(1.0)
+        } else {
+            1.0
+        };
+        self.quiet_secs = if woken || level > SILENT_LIMIT { 0.0 } else { self.quiet_secs + dt };
+        if self.quiet_secs >= self.after_secs {
+            info!("💤 Standby after {:.0}s of quiet", self.quiet_secs);
+            self.asleep = true;
+            self.dot_phase = 0.0;
+        }
+        self.asleep
+    }
+
+    /// Seconds of quiet left before standby (infinite with standby off)
+    pub fn secs_until_sleep(&self) -> f64 {
+        if self.after_secs <= 0.0 {
+            f64::INFINITY
+        } else {
+            (self.after_secs - self.quiet_secs).max(0.0)
+        }
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    /// Face brightness multiplier, below 1.0 while fading up after waking
+    pub fn fade(&self) -> f64 {
+        self.fade
+    }
+
+    /// Brightness of the breathing dot right now (0.0 when the dot is off)
+    pub fn dot_brightness(&self) -> f64 {
+        if !self.dot {
+            return 0.0;
+        }
+        let breath = 0.5 - 0.5 * (std::f64::consts::TAU * self.dot_phase / DOT_BREATH_SECS).cos();
+        DOT_MAX_BRIGHTNESS * breath
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sleeps_when_quiet_and_fades_up_on_wake() {
+        let mut standby = Standby::new(10.0, 0.3, 1.0, true);
+        for _ in 0..9 {
+            assert!(!standby.update(0.0, false, 1.0));
+        }
+        assert!(standby.update(0.0, false, 1.0));
+
+        // Background noise under the wake level keeps it asleep
+        assert!(standby.update(0.2, false, 1.0));
+        // Loud sound wakes it, fading up from black
+        assert!(!standby.update(0.5, false, 0.5));
+        assert_eq!(standby.fade(), 0.5);
+        assert!(!standby.update(0.0, false, 0.5));
+        assert_eq!(standby.fade(), 1.0);
+
+        // A button press counts as activity, and wakes it too
+        assert!(!standby.update(0.0, true, 1.0));
+        let mut disabled = Standby::new(0.0, 0.3, 1.0, true);
+        assert!(!disabled.update(0.0, false, 100.0));
+    }
+}
@@ -0,0 +1,160 @@
+// MJPEG stream
+// Serves the rendered face as motion JPEG (GET /stream.mjpg on the HTTP API) so OBS
+// can pull the live face with a Media or Browser source, no capture card needed.
+// The render thread only hands over the latest frame's pixels (and only while someone
+// is watching); each viewer has its own thread that scales, encodes and sends frames.
+
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+use image::imageops::{self, FilterType};
+use image::codecs::jpeg::JpegEncoder;
+use image::RgbImage;
+
+use crate::canvas::VirtualLedCanvas;
+
+/// Each LED becomes a block of this many pixels square (128x32 -> 1024x256)
+pub const STREAM_SCALE: u32 = 8;
+
+/// Viewers served at once, later requests get 503
+pub const MAX_VIEWERS: usize = 4;
+
+const JPEG_QUALITY: u8 = 90;
+const BOUNDARY: &str = "frame";
+
+// A viewer with no new frame for this long (face hidden by video, say) gets the last one again
+const FRAME_WAIT: Duration = Duration::from_secs(1);
+
+/// Latest rendered frame, shared between the render thread and the stream viewers
+#[derive(Default)]
+pub struct FrameFeed {
+    frame: Mutex<(u64, Option<Arc<RgbImage>>)>,  // Frame number and pixels
+    new_frame: Condvar,
+    viewers: AtomicUsize,
+}
+
+impl FrameFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hand over a rendered frame (skipped while nobody is watching)
+    pub fn publish(&self, canvas: &VirtualLedCanvas) {
+        if self.viewers.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let image = Arc::new(canvas.to_rgb_image());
+        let mut frame = self.frame.lock().unwrap();
+        frame.0 += 1;
+        frame.1 = Some(image);
+        self.new_frame.notify_all();
+    }
+
+    /// Start a stream for one viewer, None if there are already MAX_VIEWERS
+    pub fn subscribe(self: &Arc<Self>) -> Option<MjpegStream> {
+        let viewers = self.viewers.fetch_add(1, Ordering::Relaxed);
+        if viewers >= MAX_VIEWERS {
+            self.viewers.fetch_sub(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(MjpegStream {
+            feed: self.clone(),
+            last_frame: 0,
+            part: Vec::new(),
+            pos: 0,
+        })
+    }
+
+    // Wait for a frame newer than `last`, or hand back the current one after FRAME_WAIT
+    fn next_frame(&self, last: u64) -> Option<(u64, Arc<RgbImage>)> {
+        let frame = self.frame.lock().unwrap();
+        let (frame, _) = self.new_frame
+            .wait_timeout_while(frame, FRAME_WAIT, |frame| frame.0 == last)
+            .unwrap();
+        frame.1.clone().map(|image| (frame.0, image))
+    }
+}
+
+/// Content-Type of the stream response
+pub fn content_type() -> String {
+    format!("multipart/x-mixed-replace; boundary={}", BOUNDARY)
+}
+
+/// Endless multipart body for one viewer: one JPEG part per rendered frame
+pub struct MjpegStream {
+    feed: Arc<FrameFeed>,
+    last_frame: u64,
+    part: Vec<u8>,  // Part being sent
+    pos: usize,     // Bytes of it already read
+}
+
+impl MjpegStream {
+    fn next_part(&mut self) -> io::Result<()> {
+        let (number, image) = loop {
+            if let Some(frame) = self.feed.next_frame(self.last_frame) {
+                break frame;
+            }
+        };
+        self.last_frame = number;
+
+        let scaled = imageops::resize(image.as_ref(), image.width() * STREAM_SCALE,
+                                      image.height() * STREAM_SCALE, FilterType::Nearest);
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY)
+            .encode_image(&scaled)
+            .map_err(io::Error::other)?;
+
+        self.part.clear();
+        self.part.extend(format!("--{}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+                                 BOUNDARY, jpeg.len()).as_bytes());
+        self.part.extend(jpeg);
+        self.part.extend(b"\r\n");
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl Read for MjpegStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.part.len() {
+            self.next_part()?;
+        }
+        let len = buf.len().min(self.part.len() - self.pos);
+        buf[..len].copy_from_slice(&self.part[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+impl Drop for MjpegStream {
+    fn drop(&mut self) {
+        self.feed.viewers.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewers_get_scaled_jpeg_parts() {
+        let feed = Arc::new(FrameFeed::new());
+        let mut stream = feed.subscribe().unwrap();
+        feed.publish(&VirtualLedCanvas::new(4, 2));
+
+        let mut buf = [0u8; 256];
+        let len = stream.read(&mut buf).unwrap();
+        let head = String::from_utf8_lossy(&buf[..len]);
+        assert!(head.starts_with("--frame\r\nContent-Type: image/jpeg\r\n"));
+
+        let body_start = stream.part.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+        let jpeg = &stream.part[body_start..stream.part.len() - 2];
+        let image = image::load_from_memory(jpeg).unwrap();
+        assert_eq!((image.width(), image.height()), (4 * STREAM_SCALE, 2 * STREAM_SCALE));
+
+        // The viewer count drops again when the stream ends
+        drop(stream);
+        assert_eq!(feed.viewers.load(Ordering::Relaxed), 0);
+    }
+}
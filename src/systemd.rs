@@ -0,0 +1,49 @@
+// systemd service integration
+// With the `systemd` feature enabled, readiness and watchdog keep-alives are sent
+// over sd_notify so a unit with `Type=notify` and `WatchdogSec=` restarts the mask
+// if the render loop stalls. Without the feature these are no-ops.
+
+#[cfg(feature = "systemd")]
+use sd_notify::NotifyState;
+#[cfg(feature = "systemd")]
+use tracing::{debug, warn};
+
+/// Tell systemd that startup finished (hardware initialized)
+pub fn notify_ready() {
+    #[cfg(feature = "systemd")]
+    {
+        if let Err(e) = sd_notify::notify(false, &[NotifyState::Ready]) {
+            warn!("⚠️  sd_notify READY failed: {}", e);
+        }
+        if let Some(interval) = watchdog_interval() {
+            debug!("systemd watchdog enabled ({:?})", interval);
+        }
+    }
+}
+
+/// Keep the systemd watchdog from firing (call once per frame)
+pub fn notify_watchdog() {
+    #[cfg(feature = "systemd")]
+    {
+        // Errors are ignored - this runs every frame and there's nothing to recover
+        let _ = sd_notify::notify(false, &[NotifyState::Watchdog]);
+    }
+}
+
+/// Tell systemd a clean shutdown is in progress
+pub fn notify_stopping() {
+    #[cfg(feature = "systemd")]
+    {
+        let _ = sd_notify::notify(false, &[NotifyState::Stopping]);
+    }
+}
+
+#[cfg(feature = "systemd")]
+fn watchdog_interval() -> Option<std::time::Duration> {
+    let mut usec = 0;
+    if sd_notify::watchdog_enabled(false, &mut usec) {
+        Some(std::time::Duration::from_micros(usec))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,80 @@
+// Face telemetry
+// One snapshot of the face's state shared by every status output (console,
+// HTTP, metrics) so the mode logic is only computed in one place.
+
+use std::fmt;
+use crate::color::ColorPalette;
+use crate::IDLE_TIMEOUT_SECS;
+
+/// What is currently driving the mouth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaceMode {
+    Manual,    // Trigger-controlled mouth
+    Mic,       // Audio-reactive mouth
+    Breathing, // Idle animation (silence or mic muted)
+}
+
+impl FaceMode {
+    /// Work out the mode from the mask controls and time since the last audio
+    pub fn from_inputs(manual_mouth_mode: bool, mic_muted: bool, seconds_since_audio: u64) -> Self {
+        if manual_mouth_mode {
+            FaceMode::Manual
+        } else if mic_muted || seconds_since_audio >= IDLE_TIMEOUT_SECS {
+            FaceMode::Breathing
+        } else {
+            FaceMode::Mic
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            FaceMode::Manual => "MANUAL",
+            FaceMode::Mic => "MIC",
+            FaceMode::Breathing => "BREATHING",
+        }
+    }
+}
+
+/// Snapshot of the face state for status reporting
+#[derive(Debug, Clone)]
+pub struct FaceTelemetry {
+    pub mode: FaceMode,
+    pub audio_level: f64,
+    pub seconds_since_audio: u64,
+    pub brightness: f64,
+    pub palette: ColorPalette,
+    pub mouth_opening: f64,
+    pub active_eyes: String,
+    pub fps: f64,
+    pub cpu_temp: Option<f64>,              // °C, None when it can't be read
+    pub heart_rate: Option<u32>,            // BPM (0 = not connected), None without a monitor
+    pub rng_seed: u64,
+    pub exclusive_element: Option<String>,  // Full-screen effect hiding the face, if any
+    pub reduced_effects: Vec<String>,       // Optional effects dropped by adaptive quality
+    pub profile: Option<String>,            // Profile picked last, if any
+}
+
+impl fmt::Display for FaceTelemetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mode: {} | Audio: {:.4} | Idle: {}s | Mouth: {:.2} | Eyes: {} | Brightness: {:.0}% | Color: {} | FPS: {:.1}",
+               self.mode.name(), self.audio_level, self.seconds_since_audio, self.mouth_opening,
+               self.active_eyes, self.brightness * 100.0, self.palette.name(), self.fps)?;
+        if let Some(celsius) = self.cpu_temp {
+            write!(f, " | CPU: {:.1}°C", celsius)?;
+        }
+        if let Some(bpm) = self.heart_rate {
+            write!(f, " | HR: {} bpm", bpm)?;
+        }
+        write!(f, " | Seed: {}", self.rng_seed)?;
+        if let Some(name) = &self.exclusive_element {
+            write!(f, " | Takeover: {}", name)?;
+        }
+        if let Some(name) = &self.profile {
+            write!(f, " | Profile: {}", name)?;
+        }
+        if !self.reduced_effects.is_empty() {
+            write!(f, " | Reduced: {}", self.reduced_effects.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+// CPU temperature monitor
+// A Pi sealed inside a mask head can get hot enough to throttle and eventually crash.
+// A background thread reads the SoC temperature every 10 seconds for the status line
+// and the thermometer warning on the face.
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+/// Kernel file with the SoC temperature in millidegrees Celsius
+pub const THERMAL_ZONE_PATH: &str = "/sys/class/thermal/thermal_zone0/temp";
+
+/// Temperature (°C) above which the thermometer warning is shown
+pub const TEMP_WARNING_C: f64 = 80.0;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Latest CPU temperature in °C, NaN until the first successful read
+pub type CpuTemperature = Arc<Mutex<f64>>;
+
+/// Start reading the temperature on a background thread (stops after logging once
+/// if the file can't be read, e.g. when not running on a Pi)
+pub fn start_temperature_monitor() -> CpuTemperature {
+    let temperature = Arc::new(Mutex::new(f64::NAN));
+    let shared = temperature.clone();
+    thread::spawn(move || {
+        let mut hot = false;
+        loop {
+            let reading = fs::read_to_string(THERMAL_ZONE_PATH).ok()
+                .and_then(|contents| parse_millidegrees(&contents));
+            let Some(celsius) = reading else {
+                warn!("⚠️  Could not read CPU temperature from {}", THERMAL_ZONE_PATH);
+                return;
+            };
+            *shared.lock().unwrap() = celsius;
+
+            // Log when crossing the warning line, the status line shows it the rest of the time
+            if celsius > TEMP_WARNING_C && !hot {
+                warn!("🌡️  CPU at {:.1}°C, over {:.0}°C", celsius, TEMP_WARNING_C);
+            } else if celsius <= TEMP_WARNING_C && hot {
+                info!("🌡️  CPU back to {:.1}°C", celsius);
+            }
+            hot = celsius > TEMP_WARNING_C;
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+    temperature
+}
+
+/// Degrees Celsius from the thermal zone file contents (e.g. "48312\n")
+pub fn parse_millidegrees(contents: &str) -> Option<f64> {
+    contents.trim().parse::<i64>().ok().map(|milli| milli as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_thermal_zone_contents() {
+        assert_eq!(parse_millidegrees("48312\n"), Some(48.312));
+        assert_eq!(parse_millidegrees("-2500"), Some(-2.5));
+        assert_eq!(parse_millidegrees(""), None);
+    }
+}
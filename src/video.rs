@@ -1,10 +1,51 @@
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::time::Instant;
+use rpi_led_matrix::LedColor;
+use serde::Deserialize;
 use ffmpeg_next as ffmpeg;
 use ffmpeg_next::format::{input, Pixel};
 use ffmpeg_next::media::Type;
 use ffmpeg_next::software::scaling::{context::Context, flag::Flags};
 use ffmpeg_next::util::frame::video::Video;
+use tracing::{info, warn, error};
+
+use crate::canvas::VirtualLedCanvas;
+use crate::PANEL_WIDTH;
+
+// Frame rate assumed when a stream doesn't report one (or reports a nonsense value)
+const DEFAULT_FPS: f64 = 30.0;
+
+// Some containers report their timebase (e.g. 90000/1 or 1000/1) as the frame rate
+const MAX_PLAUSIBLE_FPS: f64 = 240.0;
+
+// Units of container-level seek timestamps (ffmpeg's AV_TIME_BASE, microseconds)
+const SEEK_TIME_BASE: f64 = 1_000_000.0;
+
+// ffmpeg's AV_NOPTS_VALUE, reported when a stream has no start time
+const NO_PTS: i64 = i64::MIN;
+
+// Give up on a file after this many decode errors in a row (corrupt data mid-file)
+const MAX_CONSECUTIVE_DECODE_ERRORS: u32 = 30;
+
+/// What happens when the current clip finishes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EndOfVideoPolicy {
+    Loop,    // Replay the same clip
+    Advance, // Play the next clip in the playlist (wraps around)
+    Stop,    // Stop playback and return to the face
+}
+
+impl EndOfVideoPolicy {
+    pub fn name(&self) -> &str {
+        match self {
+            EndOfVideoPolicy::Loop => "Loop",
+            EndOfVideoPolicy::Advance => "Advance",
+            EndOfVideoPolicy::Stop => "Stop",
+        }
+    }
+}
 
 /// Manages video playback and frame extraction
 pub struct VideoPlayer {
@@ -12,6 +53,7 @@ pub struct VideoPlayer {
     current_video_index: usize,
     video_files: Vec<PathBuf>,
     video_ended: bool,
+    end_policy: EndOfVideoPolicy,
 }
 
 struct VideoContext {
@@ -19,6 +61,85 @@ struct VideoContext {
     decoder: ffmpeg::decoder::Video,
     scaler: Context,
     stream_index: usize,
+    eof_sent: bool, // Demuxer exhausted, decoder is draining buffered frames
+    decode_errors: u32, // Consecutive packets/frames that failed to decode
+    failed: bool,       // Gave up on this file after too many decode errors
+    time_base: f64,                              // Seconds per PTS tick
+    fps: f64,                                    // Source frame rate
+    start_pts: Option<i64>,                      // PTS of the first decoded frame
+    last_frame_time: Option<f64>,                // Presentation time of the last decoded frame
+    started_at: Instant,                         // Wall-clock start of playback (or of the last seek)
+    elapsed_offset: f64,                         // Playback seconds at started_at (the seek target)
+    current_frame: Option<VideoFrame>,           // Frame currently on screen
+    pending_frame: Option<(VideoFrame, f64)>,    // Next decoded frame and its presentation time
+    frame_number: usize,                         // Frames delivered so far (index of the next new frame)
+}
+
+impl VideoContext {
+    /// Decode the next frame along with its presentation time in seconds
+    fn decode_frame(&mut self) -> Option<(VideoFrame, f64)> {
+        loop {
+            // Return any frame the decoder already has buffered
+            let mut decoded = Video::empty();
+            if self.decoder.receive_frame(&mut decoded).is_ok() {
+                let time = self.frame_time(decoded.timestamp());
+                let mut rgb_frame = Video::empty();
+                if self.scaler.run(&decoded, &mut rgb_frame).is_ok() {
+                    self.decode_errors = 0;
+                    return Some((VideoFrame::from_frame(rgb_frame), time));
+                }
+                if self.record_decode_error() {
+                    return None;
+                }
+                continue;
+            }
+
+            // Decoder has nothing left after EOF - every buffered frame was emitted
+            if self.eof_sent {
+                return None;
+            }
+
+            // Feed the decoder more input
+            match self.ictx.packets().next() {
+                Some((stream, packet)) => {
+                    if stream.index() == self.stream_index {
+                        // A bad packet is skipped rather than ending playback
+                        if self.decoder.send_packet(&packet).is_err() && self.record_decode_error() {
+                            return None;
+                        }
+                    }
+                }
+                None => {
+                    // Out of packets: enter draining mode, then keep receiving until empty
+                    self.decoder.send_eof().ok();
+                    self.eof_sent = true;
+                }
+            }
+        }
+    }
+
+    /// Count a decode failure, returns true once the file should be given up on
+    fn record_decode_error(&mut self) -> bool {
+        self.decode_errors += 1;
+        if self.decode_errors >= MAX_CONSECUTIVE_DECODE_ERRORS {
+            self.failed = true;
+        }
+        self.failed
+    }
+
+    /// Convert a frame PTS to seconds since the first frame
+    /// Frames without a PTS are placed one frame duration after the previous one
+    fn frame_time(&mut self, pts: Option<i64>) -> f64 {
+        let time = match pts {
+            Some(pts) => {
+                let start = *self.start_pts.get_or_insert(pts);
+                (pts - start) as f64 * self.time_base
+            }
+            None => self.last_frame_time.map(|t| t + 1.0 / self.fps).unwrap_or(0.0),
+        };
+        self.last_frame_time = Some(time);
+        time
+    }
 }
 
 impl VideoPlayer {
@@ -30,11 +151,11 @@ impl VideoPlayer {
         let video_files = Self::scan_video_directory(videos_dir);
 
         if video_files.is_empty() {
-            println!("⚠️  No video files found in {}", videos_dir);
+            warn!("⚠️  No video files found in {}", videos_dir);
         } else {
-            println!("📹 Found {} video file(s) in {}", video_files.len(), videos_dir);
+            info!("📹 Found {} video file(s) in {}", video_files.len(), videos_dir);
             for (i, file) in video_files.iter().enumerate() {
-                println!("   [{}] {}", i, file.display());
+                info!("   [{}] {}", i, file.display());
             }
         }
 
@@ -43,17 +164,27 @@ impl VideoPlayer {
             current_video_index: 0,
             video_files,
             video_ended: false,
+            end_policy: EndOfVideoPolicy::Stop,
         }
     }
 
+    /// Set what happens when a clip finishes
+    pub fn set_end_policy(&mut self, policy: EndOfVideoPolicy) {
+        self.end_policy = policy;
+    }
+
+    pub fn end_policy(&self) -> EndOfVideoPolicy {
+        self.end_policy
+    }
+
     /// Scan directory for video files
     fn scan_video_directory(dir: &str) -> Vec<PathBuf> {
         let path = Path::new(dir);
 
         if !path.exists() {
-            println!("📁 Creating videos directory: {}", dir);
+            info!("📁 Creating videos directory: {}", dir);
             if let Err(e) = fs::create_dir_all(path) {
-                println!("❌ Failed to create directory: {}", e);
+                error!("❌ Failed to create directory: {}", e);
                 return Vec::new();
             }
         }
@@ -72,6 +203,15 @@ impl VideoPlayer {
             }
         }
 
+        // Probe each file so corrupt or unsupported videos never reach the playlist
+        files.retain(|path| match Self::open_video(path) {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("⚠️  Skipping unplayable video {}: {}", path.display(), e);
+                false
+            }
+        });
+
         // Sort alphabetically for consistent ordering
         files.sort();
         files
@@ -80,12 +220,11 @@ impl VideoPlayer {
     /// Start playing the first video
     pub fn play_first(&mut self) -> bool {
         if self.video_files.is_empty() {
-            println!("❌ No videos available to play");
+            error!("❌ No videos available to play");
             return false;
         }
 
-        self.current_video_index = 0;
-        self.load_video(0)
+        self.load_playable_from(0)
     }
 
     /// Skip to next video
@@ -94,8 +233,49 @@ impl VideoPlayer {
             return false;
         }
 
-        self.current_video_index = (self.current_video_index + 1) % self.video_files.len();
-        self.load_video(self.current_video_index)
+        self.load_playable_from(self.current_video_index + 1)
+    }
+
+    /// Play a video by file name (e.g. "intro.mp4")
+    pub fn play_named(&mut self, name: &str) -> bool {
+        let index = self.video_files.iter()
+            .position(|p| p.file_name().and_then(|n| n.to_str()) == Some(name));
+
+        match index {
+            Some(index) => {
+                self.current_video_index = index;
+                self.load_video(index)
+            }
+            None => {
+                error!("❌ Video not found: {}", name);
+                false
+            }
+        }
+    }
+
+    /// Restart the current video from the beginning
+    pub fn restart(&mut self) -> bool {
+        if self.video_files.is_empty() {
+            return false;
+        }
+
+        self.load_playable_from(self.current_video_index)
+    }
+
+    /// Load the first playable video at or after `start` (wrapping around)
+    /// Files that fail to open are dropped from the playlist, so a bad file
+    /// is skipped instead of stopping playback
+    fn load_playable_from(&mut self, start: usize) -> bool {
+        while !self.video_files.is_empty() {
+            let index = start % self.video_files.len();
+            if self.load_video(index) {
+                self.current_video_index = index;
+                return true;
+            }
+        }
+
+        error!("❌ No playable videos left");
+        false
     }
 
     /// Load a specific video by index
@@ -104,25 +284,41 @@ impl VideoPlayer {
             return false;
         }
 
+        // Drop the previous decoder first so no EOF/drain state carries over
+        self.current_context = None;
+        self.video_ended = false;
+
         let path = &self.video_files[index];
-        println!("🎬 Loading video: {}", path.display());
+        info!("🎬 Loading video: {}", path.display());
 
-        match self.open_video(path) {
+        match Self::open_video(path) {
             Ok(context) => {
+                info!("✅ Video loaded successfully ({:.2} fps)", context.fps);
                 self.current_context = Some(context);
                 self.video_ended = false;
-                println!("✅ Video loaded successfully");
                 true
             }
             Err(e) => {
-                println!("❌ Failed to load video: {}", e);
+                error!("❌ Failed to load video {}: {} (removed from playlist)", path.display(), e);
                 self.current_context = None;
+                self.remove_video(index);
                 false
             }
         }
     }
 
-    fn open_video(&self, path: &Path) -> Result<VideoContext, ffmpeg::Error> {
+    /// Drop a bad file from the playlist, keeping the current index on the same video
+    fn remove_video(&mut self, index: usize) {
+        self.video_files.remove(index);
+        if self.current_video_index > index {
+            self.current_video_index -= 1;
+        }
+        if self.current_video_index >= self.video_files.len() {
+            self.current_video_index = 0;
+        }
+    }
+
+    fn open_video(path: &Path) -> Result<VideoContext, ffmpeg::Error> {
         let ictx = input(&path)?;
 
         let input_stream = ictx
@@ -130,6 +326,16 @@ impl VideoPlayer {
             .best(Type::Video)
             .ok_or(ffmpeg::Error::StreamNotFound)?;
         let stream_index = input_stream.index();
+        let time_base = f64::from(input_stream.time_base());
+        // Known up front so frame times stay correct if the first thing we do is seek
+        let start_pts = Some(input_stream.start_time()).filter(|&t| t != NO_PTS);
+
+        // Prefer the average frame rate, fall back to the container's base rate
+        let fps = [input_stream.avg_frame_rate(), input_stream.rate()].iter()
+            .filter(|r| r.numerator() > 0 && r.denominator() > 0)
+            .map(|r| f64::from(*r))
+            .find(|fps| *fps <= MAX_PLAUSIBLE_FPS)
+            .unwrap_or(DEFAULT_FPS);
 
         let context_decoder = ffmpeg::codec::context::Context::from_parameters(input_stream.parameters())?;
         let decoder = context_decoder.decoder().video()?;
@@ -149,51 +355,122 @@ impl VideoPlayer {
             decoder,
             scaler,
             stream_index,
+            eof_sent: false,
+            decode_errors: 0,
+            failed: false,
+            time_base,
+            fps,
+            start_pts,
+            last_frame_time: None,
+            started_at: Instant::now(),
+            elapsed_offset: 0.0,
+            current_frame: None,
+            pending_frame: None,
+            frame_number: 0,
         })
     }
 
-    /// Get the next frame, scaled to matrix dimensions
+    /// Get the frame to show right now, scaled to matrix dimensions
+    /// Frames are paced by their PTS against wall-clock time since the clip started,
+    /// so the current frame is repeated until the next one is due and late frames are dropped
     pub fn next_frame(&mut self, _width: usize, _height: usize) -> Option<VideoFrame> {
         let context = self.current_context.as_mut()?;
+        let elapsed = context.elapsed_offset + context.started_at.elapsed().as_secs_f64();
 
         loop {
-            match context.ictx.packets().next() {
-                Some((stream, packet)) => {
-                    if stream.index() == context.stream_index {
-                        match context.decoder.send_packet(&packet) {
-                            Ok(_) => {
-                                let mut decoded = Video::empty();
-                                if context.decoder.receive_frame(&mut decoded).is_ok() {
-                                    let mut rgb_frame = Video::empty();
-                                    if context.scaler.run(&decoded, &mut rgb_frame).is_ok() {
-                                        return Some(VideoFrame::from_frame(rgb_frame));
-                                    }
-                                }
-                            }
-                            Err(_) => continue,
+            if context.pending_frame.is_none() {
+                match context.decode_frame() {
+                    Some(decoded) => context.pending_frame = Some(decoded),
+                    None if context.failed => {
+                        // Corrupt file: drop it and move straight on to the next playable one
+                        let index = self.current_video_index;
+                        error!("❌ Too many decode errors in {}, skipping (removed from playlist)",
+                               self.video_files[index].display());
+                        self.current_context = None;
+                        self.remove_video(index);
+                        if self.load_playable_from(index) {
+                            return self.next_frame(_width, _height);
                         }
+                        self.video_ended = true;
+                        return None;
                     }
-                }
-                None => {
-                    // Try to flush decoder
-                    context.decoder.send_eof().ok();
-                    let mut decoded = Video::empty();
-                    if context.decoder.receive_frame(&mut decoded).is_ok() {
-                        let mut rgb_frame = Video::empty();
-                        if context.scaler.run(&decoded, &mut rgb_frame).is_ok() {
-                            return Some(VideoFrame::from_frame(rgb_frame));
+                    None => {
+                        // Show the last frame once more, then report the end
+                        if let Some(frame) = context.current_frame.take() {
+                            return Some(frame);
                         }
+                        self.video_ended = true;
+                        info!("🏁 Video ended");
+                        return None;
                     }
-
-                    // Video ended
-                    self.video_ended = true;
-                    println!("🏁 Video ended");
-                    return None;
                 }
             }
+
+            let due = matches!(&context.pending_frame, Some((_, time)) if *time <= elapsed);
+            if !due {
+                // Next frame isn't due yet - keep showing the current one
+                return match &context.current_frame {
+                    Some(frame) => Some(frame.clone()),
+                    None => context.pending_frame.as_ref().map(|(frame, _)| frame.clone()),
+                };
+            }
+
+            context.current_frame = context.pending_frame.take().map(|(frame, _)| frame);
+            context.frame_number += 1;
         }
     }
 
+    /// Jump to a frame of the current video, returns false if nothing is playing or the seek failed
+    /// Seeks to the keyframe at or before the target, then decodes forward to the exact frame
+    pub fn seek_to_frame(&mut self, frame: usize) -> bool {
+        let Some(context) = self.current_context.as_mut() else {
+            return false;
+        };
+        let target_secs = frame as f64 / context.fps;
+        let target_ts = (target_secs * SEEK_TIME_BASE) as i64
+            + context.start_pts.map(|pts| (pts as f64 * context.time_base * SEEK_TIME_BASE) as i64)
+                .unwrap_or(0);
+
+        if let Err(e) = context.ictx.seek(target_ts, ..=target_ts) {
+            warn!("⚠️  Could not seek to frame {}: {}", frame, e);
+            return false;
+        }
+        context.decoder.flush();
+        context.eof_sent = false;
+        context.current_frame = None;
+        context.pending_frame = None;
+        context.last_frame_time = None;
+
+        // Skip frames between the keyframe and the target (half a frame of slack for rounding)
+        let half_frame = 0.5 / context.fps;
+        while let Some((decoded, time)) = context.decode_frame() {
+            if time + half_frame >= target_secs {
+                context.pending_frame = Some((decoded, time));
+                break;
+            }
+        }
+
+        // Restart the pacing clock as if playback had reached the target normally
+        // (kept as an offset: backdating an Instant fails while the clock is younger
+        // than the target, e.g. shortly after boot)
+        context.started_at = Instant::now();
+        context.elapsed_offset = target_secs;
+        context.frame_number = frame;
+        self.video_ended = false;
+        info!("⏩ Seeked to frame {}", frame);
+        true
+    }
+
+    /// Number of frames delivered from the current video (the frame index after a seek)
+    pub fn current_frame(&self) -> usize {
+        self.current_context.as_ref().map(|c| c.frame_number).unwrap_or(0)
+    }
+
+    /// Frame rate of the current video as reported by its stream
+    pub fn source_fps(&self) -> Option<f64> {
+        self.current_context.as_ref().map(|c| c.fps)
+    }
+
     /// Check if current video has ended
     pub fn has_ended(&self) -> bool {
         self.video_ended
@@ -203,7 +480,7 @@ impl VideoPlayer {
     pub fn stop(&mut self) {
         self.current_context = None;
         self.video_ended = false;
-        println!("⏹️  Video playback stopped");
+        info!("⏹️  Video playback stopped");
     }
 
     /// Check if a video is currently loaded
@@ -222,6 +499,7 @@ impl VideoPlayer {
 }
 
 /// A single video frame scaled to display dimensions
+#[derive(Clone)]
 pub struct VideoFrame {
     pub width: usize,
     pub height: usize,
@@ -244,6 +522,51 @@ impl VideoFrame {
         }
     }
 
+    /// Build a frame from packed RGB24 data
+    pub fn from_rgb(width: usize, height: usize, data: Vec<u8>) -> Self {
+        VideoFrame {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Linear blend toward another frame of the same size (amount 0.0 = self, 1.0 = other)
+    pub fn blend(&self, other: &VideoFrame, amount: f64) -> VideoFrame {
+        let amount = amount.clamp(0.0, 1.0);
+        let data = self.data.iter()
+            .zip(other.data.iter())
+            .map(|(&a, &b)| (a as f64 + (b as f64 - a as f64) * amount).round() as u8)
+            .collect();
+
+        VideoFrame {
+            width: other.width,
+            height: other.height,
+            data,
+        }
+    }
+
+    /// Alpha-composite the frame over whatever is already on the canvas, on both panels
+    /// (alpha 1.0 = opaque video, 0.0 = canvas unchanged)
+    pub fn overlay_on_canvas(&self, canvas: &mut VirtualLedCanvas, alpha: f64, brightness: f64) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let video_weight = alpha * brightness.clamp(0.0, 1.0);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (r, g, b) = self.get_pixel(x, y);
+                for panel_x in [x as i32, x as i32 + PANEL_WIDTH] {
+                    let (br, bg, bb) = canvas.get(panel_x, y as i32);
+                    let mix = |video: u8, below: u8| {
+                        (video as f64 * video_weight + below as f64 * (1.0 - alpha)).round() as u8
+                    };
+                    canvas.set(panel_x, y as i32,
+                               &LedColor { red: mix(r, br), green: mix(g, bg), blue: mix(b, bb) });
+                }
+            }
+        }
+    }
+
     /// Get RGB color at pixel position
     pub fn get_pixel(&self, x: usize, y: usize) -> (u8, u8, u8) {
         if x >= self.width || y >= self.height {
@@ -262,3 +585,72 @@ impl VideoFrame {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Start of an MP4 'ftyp' box, cut off well before any media data
+    const TRUNCATED_MP4: &[u8] = b"\x00\x00\x00\x18ftypmp42\x00\x00\x00\x00mp42is";
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("protogen_video_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn overlay_blends_video_with_the_canvas_by_alpha() {
+        let frame = VideoFrame::from_rgb(1, 1, vec![200, 100, 0]);
+        let face = LedColor { red: 0, green: 100, blue: 200 };
+
+        let mut canvas = VirtualLedCanvas::new(PANEL_WIDTH * 2, 1);
+        canvas.set(0, 0, &face);
+        frame.overlay_on_canvas(&mut canvas, 0.0, 1.0);
+        assert_eq!(canvas.get(0, 0), (0, 100, 200));
+
+        frame.overlay_on_canvas(&mut canvas, 0.5, 1.0);
+        assert_eq!(canvas.get(0, 0), (100, 100, 100));
+        assert_eq!(canvas.get(PANEL_WIDTH, 0), (100, 50, 0));
+
+        frame.overlay_on_canvas(&mut canvas, 1.0, 1.0);
+        assert_eq!(canvas.get(0, 0), (200, 100, 0));
+    }
+
+    #[test]
+    fn truncated_video_is_skipped_during_scan() {
+        let dir = scratch_dir("scan");
+        fs::write(dir.join("broken.mp4"), TRUNCATED_MP4).unwrap();
+
+        let mut player = VideoPlayer::new(dir.to_str().unwrap());
+        assert!(player.video_files.is_empty());
+        assert!(!player.play_first());
+        assert!(!player.is_playing());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn unloadable_video_is_dropped_from_playlist() {
+        let dir = scratch_dir("load");
+        let broken = dir.join("broken.mp4");
+        fs::write(&broken, TRUNCATED_MP4).unwrap();
+
+        // File got past the scan (e.g. replaced after startup) but can't be opened
+        let mut player = VideoPlayer {
+            current_context: None,
+            current_video_index: 0,
+            video_files: vec![broken],
+            video_ended: false,
+            end_policy: EndOfVideoPolicy::Advance,
+        };
+        assert!(!player.play_first());
+        assert!(player.video_files.is_empty());
+        assert!(!player.next_video());
+        assert!(!player.is_playing());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
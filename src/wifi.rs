@@ -0,0 +1,88 @@
+// Wi-Fi signal monitor
+// Reads the link's signal level from /proc/net/wireless every 5 seconds for the signal
+// bars on the face, so it's easy to tell from inside the suit whether the phone app
+// (HTTP API) or OSC controller is likely to reach the mask.
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use tracing::warn;
+
+/// Kernel file listing wireless interfaces and their signal levels
+pub const WIRELESS_PATH: &str = "/proc/net/wireless";
+
+/// Number of bars in the signal icon
+pub const SIGNAL_BARS: u8 = 4;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Weakest signal (dBm) for each bar count from 4 down to 1, as on phones
+const BAR_THRESHOLDS_DBM: [i32; 4] = [-55, -65, -75, -85];
+
+/// Latest signal level in dBm, None while not connected (or not read yet)
+pub type WifiSignal = Arc<Mutex<Option<i32>>>;
+
+/// Start reading the signal on a background thread (stops after logging once if the
+/// file can't be read, e.g. on a machine without wireless drivers)
+pub fn start_wifi_monitor() -> WifiSignal {
+    let signal = Arc::new(Mutex::new(None));
+    let shared = signal.clone();
+    thread::spawn(move || loop {
+        let contents = match fs::read_to_string(WIRELESS_PATH) {
+            Ok(contents) => contents,
+            Err(e) => {
+                warn!("⚠️  Could not read Wi-Fi signal from {}: {}", WIRELESS_PATH, e);
+                return;
+            }
+        };
+        *shared.lock().unwrap() = parse_signal_dbm(&contents);
+        thread::sleep(POLL_INTERVAL);
+    });
+    signal
+}
+
+/// Signal level (dBm) of the first interface in /proc/net/wireless, None if none is listed
+pub fn parse_signal_dbm(contents: &str) -> Option<i32> {
+    // Two header lines, then "wlan0: 0000   70.  -40.  -256 ..." (status, link, level, noise)
+    let line = contents.lines().skip(2).find(|line| line.contains(':'))?;
+    let (_, fields) = line.split_once(':')?;
+    let level: f64 = fields.split_whitespace().nth(2)?.trim_end_matches('.').parse().ok()?;
+    let level = level as i32;
+    // Some drivers report the level as an unsigned byte (216 for -40 dBm)
+    Some(if level > 0 { level - 256 } else { level })
+}
+
+/// Filled bars (0 to SIGNAL_BARS) for a signal level, 0 when not connected
+pub fn signal_bars(dbm: Option<i32>) -> u8 {
+    let Some(dbm) = dbm else {
+        return 0;
+    };
+    let weaker = BAR_THRESHOLDS_DBM.iter().filter(|&&threshold| dbm < threshold).count();
+    SIGNAL_BARS - weaker as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIRELESS: &str = "Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE\n \
+                            face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22\n";
+
+    #[test]
+    fn parses_levels_into_bars() {
+        let connected = format!("{} wlan0: 0000   70.  -48.  -256        0      0      0      0      0        0\n",
+                                WIRELESS);
+        assert_eq!(parse_signal_dbm(&connected), Some(-48));
+        let unsigned = format!("{} wlan0: 0000   40.  186.  0        0      0      0      0      0        0\n",
+                               WIRELESS);
+        assert_eq!(parse_signal_dbm(&unsigned), Some(-70));
+        assert_eq!(parse_signal_dbm(WIRELESS), None);
+
+        assert_eq!(signal_bars(Some(-48)), 4);
+        assert_eq!(signal_bars(Some(-70)), 2);
+        assert_eq!(signal_bars(Some(-90)), 0);
+        assert_eq!(signal_bars(None), 0);
+    }
+}